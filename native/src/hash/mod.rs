@@ -4,7 +4,11 @@
 //! - `HashFn`: a simple trait for 256-bit hash functions with streaming update.
 //! - A default `Blake3Hash` implementation (pure Rust, fast).
 //! - Domain-separation tags (`DsTag`) to avoid cross-protocol collisions.
-//! - Small helper functions for one-shot / multi-part hashing.
+//! - Small helper functions for one-shot / multi-part hashing, plus
+//!   `double_hash_ds` / `double_hash_digests` for `H(H(x))` interop formats.
+//! - Hiding/binding commitments for commit-reveal (`commit`, `open`).
+//! - BLAKE3 tree roots plus chunk-inclusion proofs over BLAKE3's own Merkle
+//!   tree (`blake3_tree_root`, `blake3_prove_chunk`, `blake3_verify_chunk`).
 //!
 //! ### Why domain separation?
 //! To keep different data families (txs, headers, proofs, etc.) from
@@ -65,6 +69,8 @@ pub enum DsTag {
     Zk,
     /// Capability binding helpers (cross-subsystem).
     Capability,
+    /// Commit-reveal commitments (`hash::commit`).
+    Commitment,
 }
 
 impl DsTag {
@@ -90,6 +96,7 @@ impl DsTag {
             DsTag::Explorer      => "animica:v1:explorer",
             DsTag::Zk            => "animica:v1:zk",
             DsTag::Capability    => "animica:v1:capability",
+            DsTag::Commitment    => "animica:v1:commitment",
         }
     }
 }
@@ -199,6 +206,163 @@ pub fn hash_digests(tag: DsTag, items: &[Digest32]) -> Digest32 {
     h.finalize()
 }
 
+/// Double hash `H(H(data))`, both passes under `tag`.
+///
+/// BLAKE3 is not length-extendable, so unlike Bitcoin's SHA-256d this adds no
+/// security; it exists only to match interop formats that specify the
+/// double-hash construction.
+#[inline]
+pub fn double_hash_ds(tag: DsTag, data: &[u8]) -> Digest32 {
+    hash_ds(tag, &hash_ds(tag, data))
+}
+
+/// Double-hash variant of [`hash_digests`]: `H(H(items[0] || items[1] || ...))`.
+#[inline]
+pub fn double_hash_digests(tag: DsTag, items: &[Digest32]) -> Digest32 {
+    hash_ds(tag, &hash_digests(tag, items))
+}
+
+/* ------------------------------ Commitments ------------------------------ */
+
+/// Commit to `value` with blinding `randomness`.
+///
+/// Computes `H_commitment(len(value) as u64 LE || value || randomness)`. The
+/// length prefix fixes where `value` ends, so no other `(value, randomness)`
+/// split of the same bytes opens the commitment.
+pub fn commit(value: &[u8], randomness: &[u8]) -> Digest32 {
+    let len = (value.len() as u64).to_le_bytes();
+    hash_many(DsTag::Commitment, [&len[..], value, randomness])
+}
+
+/// Check that `(value, randomness)` opens `commitment` (constant-time compare).
+pub fn open(commitment: &Digest32, value: &[u8], randomness: &[u8]) -> bool {
+    crate::utils::ct_eq(&commit(value, randomness), commitment)
+}
+
+/* ---------------------------- BLAKE3 tree proofs ---------------------------- */
+
+/// BLAKE3 chunk size in bytes. Inputs are split into 1 KiB chunks which form
+/// the leaves of BLAKE3's internal binary Merkle tree; only the final chunk
+/// may be shorter.
+pub const BLAKE3_CHUNK_LEN: usize = ::blake3::CHUNK_LEN;
+
+/// Root of BLAKE3's internal Merkle tree over `data`.
+///
+/// This is exactly `blake3::hash(data)`; it is named separately so callers that
+/// pair it with [`blake3_prove_chunk`] make the intended relationship explicit.
+#[inline]
+pub fn blake3_tree_root(data: &[u8]) -> Digest32 {
+    *::blake3::hash(data).as_bytes()
+}
+
+/// Inclusion proof for one 1 KiB chunk against a [`blake3_tree_root`].
+///
+/// The tree shape is fully determined by `total_len`, so only the sibling
+/// chaining values are carried (ordered leaf → root); their left/right side is
+/// recomputed during verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Blake3ChunkProof {
+    /// Index of the proven chunk (`offset / BLAKE3_CHUNK_LEN`).
+    pub chunk_index: u64,
+    /// Length in bytes of the whole hashed input.
+    pub total_len: u64,
+    /// Sibling chaining values from the chunk's parent level up to the root.
+    pub siblings: Vec<Digest32>,
+}
+
+/// Number of chunks BLAKE3 splits an input of `total_len` bytes into (at least 1).
+#[inline]
+fn blake3_chunk_count(total_len: u64) -> u64 {
+    total_len.div_ceil(BLAKE3_CHUNK_LEN as u64).max(1)
+}
+
+/// Walk from the root down to `chunk_index`, returning for every level
+/// (root first) whether the path goes left, plus the sibling subtree's
+/// `(offset, len)`.
+fn blake3_path(total_len: u64, chunk_index: u64) -> Vec<(bool, u64, u64)> {
+    use ::blake3::hazmat::left_subtree_len;
+
+    let target = chunk_index * BLAKE3_CHUNK_LEN as u64;
+    let (mut off, mut len) = (0u64, total_len);
+    let mut path = Vec::new();
+    while len > BLAKE3_CHUNK_LEN as u64 {
+        let left = left_subtree_len(len);
+        if target < off + left {
+            path.push((true, off + left, len - left));
+            len = left;
+        } else {
+            path.push((false, off, left));
+            off += left;
+            len -= left;
+        }
+    }
+    path
+}
+
+/// Chaining value of the subtree covering `data`, which starts at byte `offset`.
+fn blake3_subtree_cv(offset: u64, data: &[u8]) -> Digest32 {
+    use ::blake3::hazmat::HasherExt;
+
+    ::blake3::Hasher::new()
+        .set_input_offset(offset)
+        .update(data)
+        .finalize_non_root()
+}
+
+/// Build an inclusion proof for chunk `chunk_index` of `data`.
+///
+/// Returns `None` if the index is out of range.
+pub fn blake3_prove_chunk(data: &[u8], chunk_index: u64) -> Option<Blake3ChunkProof> {
+    let total_len = data.len() as u64;
+    if chunk_index >= blake3_chunk_count(total_len) {
+        return None;
+    }
+    let mut siblings: Vec<Digest32> = blake3_path(total_len, chunk_index)
+        .into_iter()
+        .map(|(_, off, len)| blake3_subtree_cv(off, &data[off as usize..(off + len) as usize]))
+        .collect();
+    siblings.reverse();
+    Some(Blake3ChunkProof { chunk_index, total_len, siblings })
+}
+
+/// Verify that `chunk` is chunk `proof.chunk_index` of the input hashed to `root`.
+///
+/// The chunk must have its exact in-file length (1 KiB, or the remainder for
+/// the final chunk).
+pub fn blake3_verify_chunk(root: &Digest32, chunk: &[u8], proof: &Blake3ChunkProof) -> bool {
+    use ::blake3::hazmat::{merge_subtrees_non_root, merge_subtrees_root, Mode};
+
+    let count = blake3_chunk_count(proof.total_len);
+    if proof.chunk_index >= count {
+        return false;
+    }
+    let start = proof.chunk_index * BLAKE3_CHUNK_LEN as u64;
+    let expected_len = (proof.total_len - start).min(BLAKE3_CHUNK_LEN as u64);
+    if chunk.len() as u64 != expected_len {
+        return false;
+    }
+
+    let path = blake3_path(proof.total_len, proof.chunk_index);
+    if path.len() != proof.siblings.len() {
+        return false;
+    }
+    if path.is_empty() {
+        // Single-chunk input: the chunk itself is the root node.
+        return blake3_tree_root(chunk) == *root;
+    }
+
+    let mut cv = blake3_subtree_cv(start, chunk);
+    // `path` is root-first, `siblings` leaf-first.
+    for (depth, ((went_left, _, _), sib)) in path.iter().rev().zip(&proof.siblings).enumerate() {
+        let (l, r) = if *went_left { (&cv, sib) } else { (sib, &cv) };
+        if depth + 1 == path.len() {
+            return merge_subtrees_root(l, r, Mode::Hash).as_bytes() == root;
+        }
+        cv = merge_subtrees_non_root(l, r, Mode::Hash);
+    }
+    unreachable!("loop returns at the root level")
+}
+
 /* ------------------------------ Python bindings ------------------------------ */
 
 #[cfg(feature = "python")]
@@ -225,6 +389,7 @@ mod py {
             "explorer"      => DsTag::Explorer,
             "zk"            => DsTag::Zk,
             "capability"    => DsTag::Capability,
+            "commitment"    => DsTag::Commitment,
             _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("unknown DsTag: {tag}")))
         };
         Ok(t)
@@ -289,6 +454,20 @@ mod tests {
         assert_eq!(one, cat);
     }
 
+    #[test]
+    fn commitment_opens_only_with_original_split() {
+        let value = b"bid:42";
+        let r = [0x5au8; 32];
+        let c = commit(value, &r);
+        assert!(open(&c, value, &r));
+        assert!(!open(&c, b"bid:43", &r));
+        assert!(!open(&c, value, &[0u8; 32]));
+        // Swapped inputs, and a shifted boundary over the same bytes, must fail.
+        assert!(!open(&c, &r, value));
+        assert!(!open(&c, b"bid:4", &[b"2" as &[u8], &r].concat()));
+        assert_ne!(c, blake3_256_ds(DsTag::Generic, &[&value[..], &r].concat()));
+    }
+
     #[test]
     fn hash_digests_is_stable_concat() {
         let x = blake3_256_ds(DsTag::Generic, b"x");
@@ -305,6 +484,43 @@ mod tests {
 
         assert_eq!(via_helper, via_manual);
     }
+
+    #[test]
+    fn double_hash_applies_hash_twice() {
+        let once = hash_ds(DsTag::Tx, b"payload");
+        assert_eq!(double_hash_ds(DsTag::Tx, b"payload"), hash_ds(DsTag::Tx, &once));
+        assert_ne!(double_hash_ds(DsTag::Tx, b"payload"), once);
+
+        let items = [once, hash_ds(DsTag::Tx, b"other")];
+        let inner = hash_digests(DsTag::Nmt, &items);
+        assert_eq!(double_hash_digests(DsTag::Nmt, &items), hash_ds(DsTag::Nmt, &inner));
+    }
+
+    #[test]
+    fn blake3_chunk_inclusion_proof() {
+        let data: Vec<u8> = (0..(5 * BLAKE3_CHUNK_LEN + 300)).map(|i| (i * 31 % 251) as u8).collect();
+        let root = blake3_tree_root(&data);
+        assert_eq!(root, *::blake3::hash(&data).as_bytes());
+
+        let chunk = |i: usize| &data[i * BLAKE3_CHUNK_LEN..((i + 1) * BLAKE3_CHUNK_LEN).min(data.len())];
+        for i in 0..6u64 {
+            let proof = blake3_prove_chunk(&data, i).unwrap();
+            assert!(blake3_verify_chunk(&root, chunk(i as usize), &proof), "chunk {i}");
+        }
+
+        let proof = blake3_prove_chunk(&data, 3).unwrap();
+        let mut tampered = chunk(3).to_vec();
+        tampered[0] ^= 1;
+        assert!(!blake3_verify_chunk(&root, &tampered, &proof));
+        assert!(!blake3_verify_chunk(&root, chunk(2), &proof));
+        assert!(blake3_prove_chunk(&data, 6).is_none());
+
+        // Single-chunk input proves against the plain hash.
+        let small = b"tiny";
+        let p = blake3_prove_chunk(small, 0).unwrap();
+        assert!(p.siblings.is_empty());
+        assert!(blake3_verify_chunk(&blake3_tree_root(small), small, &p));
+    }
 }
 
 /* ------------------------- Legacy-style BLAKE3 API ------------------------- */
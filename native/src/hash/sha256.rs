@@ -224,6 +224,7 @@ mod py {
             "explorer" => Ok(DsTag::Explorer),
             "zk" => Ok(DsTag::Zk),
             "capability" => Ok(DsTag::Capability),
+            "commitment" => Ok(DsTag::Commitment),
             _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
                 "unknown DsTag: {tag}"
             ))),
@@ -64,6 +64,14 @@ pub fn blake3_hash(input: &[u8]) -> [u8; 32] {
     *hash.as_bytes()
 }
 
+/// Compute the BLAKE3 keyed hash (32-byte MAC) of `input` under `key`.
+///
+/// Unlike the domain-separated `derive_key` hashing in [`hash`], this is
+/// BLAKE3's keyed mode: a true MAC whose key must stay secret.
+pub fn blake3_keyed(key: &[u8; 32], input: &[u8]) -> [u8; 32] {
+    *blake3::keyed_hash(key, input).as_bytes()
+}
+
 /// Compute SHA3-256 hash (32 bytes).
 ///
 /// If `c_keccak` is enabled, a C backend can be wired via build.rs; this function
@@ -91,15 +99,15 @@ pub fn blake3_hash_chunks<'a, I>(chunks: I) -> Vec<[u8; 32]>
 where
     I: IntoIterator<Item = &'a [u8]>,
 {
-    let iter = chunks.into_iter();
-
     #[cfg(feature = "rayon")]
     {
-        return iter.par_bridge().map(blake3_hash).collect();
+        // Collect first so the parallel map is indexed and keeps input order.
+        let chunks: Vec<&[u8]> = chunks.into_iter().collect();
+        utils::rayon_pool::install(|| chunks.par_iter().map(|c| blake3_hash(c)).collect())
     }
 
-    #[allow(unreachable_code)]
-    iter.map(blake3_hash).collect()
+    #[cfg(not(feature = "rayon"))]
+    chunks.into_iter().map(blake3_hash).collect()
 }
 
 //
@@ -152,6 +160,9 @@ pub fn enabled_features() -> &'static [&'static str] {
 //
 
 /// FFI return codes (mirrored as `i32`).
+///
+/// Every fallible FFI function returns `OK` or one of these positive codes;
+/// results (such as `animica_ct_eq`'s verdict) go through out-pointers.
 pub mod ffi_codes {
     pub const OK: i32 = 0;
     pub const INVALID_ARGUMENT: i32 = 1;
@@ -181,6 +192,10 @@ fn check_nonnull<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], Error> {
     if ptr.is_null() && len > 0 {
         return Err(Error::InvalidArgument("null input pointer with nonzero length"));
     }
+    if len == 0 {
+        // `from_raw_parts` requires non-null even for empty slices.
+        return Ok(&[]);
+    }
     // SAFETY: caller promises `ptr` is valid for `len` bytes; we guard null + len>0 above.
     let slice = unsafe { slice::from_raw_parts(ptr, len) };
     Ok(slice)
@@ -242,9 +257,101 @@ pub extern "C" fn animica_sha3_256(
     result_to_code(r)
 }
 
+/// Compute the BLAKE3 keyed hash (MAC) of `data` under a 32-byte key -> 32 bytes.
+///
+/// Returns `INVALID_ARGUMENT` unless `key_len == 32`.
+///
+/// # Safety
+/// - `key32` must be a valid pointer to `key_len` bytes.
+/// - `data` must be either NULL with `len==0` or a valid pointer to `len` bytes.
+/// - `out32` must point to a buffer of at least 32 bytes.
+#[no_mangle]
+pub extern "C" fn animica_blake3_keyed(
+    key32: *const u8,
+    key_len: usize,
+    data: *const u8,
+    len: usize,
+    out32: *mut u8,
+    out_len: usize,
+) -> i32 {
+    let r = (|| {
+        if key32.is_null() || key_len != 32 {
+            return Err(Error::InvalidArgument("key must be exactly 32 bytes"));
+        }
+        let key: &[u8; 32] = check_nonnull(key32, key_len)?
+            .try_into()
+            .map_err(|_| Error::InvalidArgument("key must be exactly 32 bytes"))?;
+        let input = check_nonnull(data, len)?;
+        let out = check_outbuf(out32, out_len, 32)?;
+        out.copy_from_slice(&blake3_keyed(key, input));
+        Ok(())
+    })();
+    result_to_code(r)
+}
+
+/// BLAKE3 extendable output: fill `out[..out_len]` from BLAKE3's XOF over `data`.
+///
+/// The first 32 bytes equal `animica_blake3_hash`; longer outputs extend the
+/// same stream, so callers can use this as a simple variable-length KDF.
+///
+/// # Safety
+/// - `data` must be either NULL with `len==0` or a valid pointer to `len` bytes.
+/// - `out` must be either NULL with `out_len==0` or valid for `out_len` bytes.
+#[no_mangle]
+pub extern "C" fn animica_blake3_xof(
+    data: *const u8,
+    len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> i32 {
+    let r = (|| {
+        let input = check_nonnull(data, len)?;
+        if out_len == 0 {
+            return Ok(());
+        }
+        let out = check_outbuf(out, out_len, out_len)?;
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(input);
+        hasher.finalize_xof().fill(out);
+        Ok(())
+    })();
+    result_to_code(r)
+}
+
+/// Constant-time equality of two byte buffers (MACs, commitments, digests).
+///
+/// Writes `1` to `*out_eq` if equal, `0` if not (including length mismatch),
+/// and returns `OK`; `INVALID_ARGUMENT` if either input pointer is NULL with a
+/// nonzero length or `out_eq` is NULL (`*out_eq` is then left untouched).
+///
+/// # Safety
+/// - `a`/`b` must each be either NULL with len==0 or valid for their length.
+/// - `out_eq` must point to one writable byte.
+/// - Runtime depends only on the lengths, not on the contents.
+#[no_mangle]
+pub extern "C" fn animica_ct_eq(
+    a: *const u8,
+    a_len: usize,
+    b: *const u8,
+    b_len: usize,
+    out_eq: *mut u8,
+) -> i32 {
+    let r = (|| {
+        let a = check_nonnull(a, a_len)?;
+        let b = check_nonnull(b, b_len)?;
+        let out = check_outbuf(out_eq, 1, 1)?;
+        out[0] = crate::utils::ct_eq(a, b) as u8;
+        Ok(())
+    })();
+    result_to_code(r)
+}
+
 /// Return a bitset of enabled features for quick probing.
 ///
 /// Bit layout (LSB->MSB): 0:simd, 1:rayon, 2:isal, 3:c_keccak, 4:python
+///
+/// These are compile-time features; runtime CPU capabilities come from
+/// `animica_cpu_features` (0:avx2, 1:sha_ni, 2:neon, 3:sha2, 4:avx512f, 5:ssse3).
 #[no_mangle]
 pub extern "C" fn animica_features_mask() -> u32 {
     let mut m = 0u32;
@@ -271,6 +378,119 @@ pub extern "C" fn animica_features_mask() -> u32 {
     m
 }
 
+/// Return a bitset of CPU capabilities detected at runtime (cached), so
+/// native loaders can pick code paths. Independent of compile-time features;
+/// see `animica_features_mask` for those.
+///
+/// Bit layout (LSB->MSB): 0:avx2, 1:sha_ni, 2:neon, 3:sha2, 4:avx512f, 5:ssse3
+#[no_mangle]
+pub extern "C" fn animica_cpu_features() -> u32 {
+    let f = utils::cpu_features();
+    [f.avx2, f.sha_ni, f.neon, f.sha2, f.avx512f, f.ssse3]
+        .iter()
+        .enumerate()
+        .fold(0u32, |m, (bit, &on)| m | (u32::from(on) << bit))
+}
+
+fn rs_to_error(e: rs::RsError) -> Error {
+    use rs::RsError;
+    match e {
+        RsError::InvalidArg(msg) => Error::InvalidArgument(msg),
+        RsError::ShardLenMismatch => Error::InvalidArgument("all shards must have identical length"),
+        RsError::NotEnoughShards => Error::InvalidArgument("fewer than k shards present"),
+        RsError::VerifyFailed => Error::CryptoError("reconstructed shards fail parity verification"),
+        RsError::BackendError(_) => Error::Internal("reed-solomon backend error"),
+    }
+}
+
+/// Borrow the contiguous `(k + m) * shard_len` shard buffer used by the RS entrypoints.
+fn rs_shard_buf<'a>(
+    params: rs::RsParams,
+    shards: *mut u8,
+    shard_len: usize,
+    buf_len: usize,
+) -> Result<&'a mut [u8], Error> {
+    params.validate().map_err(rs_to_error)?;
+    if shard_len == 0 {
+        return Err(Error::InvalidArgument("shard length must be > 0"));
+    }
+    let needed = params
+        .total()
+        .checked_mul(shard_len)
+        .ok_or(Error::InvalidArgument("shard buffer size overflows usize"))?;
+    check_outbuf(shards, buf_len, needed)
+}
+
+/// Systematic Reed–Solomon encode over a contiguous shard buffer.
+///
+/// `shards` holds `k + m` shards of `shard_len` bytes back to back; the first
+/// `k` are data (read), the last `m` are parity (overwritten).
+///
+/// # Safety
+/// - `shards` must be valid for `buf_len` bytes, with `buf_len >= (k + m) * shard_len`.
+#[no_mangle]
+pub extern "C" fn animica_rs_encode(
+    k: u32,
+    m: u32,
+    shards: *mut u8,
+    shard_len: usize,
+    buf_len: usize,
+) -> i32 {
+    let r = (|| {
+        let params = rs::RsParams::new(k as usize, m as usize);
+        let buf = rs_shard_buf(params, shards, shard_len, buf_len)?;
+        let mut owned: Vec<Vec<u8>> = buf.chunks_exact(shard_len).map(<[u8]>::to_vec).collect();
+        rs::encode_in_place(params, &mut owned).map_err(rs_to_error)?;
+        for (dst, src) in buf.chunks_exact_mut(shard_len).zip(&owned).skip(params.data_shards) {
+            dst.copy_from_slice(src);
+        }
+        Ok(())
+    })();
+    result_to_code(r)
+}
+
+/// Rebuild missing shards in place within a contiguous shard buffer.
+///
+/// Layout as in `animica_rs_encode`. `present[i] != 0` marks shard `i` as
+/// intact; the contents of missing slots are ignored and overwritten. At
+/// least `k` shards must be present.
+///
+/// # Safety
+/// - `shards` must be valid for `buf_len` bytes, with `buf_len >= (k + m) * shard_len`.
+/// - `present` must be a valid pointer to `present_len == k + m` bytes.
+#[no_mangle]
+pub extern "C" fn animica_rs_reconstruct(
+    k: u32,
+    m: u32,
+    shards: *mut u8,
+    shard_len: usize,
+    buf_len: usize,
+    present: *const u8,
+    present_len: usize,
+) -> i32 {
+    let r = (|| {
+        let params = rs::RsParams::new(k as usize, m as usize);
+        let buf = rs_shard_buf(params, shards, shard_len, buf_len)?;
+        let present = check_nonnull(present, present_len)?;
+        if present.len() != params.total() {
+            return Err(Error::InvalidArgument("present mask must have k + m entries"));
+        }
+        let mut opt: Vec<Option<Vec<u8>>> = buf
+            .chunks_exact(shard_len)
+            .zip(present)
+            .map(|(s, &p)| (p != 0).then(|| s.to_vec()))
+            .collect();
+        rs::reconstruct(params, &mut opt).map_err(rs_to_error)?;
+        for ((dst, src), &p) in buf.chunks_exact_mut(shard_len).zip(opt).zip(present) {
+            if p == 0 {
+                dst.copy_from_slice(&src.ok_or(Error::Internal("shard missing after reconstruct"))?);
+            }
+        }
+        Ok(())
+    })();
+    result_to_code(r)
+}
+
 //
 // -----------------------------
 // Python module (PyO3)
@@ -300,6 +520,15 @@ mod pyo3_mod {
         Ok(PyBytes::new(py, &digest).into_py(py))
     }
 
+    #[pyfunction]
+    fn blake3_keyed(py: Python<'_>, key: &[u8], data: &[u8]) -> PyResult<PyObject> {
+        let key: &[u8; 32] = key
+            .try_into()
+            .map_err(|_| PyValueError::new_err("key must be exactly 32 bytes"))?;
+        let digest = super::blake3_keyed(key, data);
+        Ok(PyBytes::new(py, &digest).into_py(py))
+    }
+
     #[pyfunction]
     fn sha3_256(py: Python<'_>, data: &[u8]) -> PyResult<PyObject> {
         let digest = super::sha3_256_hash(data);
@@ -333,6 +562,7 @@ mod pyo3_mod {
     #[pymodule]
     fn animica_native_py(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
         m.add_function(wrap_pyfunction!(blake3, m)?)?;
+        m.add_function(wrap_pyfunction!(blake3_keyed, m)?)?;
         m.add_function(wrap_pyfunction!(sha3_256, m)?)?;
         m.add_function(wrap_pyfunction!(blake3_chunks, m)?)?;
         m.add_function(wrap_pyfunction!(features, m)?)?;
@@ -354,3 +584,103 @@ pub mod hash;
 // --- Animica Reed–Solomon erasure coding -----------------------------------
 // Expose the RS implementation so callers and tests can use `animica_native::rs`.
 pub mod rs;
+
+// --- Shared error type and low-level helpers --------------------------------
+// `error` carries the crate-wide `NativeError`; `utils` holds alignment, XOR,
+// constant-time and CPU-probe helpers used by the SIMD/DA fast paths.
+pub mod error;
+pub mod utils;
+pub use utils::ct_eq;
+pub use utils::rayon_pool::set_thread_pool_size;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_blake3_xof_extends_hash() {
+        let data = b"animica kdf input";
+        let mut xof = [0u8; 64];
+        assert_eq!(animica_blake3_xof(data.as_ptr(), data.len(), xof.as_mut_ptr(), xof.len()), ffi_codes::OK);
+        let mut h = [0u8; 32];
+        assert_eq!(animica_blake3_hash(data.as_ptr(), data.len(), h.as_mut_ptr(), h.len()), ffi_codes::OK);
+        assert_eq!(xof[..32], h);
+        assert_ne!(xof[32..], [0u8; 32]);
+
+        assert_eq!(
+            animica_blake3_xof(data.as_ptr(), data.len(), core::ptr::null_mut(), 8),
+            ffi_codes::INVALID_ARGUMENT
+        );
+    }
+
+    #[test]
+    fn ffi_blake3_keyed_matches_reference_vectors() {
+        // Official BLAKE3 test vectors (keyed_hash, first 32 bytes): key
+        // "whats the Elvish word for friend", input byte i = i % 251.
+        let key = b"whats the Elvish word for friend";
+        let vectors: [(usize, &str); 5] = [
+            (0, "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26"),
+            (1, "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b"),
+            (1023, "c951ecdf03288d0fcc96ee3413563d8a6d3589547f2c2fb36d9786470f1b9d6e"),
+            (1024, "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4"),
+            (1025, "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69"),
+        ];
+        for (len, want) in vectors {
+            let input: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let mut out = [0u8; 32];
+            let rc = animica_blake3_keyed(key.as_ptr(), 32, input.as_ptr(), len, out.as_mut_ptr(), 32);
+            assert_eq!(rc, ffi_codes::OK);
+            let hex: String = out.iter().map(|b| format!("{b:02x}")).collect();
+            assert_eq!(hex, want, "len={len}");
+            assert_eq!(blake3_keyed(key, &input), out);
+        }
+
+        let mut out = [0u8; 32];
+        assert_eq!(
+            animica_blake3_keyed(key.as_ptr(), 31, core::ptr::null(), 0, out.as_mut_ptr(), 32),
+            ffi_codes::INVALID_ARGUMENT
+        );
+        assert_eq!(
+            animica_blake3_keyed(core::ptr::null(), 32, core::ptr::null(), 0, out.as_mut_ptr(), 32),
+            ffi_codes::INVALID_ARGUMENT
+        );
+    }
+
+    #[test]
+    fn ffi_cpu_features_match_probe() {
+        let m = animica_cpu_features();
+        let f = utils::cpu_features();
+        let bit = |b: u32| m & (1 << b) != 0;
+        assert_eq!(bit(0), f.avx2);
+        assert_eq!(bit(1), f.sha_ni);
+        assert_eq!(bit(2), f.neon);
+        assert_eq!(bit(3), f.sha2);
+        assert_eq!(bit(4), f.avx512f);
+        assert_eq!(bit(5), f.ssse3);
+        assert_eq!(m >> 6, 0);
+    }
+
+    #[test]
+    fn ffi_ct_eq_codes() {
+        let eq = |a: *const u8, a_len: usize, b: *const u8, b_len: usize| {
+            let mut out = 0xAAu8;
+            let rc = animica_ct_eq(a, a_len, b, b_len, &mut out);
+            (rc, out)
+        };
+        let x = [7u8; 32];
+        let mut y = x;
+        assert_eq!(eq(x.as_ptr(), x.len(), y.as_ptr(), y.len()), (ffi_codes::OK, 1));
+        y[31] ^= 1;
+        assert_eq!(eq(x.as_ptr(), x.len(), y.as_ptr(), y.len()), (ffi_codes::OK, 0));
+        // Length mismatch is "not equal", not an error.
+        assert_eq!(eq(x.as_ptr(), x.len(), x.as_ptr(), 31), (ffi_codes::OK, 0));
+        // Empty inputs may be NULL.
+        assert_eq!(eq(core::ptr::null(), 0, core::ptr::null(), 0), (ffi_codes::OK, 1));
+        // Errors use the same positive codes as every other FFI call.
+        assert_eq!(eq(core::ptr::null(), 4, x.as_ptr(), x.len()), (ffi_codes::INVALID_ARGUMENT, 0xAA));
+        assert_eq!(
+            animica_ct_eq(x.as_ptr(), x.len(), y.as_ptr(), y.len(), core::ptr::null_mut()),
+            ffi_codes::INVALID_ARGUMENT
+        );
+    }
+}
@@ -6,6 +6,7 @@
 //! - [`nmt_root`] — build a tree from `(namespace, payload)` leaves and return the root
 //! - [`open`]     — produce a Merkle path (proof) for a leaf index
 //! - [`verify`]   — verify a leaf against a root using the provided proof
+//! - [`IncrementalNmt`] — streaming builder whose `root()` matches [`nmt_root`]
 //!
 //! ### Design
 //! * **Namespace width:** 8 bytes (`Ns = [u8; 8]`), lexicographically ordered.
@@ -21,6 +22,9 @@
 //!   This is sufficient for membership proofs and stable roots across builder
 //!   and verifier here. (It is not a drop-in replacement for Celestia’s NMT
 //!   rules; adapt as needed.)
+//! * **Parallelism:** with the `rayon` feature, wide levels are folded across
+//!   threads. Padding happens before the fold, so roots are byte-identical to
+//!   the serial builder.
 //!
 //! ### What this is (and isn’t)
 //! This is a pragmatic, dependency-light NMT used by tests/benches. It does not
@@ -37,6 +41,7 @@
 //!   the same sequence used to compute the target `root`, otherwise proofs will
 //!   not match. This module does not attempt to deduplicate or reorder leaves.
 
+use crate::error::{NativeError, NativeResult};
 use crate::hash::{blake3, Digest32};
 
 /// 8-byte namespace identifier (lexicographically ordered).
@@ -81,7 +86,7 @@ struct Node {
 /// Compute the NMT root for a slice of `(namespace, payload)` leaves.
 ///
 /// Returns `None` for an empty leaf set.
-pub fn nmt_root<'a>(leaves: &[(Ns, &'a [u8])]) -> Option<Root> {
+pub fn nmt_root(leaves: &[(Ns, &[u8])]) -> Option<Root> {
     let mut level = build_leaf_level(leaves)?;
     let root = reduce_levels(&mut level);
     Some(root)
@@ -91,7 +96,7 @@ pub fn nmt_root<'a>(leaves: &[(Ns, &'a [u8])]) -> Option<Root> {
 ///
 /// The proof is generated against the *current* sequence of leaves supplied.
 /// Returns `None` if `leaves` is empty or `index` is out of bounds.
-pub fn open<'a>(leaves: &[(Ns, &'a [u8])], index: usize) -> Option<Proof> {
+pub fn open(leaves: &[(Ns, &[u8])], index: usize) -> Option<Proof> {
     if leaves.is_empty() || index >= leaves.len() {
         return None;
     }
@@ -166,10 +171,92 @@ pub fn verify(root: &Root, leaf_ns: Ns, leaf_data: &[u8], proof: &Proof) -> bool
     acc.min_ns == root.min_ns && acc.max_ns == root.max_ns && acc.hash == root.hash
 }
 
+/* --------------------------- Incremental builder --------------------------- */
+
+/// Streaming NMT builder: push leaves one at a time and ask for the root at
+/// any point without buffering the leaves.
+///
+/// Keeps a frontier of at most `log2(n) + 1` complete subtrees (one per set
+/// bit of the leaf count, like a binary counter). `root()` folds the frontier
+/// with the same duplicate-last padding as the batch builder, so it always
+/// equals `nmt_root` over the leaves pushed so far.
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalNmt {
+    /// `frontier[i]`: unpaired complete subtree of `2^i` leaves, if any.
+    frontier: Vec<Option<Node>>,
+    len: u64,
+    last_ns: Option<Ns>,
+}
+
+impl IncrementalNmt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of leaves pushed so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append a leaf. Namespaces must be non-decreasing across pushes.
+    pub fn push(&mut self, (ns, data): (Ns, &[u8])) -> NativeResult<()> {
+        if self.last_ns.is_some_and(|last| ns < last) {
+            return Err(NativeError::InvalidArgument("IncrementalNmt::push: namespaces must be non-decreasing"));
+        }
+        self.last_ns = Some(ns);
+
+        let mut node = leaf(ns, data);
+        let mut level = 0;
+        while let Some(left) = self.frontier.get_mut(level).and_then(Option::take) {
+            node = parent(left, node);
+            level += 1;
+        }
+        if level == self.frontier.len() {
+            self.frontier.push(None);
+        }
+        self.frontier[level] = Some(node);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Root over all leaves pushed so far; `None` if empty.
+    pub fn root(&self) -> Option<Root> {
+        if self.len == 0 {
+            return None;
+        }
+        // Walk up the levels. At level `i` the batch builder's unpaired tail is
+        // `frontier[i]` followed by `carry` (the partial subtree built from
+        // the leftover lower levels); an odd tail pairs with itself.
+        let mut carry: Option<Node> = None;
+        let mut width = self.len; // nodes on the current level: ceil(len / 2^i)
+        let mut level = 0;
+        while width > 1 {
+            let full = self.frontier.get(level).copied().flatten();
+            carry = match (full, carry) {
+                (Some(l), Some(r)) => Some(parent(l, r)),
+                (Some(n), None) | (None, Some(n)) => Some(parent(n, n)),
+                (None, None) => None,
+            };
+            width = width.div_ceil(2);
+            level += 1;
+        }
+        let n = carry.or_else(|| self.frontier.get(level).copied().flatten())?;
+        Some(Root {
+            min_ns: n.min_ns,
+            max_ns: n.max_ns,
+            hash: n.hash,
+        })
+    }
+}
+
 /* ------------------------------ Construction ------------------------------- */
 
 #[inline]
-fn build_leaf_level<'a>(leaves: &[(Ns, &'a [u8])]) -> Option<Vec<Node>> {
+fn build_leaf_level(leaves: &[(Ns, &[u8])]) -> Option<Vec<Node>> {
     if leaves.is_empty() {
         return None;
     }
@@ -180,6 +267,11 @@ fn build_leaf_level<'a>(leaves: &[(Ns, &'a [u8])]) -> Option<Vec<Node>> {
     Some(out)
 }
 
+/// Levels narrower than this are folded serially even with `rayon`; the
+/// per-task overhead outweighs a handful of parent hashes.
+#[cfg(feature = "rayon")]
+const PAR_MIN_LEVEL: usize = 256;
+
 #[inline]
 fn reduce_levels(level0: &mut [Node]) -> Root {
     let mut level = level0.to_vec();
@@ -188,11 +280,7 @@ fn reduce_levels(level0: &mut [Node]) -> Root {
             let last = *level.last().unwrap();
             level.push(last);
         }
-        let mut next = Vec::with_capacity(level.len() / 2);
-        for pair in level.chunks_exact(2) {
-            next.push(parent(pair[0], pair[1]));
-        }
-        level = next;
+        level = fold_level(&level);
     }
     let n = level[0];
     Root {
@@ -202,6 +290,37 @@ fn reduce_levels(level0: &mut [Node]) -> Root {
     }
 }
 
+/// Fold an even-length level into its parents, pairing `(2i, 2i + 1)`.
+#[inline]
+fn fold_level_serial(level: &[Node]) -> Vec<Node> {
+    let mut next = Vec::with_capacity(level.len() / 2);
+    for pair in level.chunks_exact(2) {
+        next.push(parent(pair[0], pair[1]));
+    }
+    next
+}
+
+#[cfg(not(feature = "rayon"))]
+#[inline]
+fn fold_level(level: &[Node]) -> Vec<Node> {
+    fold_level_serial(level)
+}
+
+#[cfg(feature = "rayon")]
+fn fold_level(level: &[Node]) -> Vec<Node> {
+    use rayon::prelude::*;
+    if level.len() < PAR_MIN_LEVEL {
+        return fold_level_serial(level);
+    }
+    // `par_chunks_exact` is indexed, so `collect` preserves pair order.
+    crate::utils::rayon_pool::install(|| {
+        level
+            .par_chunks_exact(2)
+            .map(|pair| parent(pair[0], pair[1]))
+            .collect()
+    })
+}
+
 /* --------------------------------- Nodes ----------------------------------- */
 
 #[inline]
@@ -213,8 +332,7 @@ fn leaf(ns: Ns, data: &[u8]) -> Node {
             &ns[..],
             &ns[..],
             &payload_h[..],
-        ]
-        .into_iter(),
+        ],
     );
     Node {
         min_ns: ns,
@@ -241,8 +359,7 @@ fn parent_hash(left: &Node, right: &Node) -> Digest32 {
             &right.max_ns[..], // NOTE: we will recompute min/max below; this keeps DS short
             &left.hash[..],
             &right.hash[..],
-        ]
-        .into_iter(),
+        ],
     )
 }
 
@@ -313,6 +430,66 @@ mod tests {
         assert!(!verify(&root, ns(2), b"Y", &pr));
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_root_matches_serial() {
+        // xorshift64 keeps the test dependency-free and reproducible.
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for &count in &[2_049usize, 3_000, 4_096] {
+            let payloads: Vec<Vec<u8>> = (0..count)
+                .map(|_| next().to_le_bytes()[..(next() % 8 + 1) as usize].to_vec())
+                .collect();
+            let leaves: Vec<(Ns, &[u8])> = payloads
+                .iter()
+                .map(|p| (ns(next() % 16), p.as_slice()))
+                .collect();
+
+            let parallel = nmt_root(&leaves).unwrap();
+
+            let mut level = build_leaf_level(&leaves).unwrap();
+            while level.len() > 1 {
+                if level.len() % 2 == 1 {
+                    let last = *level.last().unwrap();
+                    level.push(last);
+                }
+                level = fold_level_serial(&level);
+            }
+            assert_eq!(parallel.hash, level[0].hash, "count={count}");
+            assert_eq!(parallel.min_ns, level[0].min_ns);
+            assert_eq!(parallel.max_ns, level[0].max_ns);
+        }
+    }
+
+    #[test]
+    fn incremental_root_matches_batch() {
+        let payloads: Vec<Vec<u8>> = (0..37u8).map(|i| vec![i; 1 + i as usize % 5]).collect();
+        let leaves: Vec<(Ns, &[u8])> = payloads
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (ns(i as u64 / 4), p.as_slice()))
+            .collect();
+
+        let mut inc = IncrementalNmt::new();
+        assert!(inc.root().is_none());
+        for (i, leaf) in leaves.iter().enumerate() {
+            inc.push(*leaf).unwrap();
+            assert_eq!(inc.root(), nmt_root(&leaves[..=i]), "after {} leaves", i + 1);
+        }
+        assert_eq!(inc.len(), leaves.len() as u64);
+
+        // Going back to a smaller namespace is rejected and leaves state intact.
+        let before = inc.root();
+        assert!(inc.push((ns(0), b"late")).is_err());
+        assert_eq!(inc.root(), before);
+    }
+
     #[test]
     fn odd_leaf_count_stable() {
         let leaves = vec![(ns(1), b"A".as_ref()), (ns(2), b"B".as_ref()), (ns(3), b"C".as_ref())];
@@ -1,7 +1,7 @@
 //! Python bindings for `animica_native` via PyO3.
 //!
 //! Exposes a top-level module `animica_native` with submodules:
-//! - `utils` — CPU feature flags and small helpers
+//! - `utils` — CPU feature flags and small helpers (`ct_eq`, ...)
 //! - `hash`  — BLAKE3 / SHA-256 / Keccak-256
 //! - `nmt`   — Namespace Merkle Tree helpers (root/verify - minimal surface)
 //! - `rs`    — Reed–Solomon helpers (encode, parity check)
@@ -39,14 +39,21 @@ fn cpu_flags<'py>(py: Python<'py>) -> PyResult<&'py PyDict> {
     Ok(d)
 }
 
+/// Constant-time equality for MACs/commitments; `False` on length mismatch.
+#[pyfunction]
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    crate::utils::ct_eq(a, b)
+}
+
 #[pymodule]
 fn utils(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(cpu_flags, m)?)?;
+    m.add_function(wrap_pyfunction!(ct_eq, m)?)?;
     // Potential future: utils.zero_copy_view(...) etc.
     // Keep the submodule docstring helpful.
     m.add("__doc__", "Low-level utilities (CPU feature flags, helpers)")?;
     // Provide __all__ for nicer dir()
-    let all = vec!["cpu_flags"];
+    let all = vec!["cpu_flags", "ct_eq"];
     m.add("__all__", all)?;
     Ok(())
 }
@@ -60,6 +67,14 @@ fn blake3<'py>(py: Python<'py>, data: &[u8]) -> PyResult<&'py PyBytes> {
     Ok(PyBytes::new(py, &out))
 }
 
+#[pyfunction]
+fn blake3_keyed<'py>(py: Python<'py>, key: &[u8], data: &[u8]) -> PyResult<&'py PyBytes> {
+    let key: &[u8; 32] = key
+        .try_into()
+        .map_err(|_| PyValueError::new_err("key must be exactly 32 bytes"))?;
+    Ok(PyBytes::new(py, &crate::blake3_keyed(key, data)))
+}
+
 #[pyfunction]
 fn sha256<'py>(py: Python<'py>, data: &[u8]) -> PyResult<&'py PyBytes> {
     use crate::hash::sha256;
@@ -77,10 +92,11 @@ fn keccak256<'py>(py: Python<'py>, data: &[u8]) -> PyResult<&'py PyBytes> {
 #[pymodule]
 fn hash(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(blake3, m)?)?;
+    m.add_function(wrap_pyfunction!(blake3_keyed, m)?)?;
     m.add_function(wrap_pyfunction!(sha256, m)?)?;
     m.add_function(wrap_pyfunction!(keccak256, m)?)?;
-    m.add("__doc__", "Hash functions: BLAKE3 / SHA-256 / Keccak-256")?;
-    let all = vec!["blake3", "sha256", "keccak256"];
+    m.add("__doc__", "Hash functions: BLAKE3 (plain / keyed MAC) / SHA-256 / Keccak-256")?;
+    let all = vec!["blake3", "blake3_keyed", "sha256", "keccak256"];
     m.add("__all__", all)?;
     Ok(())
 }
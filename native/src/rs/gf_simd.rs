@@ -0,0 +1,249 @@
+//! SIMD GF(2^8) multiply-accumulate for the default RS backend.
+//!
+//! Computes `dst[i] ^= c · src[i]` over the same field as
+//! `reed_solomon_erasure::galois_8` (so results are bit-identical), using the
+//! split-nibble table trick: `c·b = LO[b & 0x0f] ^ HI[b >> 4]`, where the two
+//! 16-entry tables are looked up with byte shuffles:
+//!
+//! - x86_64: AVX2 (`vpshufb`, 32 B/iter) or SSSE3 (`pshufb`, 16 B/iter)
+//! - aarch64: NEON (`tbl`, 16 B/iter)
+//!
+//! Kernel choice is made once per call from `utils::cpu_features()`; when no
+//! kernel is available, callers are expected to stay on the crate backend
+//! (see [`accelerated`]).
+
+use reed_solomon_erasure::galois_8;
+
+use crate::utils::cpu_features;
+
+/// Per-coefficient nibble tables.
+struct NibbleTables {
+    lo: [u8; 16],
+    hi: [u8; 16],
+}
+
+impl NibbleTables {
+    #[inline]
+    fn new(c: u8) -> Self {
+        let mut lo = [0u8; 16];
+        let mut hi = [0u8; 16];
+        for x in 0..16u8 {
+            lo[x as usize] = galois_8::mul(c, x);
+            hi[x as usize] = galois_8::mul(c, x << 4);
+        }
+        Self { lo, hi }
+    }
+
+    #[inline]
+    fn mul(&self, b: u8) -> u8 {
+        self.lo[(b & 0x0f) as usize] ^ self.hi[(b >> 4) as usize]
+    }
+}
+
+/// Which multiply-accumulate kernel to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kernel {
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Ssse3,
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+}
+
+/// Best kernel supported by the running CPU.
+#[inline]
+pub(crate) fn best_kernel() -> Kernel {
+    let f = cpu_features();
+    #[cfg(target_arch = "x86_64")]
+    {
+        if f.avx2 {
+            return Kernel::Avx2;
+        }
+        if f.ssse3 {
+            return Kernel::Ssse3;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if f.neon {
+            return Kernel::Neon;
+        }
+    }
+    let _ = f;
+    Kernel::Scalar
+}
+
+/// Whether a SIMD kernel is available (otherwise the crate backend is preferred).
+#[inline]
+pub(crate) fn accelerated() -> bool {
+    best_kernel() != Kernel::Scalar
+}
+
+/// `dst ^= c · src` over GF(2^8) using the best available kernel.
+///
+/// `src` and `dst` must have equal length.
+#[inline]
+pub(crate) fn mul_acc(c: u8, src: &[u8], dst: &mut [u8]) {
+    mul_acc_with(best_kernel(), c, src, dst)
+}
+
+/// `dst ^= c · src` with an explicit kernel. The caller must only pass kernels
+/// reported by [`best_kernel`] (or `Scalar`).
+pub(crate) fn mul_acc_with(kernel: Kernel, c: u8, src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len(), dst.len(), "gf mul_acc: length mismatch");
+    match c {
+        0 => return,
+        1 => {
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d ^= *s;
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let t = NibbleTables::new(c);
+    let done = match kernel {
+        Kernel::Scalar => 0,
+        // SAFETY (all SIMD arms): `best_kernel` only reports kernels whose CPU
+        // features were detected at runtime; lengths are equal (asserted above).
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Ssse3 => unsafe { x86::mul_acc_ssse3(&t, src, dst) },
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx2 => unsafe { x86::mul_acc_avx2(&t, src, dst) },
+        #[cfg(target_arch = "aarch64")]
+        Kernel::Neon => unsafe { arm::mul_acc_neon(&t, src, dst) },
+    };
+
+    // Scalar tail (or whole buffer for the scalar kernel).
+    for (d, s) in dst[done..].iter_mut().zip(&src[done..]) {
+        *d ^= t.mul(*s);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::NibbleTables;
+    use core::arch::x86_64::*;
+
+    /// Returns the number of leading bytes processed (a multiple of 16).
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn mul_acc_ssse3(t: &NibbleTables, src: &[u8], dst: &mut [u8]) -> usize {
+        let n = src.len() / 16 * 16;
+        // SAFETY: all loads/stores are unaligned and within `0..n <= len`.
+        unsafe {
+            let tlo = _mm_loadu_si128(t.lo.as_ptr() as *const __m128i);
+            let thi = _mm_loadu_si128(t.hi.as_ptr() as *const __m128i);
+            let mask = _mm_set1_epi8(0x0f);
+            let mut i = 0;
+            while i < n {
+                let s = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+                let d_ptr = dst.as_mut_ptr().add(i) as *mut __m128i;
+                let lo = _mm_shuffle_epi8(tlo, _mm_and_si128(s, mask));
+                let hi = _mm_shuffle_epi8(thi, _mm_and_si128(_mm_srli_epi64(s, 4), mask));
+                let d = _mm_loadu_si128(d_ptr);
+                _mm_storeu_si128(d_ptr, _mm_xor_si128(d, _mm_xor_si128(lo, hi)));
+                i += 16;
+            }
+        }
+        n
+    }
+
+    /// Returns the number of leading bytes processed (a multiple of 32).
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_acc_avx2(t: &NibbleTables, src: &[u8], dst: &mut [u8]) -> usize {
+        let n = src.len() / 32 * 32;
+        // SAFETY: all loads/stores are unaligned and within `0..n <= len`.
+        unsafe {
+            // `vpshufb` looks up within each 128-bit lane, so broadcast the tables.
+            let tlo = _mm256_broadcastsi128_si256(_mm_loadu_si128(t.lo.as_ptr() as *const __m128i));
+            let thi = _mm256_broadcastsi128_si256(_mm_loadu_si128(t.hi.as_ptr() as *const __m128i));
+            let mask = _mm256_set1_epi8(0x0f);
+            let mut i = 0;
+            while i < n {
+                let s = _mm256_loadu_si256(src.as_ptr().add(i) as *const __m256i);
+                let d_ptr = dst.as_mut_ptr().add(i) as *mut __m256i;
+                let lo = _mm256_shuffle_epi8(tlo, _mm256_and_si256(s, mask));
+                let hi = _mm256_shuffle_epi8(thi, _mm256_and_si256(_mm256_srli_epi64(s, 4), mask));
+                let d = _mm256_loadu_si256(d_ptr);
+                _mm256_storeu_si256(d_ptr, _mm256_xor_si256(d, _mm256_xor_si256(lo, hi)));
+                i += 32;
+            }
+        }
+        n
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arm {
+    use super::NibbleTables;
+    use core::arch::aarch64::*;
+
+    /// Returns the number of leading bytes processed (a multiple of 16).
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_acc_neon(t: &NibbleTables, src: &[u8], dst: &mut [u8]) -> usize {
+        let n = src.len() / 16 * 16;
+        // SAFETY: all loads/stores are within `0..n <= len`.
+        unsafe {
+            let tlo = vld1q_u8(t.lo.as_ptr());
+            let thi = vld1q_u8(t.hi.as_ptr());
+            let mask = vdupq_n_u8(0x0f);
+            let mut i = 0;
+            while i < n {
+                let s = vld1q_u8(src.as_ptr().add(i));
+                let d_ptr = dst.as_mut_ptr().add(i);
+                let lo = vqtbl1q_u8(tlo, vandq_u8(s, mask));
+                let hi = vqtbl1q_u8(thi, vshrq_n_u8(s, 4));
+                let d = vld1q_u8(d_ptr);
+                vst1q_u8(d_ptr, veorq_u8(d, veorq_u8(lo, hi)));
+                i += 16;
+            }
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kernels() -> Vec<Kernel> {
+        let mut v = vec![Kernel::Scalar];
+        let f = cpu_features();
+        #[cfg(target_arch = "x86_64")]
+        {
+            if f.ssse3 {
+                v.push(Kernel::Ssse3);
+            }
+            if f.avx2 {
+                v.push(Kernel::Avx2);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if f.neon {
+                v.push(Kernel::Neon);
+            }
+        }
+        let _ = f;
+        v
+    }
+
+    #[test]
+    fn every_kernel_matches_crate_mul_for_all_coefficients() {
+        // 100 bytes exercises full SIMD blocks plus a scalar tail.
+        let src: Vec<u8> = (0..100u32).map(|i| (i * 37 + 11) as u8).collect();
+        let base: Vec<u8> = (0..100u32).map(|i| (i * 101 + 3) as u8).collect();
+        for k in kernels() {
+            for c in 0..=255u8 {
+                let mut want = base.clone();
+                galois_8::mul_slice_xor(c, &src, &mut want);
+                let mut got = base.clone();
+                mul_acc_with(k, c, &src, &mut got);
+                assert_eq!(got, want, "kernel {k:?}, c={c}");
+            }
+        }
+    }
+}
@@ -167,6 +167,29 @@ impl Layout {
         Ok(out)
     }
 
+    /// Reassemble the payload from shards as returned by
+    /// [`reconstruct`](crate::rs::reconstruct) (`Some` for every shard once it
+    /// succeeded), stripping the `padding_len` tail bytes added by `shardify`.
+    ///
+    /// Only the first `k` shards are read; parity slots may still be `None`.
+    /// For non-systematic codes, recover the data with
+    /// [`extract_data`](crate::rs::extract_data) and use [`Layout::unshard`].
+    pub fn trim_to_payload(&self, shards: &[Option<Vec<u8>>]) -> Result<Vec<u8>, LayoutError> {
+        if shards.len() < self.data_shards {
+            return Err(LayoutError::InvalidArg("not enough shards to unshard"));
+        }
+        let mut out = Vec::with_capacity(self.padded_data_len());
+        for (i, s) in shards[..self.data_shards].iter().enumerate() {
+            let s = s.as_deref().ok_or(LayoutError::InvalidArg("data shard missing; reconstruct first"))?;
+            if s.len() != self.shard_len {
+                return Err(LayoutError::ShardLenMismatch { index: i, len: s.len(), expected: self.shard_len });
+            }
+            out.extend_from_slice(s);
+        }
+        out.truncate(self.payload_len);
+        Ok(out)
+    }
+
     /// Validate a shards vector matches this layout (length, counts, alignment of lengths).
     pub fn validate_shards(&self, shards: &[Vec<u8>]) -> Result<(), LayoutError> {
         if shards.len() != self.total_shards() {
@@ -206,7 +229,7 @@ impl std::error::Error for LayoutError {}
 #[inline]
 pub const fn ceil_div(n: usize, d: usize) -> usize {
     // d>0 by construction
-    (n + d - 1) / d
+    n.div_ceil(d)
 }
 
 #[inline]
@@ -283,6 +306,30 @@ mod tests {
         assert_eq!(covered, layout.payload_len);
     }
 
+    #[test]
+    fn trim_to_payload_after_reconstruct() {
+        use crate::rs::{encode_in_place, reconstruct, RsParams};
+
+        // 1000 is not a multiple of k = 3: 334-byte segments, aligned to 384.
+        let payload: Vec<u8> = (0..1000u32).map(|i| (i * 7 + 3) as u8).collect();
+        let layout = Layout::with_default_align(payload.len(), 3, 2).unwrap();
+        assert_eq!(layout.shard_len, 384);
+        assert_eq!(layout.padding_len, 3 * 384 - 1000);
+
+        let mut shards = layout.shardify(&payload).unwrap();
+        let params = RsParams::new(layout.data_shards, layout.parity_shards);
+        encode_in_place(params, &mut shards).unwrap();
+
+        let mut opt: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+        assert!(layout.trim_to_payload(&opt[..2]).is_err());
+        opt[0] = None;
+        opt[2] = None;
+        assert!(layout.trim_to_payload(&opt).is_err());
+
+        reconstruct(params, &mut opt).unwrap();
+        assert_eq!(layout.trim_to_payload(&opt).unwrap(), payload);
+    }
+
     #[test]
     fn mismatch_len_detected() {
         let layout = Layout::with_default_align(4096, 4, 2).unwrap();
@@ -4,9 +4,16 @@
 //! Reed–Solomon backend, providing three core operations commonly used by
 //! Animica's DA layer and tests/benches:
 //!
-//! - `encode_in_place`: compute parity shards for a set of data shards.
+//! - `encode_in_place`: compute parity shards for a set of data shards
+//!   (`encode_in_place_as` picks the [`Encoding`]).
 //! - `reconstruct`: recover missing shards in-place given enough survivors.
 //! - `verify`: check that the parity matches the data.
+//! - `verify_first_mismatch`: like `verify`, but report which parity shard is off.
+//! - `reconstruct_from_provider`: recover selected shards, pulling survivors
+//!   lazily from a `ShardProvider` (only as many as needed).
+//! - `extract_data`: read the original data back out of a full shard set.
+//! - [`layout`]: plan payload → shard padding, and trim it off again after
+//!   reconstruction (`Layout::trim_to_payload`).
 //!
 //! ## Design notes
 //! - The API is **backend-agnostic**; by default we use the `reed-solomon-erasure`
@@ -16,6 +23,28 @@
 //! - Shards are **flat byte slices** of equal length. For convenience, the
 //!   encode function will `resize` parity shards to the proper size.
 //! - All functions operate **in place** to avoid extra allocations/copies.
+//! - On CPUs with SSSE3/AVX2 (x86_64) or NEON (aarch64), `encode_in_place`
+//!   computes parity with a small custom path built on SIMD GF(2^8)
+//!   multiply-accumulate (`gf_simd`), using the backend's own coefficients so
+//!   output is identical. Otherwise (or for tiny shards) the backend encodes.
+//!
+//! ## Systematic vs non-systematic
+//! By default ([`Encoding::Systematic`]) the code is systematic: the first `k`
+//! shards *are* the data and only the `m` parity shards are computed. With
+//! [`Encoding::NonSystematic`], `encode_in_place_as` first mixes the data
+//! shards with a fixed invertible GF(2^8) transform, so **every** one of the
+//! `k + m` shards is a combination of all data shards and none passes data
+//! through. That needs `k >= 2` (a single shard has nothing to mix with), so
+//! `k = 1` is rejected. The stored set is still a valid codeword, so
+//! `reconstruct`, `verify` and friends work unchanged on shards; use
+//! `extract_data` with the same encoding to get the original data back.
+//!
+//! Tradeoff: systematic reads are free when the data shards are available,
+//! whereas non-systematic reads always cost an `O(k² · len)` unmix (and the
+//! encode costs the same extra pass). In exchange, no single shard exposes a
+//! plaintext slice of the data, and holders of any shard can't serve reads
+//! without the rest of the pipeline. This is not encryption: anyone with `k`
+//! shards can recover everything.
 //!
 //! ## Safety & constraints
 //! - All shards must have identical lengths (except parity shards that may be
@@ -27,6 +56,9 @@
 
 use core::fmt;
 
+mod gf_simd;
+pub mod layout;
+
 /// Parameters for an RS( k + m, k ) code over GF(2^8).
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct RsParams {
@@ -36,7 +68,32 @@ pub struct RsParams {
     pub parity_shards: usize,
 }
 
+/// How data shards are stored (see the module docs).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// The first `k` shards are the data itself.
+    #[default]
+    Systematic,
+    /// Every shard mixes all data shards; needs `k >= 2`.
+    NonSystematic,
+}
+
+impl Encoding {
+    fn check(self, params: RsParams) -> Result<(), RsError> {
+        if self == Encoding::NonSystematic && params.data_shards < 2 {
+            return Err(RsError::InvalidArg("non-systematic encoding needs at least 2 data shards"));
+        }
+        Ok(())
+    }
+}
+
 impl RsParams {
+    /// RS(k + m, k) parameters.
+    #[inline]
+    pub const fn new(data_shards: usize, parity_shards: usize) -> Self {
+        Self { data_shards, parity_shards }
+    }
+
     /// Total shard count (k + m).
     #[inline]
     pub const fn total(&self) -> usize {
@@ -62,6 +119,7 @@ pub enum RsError {
     InvalidArg(&'static str),
     ShardLenMismatch,
     NotEnoughShards,     // fewer than k available for reconstruct
+    VerifyFailed,        // rebuilt set does not satisfy parity
     BackendError(String) // wrapped backend error
 }
 
@@ -72,6 +130,7 @@ impl fmt::Display for RsError {
             InvalidArg(s) => write!(f, "invalid argument: {s}"),
             ShardLenMismatch => write!(f, "all shards must have identical length"),
             NotEnoughShards => write!(f, "not enough shards to reconstruct"),
+            VerifyFailed => write!(f, "reconstructed shards fail parity verification"),
             BackendError(e) => write!(f, "backend error: {e}"),
         }
     }
@@ -92,6 +151,16 @@ fn ensure_all_equal_len<'a, T: AsRef<[u8]> + 'a>(shards: impl IntoIterator<Item=
     Ok(len0)
 }
 
+/// Zero-length shards carry nothing to code; reject them up front rather than
+/// leaving it to the backend.
+#[inline]
+fn ensure_nonzero_len(len: usize) -> Result<usize, RsError> {
+    if len == 0 {
+        return Err(RsError::InvalidArg("shard length must be > 0"));
+    }
+    Ok(len)
+}
+
 /* ------------------------------ Backend glue ----------------------------- */
 
 mod backend {
@@ -118,11 +187,18 @@ fn build_rs(params: RsParams) -> Result<backend::Rs, RsError> {
 ///
 /// Returns `Ok(())` after parity shards are written.
 pub fn encode_in_place(params: RsParams, shards: &mut [Vec<u8>]) -> Result<(), RsError> {
+    encode_in_place_as(params, Encoding::Systematic, shards)
+}
+
+/// [`encode_in_place`] with an explicit [`Encoding`]. Non-systematic encoding
+/// also overwrites the `k` data shards with their mixed form.
+pub fn encode_in_place_as(params: RsParams, encoding: Encoding, shards: &mut [Vec<u8>]) -> Result<(), RsError> {
+    encoding.check(params)?;
     if shards.len() != params.total() {
         return Err(RsError::InvalidArg("shards.len() must equal k + m"));
     }
     // Determine/validate shard length from data shards (must be consistent).
-    let data_len = ensure_all_equal_len(&shards[..params.data_shards])?;
+    let data_len = ensure_nonzero_len(ensure_all_equal_len(&shards[..params.data_shards])?)?;
     // Ensure parity shards are sized to match.
     for p in &mut shards[params.data_shards..] {
         if p.len() != data_len {
@@ -133,9 +209,116 @@ pub fn encode_in_place(params: RsParams, shards: &mut [Vec<u8>]) -> Result<(), R
     let _ = ensure_all_equal_len(&*shards)?;
 
     let rs = build_rs(params)?;
+    if encoding == Encoding::NonSystematic {
+        mix_data(&mut shards[..params.data_shards]);
+    }
+    if data_len >= SIMD_MIN_SHARD_LEN && gf_simd::accelerated() {
+        return encode_simd(&rs, params, shards);
+    }
     rs.encode(shards).map_err(|e| RsError::BackendError(format!("{e}")))
 }
 
+/// Shards shorter than this stay on the backend; the coefficient-matrix
+/// derivation in `encode_simd` isn't worth it for tiny shards.
+const SIMD_MIN_SHARD_LEN: usize = 64;
+
+/// Parity coefficients `M[i][j]` (parity `i`, data `j`) used by the backend.
+///
+/// Derived by encoding the identity: data shard `j` is the unit vector `e_j`
+/// of length `k`, so byte `j` of parity shard `i` is exactly `M[i][j]`. This
+/// keeps the SIMD path bit-identical to the backend without reaching into its
+/// private matrix code.
+fn parity_matrix(rs: &backend::Rs, params: RsParams) -> Result<Vec<Vec<u8>>, RsError> {
+    let k = params.data_shards;
+    let mut unit: Vec<Vec<u8>> = (0..params.total())
+        .map(|j| {
+            let mut v = vec![0u8; k];
+            if j < k {
+                v[j] = 1;
+            }
+            v
+        })
+        .collect();
+    rs.encode(&mut unit).map_err(|e| RsError::BackendError(format!("{e}")))?;
+    Ok(unit.split_off(k))
+}
+
+/// Custom encode: each parity shard is `Σ_j M[i][j] · data[j]`, accumulated
+/// with the SIMD GF(2^8) kernels in `gf_simd`. Shards are already sized.
+fn encode_simd(rs: &backend::Rs, params: RsParams, shards: &mut [Vec<u8>]) -> Result<(), RsError> {
+    let matrix = parity_matrix(rs, params)?;
+    let (data, parity) = shards.split_at_mut(params.data_shards);
+    for (row, out) in matrix.iter().zip(parity.iter_mut()) {
+        out.fill(0);
+        for (&c, src) in row.iter().zip(data.iter()) {
+            gf_simd::mul_acc(c, src, out);
+        }
+    }
+    Ok(())
+}
+
+/// Coefficient of the non-systematic mixing transform. Any value other than
+/// 0 and 1 makes every mixed shard depend on every data shard.
+const MIX_COEFF: u8 = 2;
+
+/// Non-systematic mixing `d ← L·U·d` over the `k` data shards, where `U` and
+/// `L` are unit upper/lower triangular with `MIX_COEFF` off the diagonal.
+/// Both passes are in place and trivially invertible (see `unmix_data`).
+fn mix_data(data: &mut [Vec<u8>]) {
+    let k = data.len();
+    // U: ascending, so each shard still sees the untouched shards above it.
+    for i in 0..k {
+        let (head, tail) = data.split_at_mut(i + 1);
+        for src in tail.iter() {
+            gf_simd::mul_acc(MIX_COEFF, src, &mut head[i]);
+        }
+    }
+    // L: descending, so each shard still sees the untouched shards below it.
+    for i in (1..k).rev() {
+        let (head, tail) = data.split_at_mut(i);
+        for src in head.iter() {
+            gf_simd::mul_acc(MIX_COEFF, src, &mut tail[0]);
+        }
+    }
+}
+
+/// Inverse of `mix_data`: undo `L`, then `U` (addition is XOR, so each step
+/// re-applies the same term in the opposite order).
+fn unmix_data(data: &mut [Vec<u8>]) {
+    let k = data.len();
+    for i in 1..k {
+        let (head, tail) = data.split_at_mut(i);
+        for src in head.iter() {
+            gf_simd::mul_acc(MIX_COEFF, src, &mut tail[0]);
+        }
+    }
+    for i in (0..k).rev() {
+        let (head, tail) = data.split_at_mut(i + 1);
+        for src in tail.iter() {
+            gf_simd::mul_acc(MIX_COEFF, src, &mut head[i]);
+        }
+    }
+}
+
+/// Return the original `k` data shards from a full, consistent shard set
+/// (e.g. after `reconstruct`) stored with `encoding`.
+///
+/// For systematic shards this is a copy of the first `k`; otherwise it undoes
+/// the mixing applied by `encode_in_place_as`.
+pub fn extract_data(params: RsParams, encoding: Encoding, shards: &[Vec<u8>]) -> Result<Vec<Vec<u8>>, RsError> {
+    params.validate()?;
+    encoding.check(params)?;
+    if shards.len() != params.total() {
+        return Err(RsError::InvalidArg("shards.len() must equal k + m"));
+    }
+    ensure_nonzero_len(ensure_all_equal_len(shards)?)?;
+    let mut data = shards[..params.data_shards].to_vec();
+    if encoding == Encoding::NonSystematic {
+        unmix_data(&mut data);
+    }
+    Ok(data)
+}
+
 /// Reconstruct missing shards **in place**.
 ///
 /// - `params`: RS code params
@@ -166,12 +349,105 @@ pub fn reconstruct(params: RsParams, shards: &mut [Option<Vec<u8>>]) -> Result<(
     if present < params.data_shards {
         return Err(RsError::NotEnoughShards);
     }
+    ensure_nonzero_len(len_opt.unwrap_or(0))?;
     let rs = build_rs(params)?;
     rs.reconstruct(shards)
         .map_err(|e| RsError::BackendError(format!("{e}")))?;
     Ok(())
 }
 
+/// [`reconstruct`], then [`verify`] the complete set before returning.
+///
+/// Costs one extra verify pass, but turns silent corruption (a present shard
+/// that was altered, or a backend bug) into `RsError::VerifyFailed` instead
+/// of handing back inconsistent shards. The shards are still filled in on
+/// failure so callers can inspect them.
+pub fn reconstruct_verified(params: RsParams, shards: &mut [Option<Vec<u8>>]) -> Result<(), RsError> {
+    reconstruct(params, shards)?;
+    // Move the shards out for `verify` and back afterwards; no copies.
+    let full: Vec<Vec<u8>> = shards
+        .iter_mut()
+        .map(|s| s.take().expect("shard present after reconstruct"))
+        .collect();
+    let ok = verify(params, &full);
+    for (slot, s) in shards.iter_mut().zip(full) {
+        *slot = Some(s);
+    }
+    if ok? { Ok(()) } else { Err(RsError::VerifyFailed) }
+}
+
+/// Source of shards fetched on demand (e.g. from DA peers or storage).
+///
+/// `get` returns `None` when the shard is unavailable; the caller then moves
+/// on to another index.
+pub trait ShardProvider {
+    fn get(&self, index: usize) -> Option<Vec<u8>>;
+}
+
+impl<F> ShardProvider for F
+where
+    F: Fn(usize) -> Option<Vec<u8>>,
+{
+    fn get(&self, index: usize) -> Option<Vec<u8>> {
+        self(index)
+    }
+}
+
+/// Recover the shards at `wanted` (absolute indices in `[0, k+m)`), pulling
+/// shards from `provider` only until enough are held.
+///
+/// The wanted indices are requested first; if all of them are available no
+/// decoding happens. Otherwise the remaining indices are requested in order
+/// until `k` shards are present, and the rest are reconstructed. Returns the
+/// shards in the order of `wanted`.
+pub fn reconstruct_from_provider<P>(
+    params: RsParams,
+    provider: &P,
+    wanted: &[usize],
+) -> Result<Vec<Vec<u8>>, RsError>
+where
+    P: ShardProvider + ?Sized,
+{
+    params.validate()?;
+    let n = params.total();
+    if wanted.iter().any(|&i| i >= n) {
+        return Err(RsError::InvalidArg("wanted index out of range"));
+    }
+
+    let mut shards: Vec<Option<Vec<u8>>> = vec![None; n];
+    let mut tried = vec![false; n];
+    let mut present = 0usize;
+    let mut len_opt = None::<usize>;
+
+    // Wanted indices first, then the rest in order until `k` are held.
+    for (pos, i) in wanted.iter().copied().chain(0..n).enumerate() {
+        if pos == wanted.len() && wanted.iter().all(|&w| shards[w].is_some()) {
+            break;
+        }
+        if pos >= wanted.len() && present >= params.data_shards {
+            break;
+        }
+        if core::mem::replace(&mut tried[i], true) {
+            continue;
+        }
+        if let Some(s) = provider.get(i) {
+            if *len_opt.get_or_insert(s.len()) != s.len() {
+                return Err(RsError::ShardLenMismatch);
+            }
+            shards[i] = Some(s);
+            present += 1;
+        }
+    }
+    if wanted.iter().any(|&i| shards[i].is_none()) {
+        reconstruct(params, &mut shards)?;
+    }
+
+    Ok(wanted
+        .iter()
+        .map(|&i| shards[i].clone().expect("wanted shard present after reconstruct"))
+        .collect())
+}
+
 /// Verify that data+parity shards are consistent.
 ///
 /// Expects a full set (`k + m`) of present shards; returns `Ok(true)` if valid.
@@ -179,13 +455,40 @@ pub fn verify(params: RsParams, shards: &[Vec<u8>]) -> Result<bool, RsError> {
     if shards.len() != params.total() {
         return Err(RsError::InvalidArg("shards.len() must equal k + m"));
     }
-    let _ = ensure_all_equal_len(shards)?;
+    ensure_nonzero_len(ensure_all_equal_len(shards)?)?;
     let rs = build_rs(params)?;
     let ok = rs.verify(shards)
         .map_err(|e| RsError::BackendError(format!("{e}")))?;
     Ok(ok)
 }
 
+/// Verify data+parity shards, returning the **absolute** index (in `[k, k+m)`)
+/// of the first parity shard that disagrees with parity re-encoded from the
+/// data, or `None` if all parity is consistent.
+///
+/// Expects a full set (`k + m`) of present shards. The caller's buffers are not
+/// modified; parity is recomputed into a scratch copy.
+pub fn verify_first_mismatch(params: RsParams, shards: &[Vec<u8>]) -> Result<Option<usize>, RsError> {
+    if shards.len() != params.total() {
+        return Err(RsError::InvalidArg("shards.len() must equal k + m"));
+    }
+    let len = ensure_nonzero_len(ensure_all_equal_len(shards)?)?;
+    let k = params.data_shards;
+
+    let mut scratch: Vec<Vec<u8>> = Vec::with_capacity(params.total());
+    scratch.extend(shards[..k].iter().cloned());
+    scratch.resize(params.total(), vec![0u8; len]);
+    // The stored data shards are already mixed in non-systematic mode; parity
+    // over them is a plain systematic encode.
+    encode_in_place(params, &mut scratch)?;
+
+    Ok(scratch[k..]
+        .iter()
+        .zip(&shards[k..])
+        .position(|(want, have)| want != have)
+        .map(|i| k + i))
+}
+
 /* --------------------------------- Tests -------------------------------- */
 
 #[cfg(test)]
@@ -229,7 +532,7 @@ mod tests {
     }
 
     fn random_shards(k: usize, m: usize, len: usize, seed: u64) -> (RsParams, Vec<Vec<u8>>) {
-        let params = RsParams { data_shards: k, parity_shards: m };
+        let params = RsParams::new(k, m);
         let mut rng = TestRng::new(seed);
         let mut shards = vec![vec![0u8; len]; k + m];
         for s in &mut shards[..k] {
@@ -278,6 +581,27 @@ mod tests {
         matches!(err, RsError::NotEnoughShards);
     }
 
+    #[test]
+    fn reconstruct_verified_checks_parity() {
+        let (params, mut shards) = random_shards(4, 2, 512, 7);
+        encode_in_place(params, &mut shards).unwrap();
+
+        let mut opt: Vec<Option<Vec<u8>>> = shards.iter().cloned().map(Some).collect();
+        opt[1] = None;
+        opt[5] = None;
+        reconstruct_verified(params, &mut opt).unwrap();
+        let rebuilt: Vec<Vec<u8>> = opt.into_iter().map(Option::unwrap).collect();
+        assert_eq!(rebuilt, shards);
+
+        // A silently altered survivor is caught by the post-check.
+        let mut opt: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+        opt[0] = None;
+        opt[2].as_mut().unwrap()[0] ^= 0x5a;
+        let err = reconstruct_verified(params, &mut opt).unwrap_err();
+        assert!(matches!(err, RsError::VerifyFailed));
+        assert!(opt.iter().all(Option::is_some));
+    }
+
     #[test]
     fn parity_resize_is_ok() {
         let (params, mut shards) = random_shards(3, 2, 777, 13);
@@ -292,22 +616,205 @@ mod tests {
         assert_eq!(shards[4].len(), 777);
     }
 
+    #[test]
+    fn first_mismatch_reports_absolute_parity_index() {
+        let (params, mut shards) = random_shards(4, 3, 512, 21);
+        encode_in_place(params, &mut shards).unwrap();
+        assert_eq!(verify_first_mismatch(params, &shards).unwrap(), None);
+
+        // Corrupt parity shard 1 (absolute index k + 1).
+        shards[params.data_shards + 1][17] ^= 0xA5;
+        assert_eq!(verify_first_mismatch(params, &shards).unwrap(), Some(params.data_shards + 1));
+        assert!(!verify(params, &shards).unwrap());
+    }
+
+    #[test]
+    fn simd_encode_matches_backend() {
+        // Differential check: custom SIMD path vs the crate backend, including
+        // lengths that leave a scalar tail. Runs the scalar kernel on CPUs
+        // without SIMD, which still exercises the custom encode path.
+        let mut rng = TestRng::new(2201);
+        for round in 0..24u64 {
+            let k = 1 + (rng.next_u64() % 12) as usize;
+            let m = 1 + (rng.next_u64() % 6) as usize;
+            let len = SIMD_MIN_SHARD_LEN + (rng.next_u64() % 700) as usize;
+            let (params, data) = random_shards(k, m, len, round + 1);
+
+            let rs = build_rs(params).unwrap();
+            let mut want = data.clone();
+            rs.encode(&mut want).unwrap();
+
+            let mut got = data;
+            encode_simd(&rs, params, &mut got).unwrap();
+            assert_eq!(got, want, "k={k} m={m} len={len}");
+        }
+    }
+
+    #[test]
+    fn provider_reconstruct_pulls_only_k_shards() {
+        use std::cell::RefCell;
+
+        let (params, mut shards) = random_shards(4, 3, 256, 77);
+        encode_in_place(params, &mut shards).unwrap();
+
+        // Shards 1 and 2 are unavailable; record every request.
+        let requested = RefCell::new(Vec::new());
+        let provider = |i: usize| {
+            requested.borrow_mut().push(i);
+            (i != 1 && i != 2).then(|| shards[i].clone())
+        };
+
+        let got = reconstruct_from_provider(params, &provider, &[2, 1]).unwrap();
+        assert_eq!(got, vec![shards[2].clone(), shards[1].clone()]);
+
+        let requested = requested.into_inner();
+        let pulled = requested.iter().filter(|&&i| i != 1 && i != 2).count();
+        assert_eq!(pulled, params.data_shards);
+        assert_eq!(requested, vec![2, 1, 0, 3, 4, 5]);
+
+        // Directly available shards need no decode and no extra pulls.
+        let hits = RefCell::new(0usize);
+        let all = |i: usize| {
+            *hits.borrow_mut() += 1;
+            Some(shards[i].clone())
+        };
+        assert_eq!(reconstruct_from_provider(params, &all, &[6]).unwrap(), vec![shards[6].clone()]);
+        assert_eq!(hits.into_inner(), 1);
+    }
+
+    #[test]
+    fn non_systematic_roundtrip_recovers_data() {
+        use Encoding::{NonSystematic, Systematic};
+
+        for (k, m) in [(2, 1), (3, 2), (5, 3)] {
+            let (params, data) = random_shards(k, m, 300, 31 + k as u64);
+            let mut shards = data.clone();
+            encode_in_place_as(params, NonSystematic, &mut shards).unwrap();
+            assert!(verify(params, &shards).unwrap());
+            assert_eq!(verify_first_mismatch(params, &shards).unwrap(), None);
+            // No data shard is passed through.
+            assert!((0..k).all(|i| shards[i] != data[i]), "k={k} m={m}");
+
+            // Lose m shards, including data positions, and read the data back.
+            let mut opt: Vec<Option<Vec<u8>>> = shards.iter().cloned().map(Some).collect();
+            for slot in opt.iter_mut().take(m) {
+                *slot = None;
+            }
+            reconstruct_verified(params, &mut opt).unwrap();
+            let rebuilt: Vec<Vec<u8>> = opt.into_iter().map(Option::unwrap).collect();
+            assert_eq!(rebuilt, shards);
+            assert_eq!(extract_data(params, NonSystematic, &rebuilt).unwrap(), data[..k]);
+            assert_eq!(extract_data(params, Systematic, &rebuilt).unwrap(), shards[..k]);
+        }
+
+        // A single data shard would be stored as is, so k = 1 is refused.
+        let (params, mut shards) = random_shards(1, 2, 64, 7);
+        let refused = |e: RsError| matches!(e, RsError::InvalidArg(m) if m.contains("at least 2"));
+        assert!(refused(encode_in_place_as(params, NonSystematic, &mut shards).unwrap_err()));
+        assert!(refused(extract_data(params, NonSystematic, &shards).unwrap_err()));
+    }
+
     #[test]
     fn mismatched_lengths_error() {
-        let params = RsParams { data_shards: 2, parity_shards: 1 };
+        let params = RsParams::new(2, 1);
         let mut shards = vec![vec![1u8; 10], vec![2u8; 11], vec![]];
         let err = encode_in_place(params, &mut shards).unwrap_err();
         matches!(err, RsError::ShardLenMismatch);
     }
+
+    #[test]
+    fn zero_length_shards_rejected() {
+        let params = RsParams::new(2, 1);
+        let is_clear = |e: RsError| matches!(e, RsError::InvalidArg("shard length must be > 0"));
+
+        let mut shards = vec![Vec::new(); 3];
+        assert!(is_clear(encode_in_place(params, &mut shards).unwrap_err()));
+        assert!(is_clear(verify(params, &shards).unwrap_err()));
+
+        let mut opt = vec![Some(Vec::new()), Some(Vec::new()), None];
+        assert!(is_clear(reconstruct(params, &mut opt).unwrap_err()));
+    }
+}
+
+/// Round-trip `data` through the C ABI and check it against the safe API.
+///
+/// Encodes via [`crate::animica_rs_encode`], erases the shards in `missing`,
+/// rebuilds them via [`crate::animica_rs_reconstruct`], and compares each step
+/// byte for byte with [`encode_in_place`] / [`reconstruct`]. Returns the full
+/// `k + m` shard set. Integration tests use this to catch drift between the
+/// FFI and safe paths (which are systematic).
+#[doc(hidden)]
+pub fn ffi_roundtrip_test_helper(
+    params: RsParams,
+    data: &[Vec<u8>],
+    missing: &[usize],
+) -> Result<Vec<Vec<u8>>, RsError> {
+    use crate::{animica_rs_encode, animica_rs_reconstruct, ffi_codes};
+
+    params.validate()?;
+    if data.len() != params.data_shards {
+        return Err(RsError::InvalidArg("data.len() must equal k"));
+    }
+    if missing.iter().any(|&i| i >= params.total()) {
+        return Err(RsError::InvalidArg("missing index out of range"));
+    }
+    let shard_len = ensure_nonzero_len(ensure_all_equal_len(data)?)?;
+    let (k, m) = (
+        u32::try_from(params.data_shards).map_err(|_| RsError::InvalidArg("k exceeds u32"))?,
+        u32::try_from(params.parity_shards).map_err(|_| RsError::InvalidArg("m exceeds u32"))?,
+    );
+    let ffi_call = |name: &str, code: i32| {
+        if code == ffi_codes::OK {
+            Ok(())
+        } else {
+            Err(RsError::BackendError(format!("{name} returned {code}")))
+        }
+    };
+    let diverged = |step: &str| RsError::BackendError(format!("FFI {step} diverged from the safe API"));
+
+    // Safe path.
+    let mut expected: Vec<Vec<u8>> = data.to_vec();
+    expected.resize(params.total(), Vec::new());
+    encode_in_place(params, &mut expected)?;
+
+    // FFI encode over the contiguous layout.
+    let mut buf = vec![0u8; params.total() * shard_len];
+    for (dst, src) in buf.chunks_exact_mut(shard_len).zip(data) {
+        dst.copy_from_slice(src);
+    }
+    ffi_call("animica_rs_encode", animica_rs_encode(k, m, buf.as_mut_ptr(), shard_len, buf.len()))?;
+    if !buf.chunks_exact(shard_len).eq(expected.iter().map(Vec::as_slice)) {
+        return Err(diverged("encode"));
+    }
+
+    // Erase, then rebuild through both paths.
+    let mut present = vec![1u8; params.total()];
+    let mut opt: Vec<Option<Vec<u8>>> = expected.iter().cloned().map(Some).collect();
+    for &i in missing {
+        present[i] = 0;
+        opt[i] = None;
+        buf[i * shard_len..(i + 1) * shard_len].fill(0);
+    }
+    reconstruct(params, &mut opt)?;
+    ffi_call(
+        "animica_rs_reconstruct",
+        animica_rs_reconstruct(k, m, buf.as_mut_ptr(), shard_len, buf.len(), present.as_ptr(), present.len()),
+    )?;
+    let rebuilt: Vec<Vec<u8>> = opt.into_iter().flatten().collect();
+    if rebuilt != expected || !buf.chunks_exact(shard_len).eq(rebuilt.iter().map(Vec::as_slice)) {
+        return Err(diverged("reconstruct"));
+    }
+    Ok(rebuilt)
 }
 
 /// Thin "bench API" wrapper used by higher-level tests and benchmarks.
 ///
 /// It exposes a simple `encode(&data_shards, parity_shards)` helper that
-/// figures out `(k, m)` and calls the low-level `encode_in_place` routine.
-/// This keeps public tests stable even if the internal RS API evolves.
+/// figures out `(k, m)` and calls the low-level `encode_in_place` routine,
+/// plus a matching `reconstruct` over `Option` shards. This keeps public
+/// tests stable even if the internal RS API evolves.
 pub mod bench_api {
-    use super::{encode_in_place, RsParams};
+    use super::{encode_in_place, reconstruct as rs_reconstruct, RsParams};
 
     /// Encode parity shards for the given data shards.
     ///
@@ -323,10 +830,7 @@ pub mod bench_api {
             "bench_api::encode requires parity_shards > 0"
         );
 
-        let params = RsParams {
-            data_shards: k,
-            parity_shards,
-        };
+        let params = RsParams::new(k, parity_shards);
 
         // Copy data shards and append empty parity shards. `encode_in_place`
         // will resize parity shards to match the data length and fill them in.
@@ -340,4 +844,33 @@ pub mod bench_api {
 
         shards
     }
+
+    /// Recover every shard from a partial set (`None` = erasure).
+    ///
+    /// * `shards` – all `k + m` slots, data first, with at least `k` present.
+    ///
+    /// Returns the full set of `k + m` shards. Panics if the slot count does
+    /// not match `(k, m)` or fewer than `k` shards are present.
+    pub fn reconstruct(mut shards: Vec<Option<Vec<u8>>>, k: usize, m: usize) -> Vec<Vec<u8>> {
+        assert_eq!(
+            shards.len(),
+            k + m,
+            "bench_api::reconstruct expects k + m = {} shard slots",
+            k + m
+        );
+        let present = shards.iter().filter(|s| s.is_some()).count();
+        assert!(
+            present >= k,
+            "bench_api::reconstruct: only {present} of {} shards present, need at least k = {k}",
+            k + m
+        );
+
+        rs_reconstruct(RsParams::new(k, m), &mut shards)
+            .expect("bench_api::reconstruct: reconstruct failed");
+
+        shards
+            .into_iter()
+            .map(|s| s.expect("shard present after reconstruct"))
+            .collect()
+    }
 }
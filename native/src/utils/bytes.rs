@@ -1,10 +1,12 @@
-//! Byte-oriented helpers: zero-copy typed views, hex encode/decode,
+//! Byte-oriented helpers: zero-copy typed views (with a copying fallback,
+//! `cast_or_copy`), hex encode/decode,
 //! and small alignment utilities.
 //!
 //! These functions avoid allocations where possible and validate alignment
 //!/length constraints before performing `unsafe` casts.
 
 use core::{mem, ptr, slice};
+use std::borrow::Cow;
 use crate::error::{NativeError, NativeResult};
 
 use super::{is_aligned, round_up_to};
@@ -23,7 +25,7 @@ pub fn cast_slice<T>(bytes: &[u8]) -> NativeResult<&[T]> {
         // Zero-sized types are weird; reject to simplify invariants.
         return Err(NativeError::InvalidArgument("cast_slice: zero-sized type"));
     }
-    if bytes.len() % size != 0 {
+    if !bytes.len().is_multiple_of(size) {
         return Err(NativeError::InvalidArgument("cast_slice: length not multiple of T"));
     }
     let ptr = bytes.as_ptr();
@@ -42,7 +44,7 @@ pub fn cast_slice_mut<T>(bytes: &mut [u8]) -> NativeResult<&mut [T]> {
     if size == 0 {
         return Err(NativeError::InvalidArgument("cast_slice_mut: zero-sized type"));
     }
-    if bytes.len() % size != 0 {
+    if !bytes.len().is_multiple_of(size) {
         return Err(NativeError::InvalidArgument("cast_slice_mut: length not multiple of T"));
     }
     let ptr = bytes.as_mut_ptr();
@@ -54,6 +56,50 @@ pub fn cast_slice_mut<T>(bytes: &mut [u8]) -> NativeResult<&mut [T]> {
     Ok(unsafe { slice::from_raw_parts_mut(ptr as *mut T, len) })
 }
 
+/// Plain-old-data types that may be built from any bit pattern.
+///
+/// # Safety
+/// Implementors must have no padding, no invalid bit patterns and no
+/// interior pointers, so that any `size_of::<T>()` bytes form a valid `T`.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => { $(unsafe impl Pod for $t {})* };
+}
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// View `bytes` as `&[T]`, copying only when the input is misaligned for `T`.
+///
+/// Aligned input is borrowed exactly like [`cast_slice`]. Otherwise the bytes
+/// are copied into a freshly allocated `Vec<T>` (one allocation of
+/// `bytes.len()` bytes), so callers always get a usable slice. Elements are
+/// read in native byte order on both paths.
+///
+/// Errors only if `T` is zero-sized or `bytes.len()` is not a multiple of
+/// `size_of::<T>()`.
+pub fn cast_or_copy<T: Pod>(bytes: &[u8]) -> NativeResult<Cow<'_, [T]>> {
+    let size = mem::size_of::<T>();
+    if size == 0 {
+        return Err(NativeError::InvalidArgument("cast_or_copy: zero-sized type"));
+    }
+    if !bytes.len().is_multiple_of(size) {
+        return Err(NativeError::InvalidArgument("cast_or_copy: length not multiple of T"));
+    }
+    if is_aligned(bytes.as_ptr(), mem::align_of::<T>()) {
+        return cast_slice(bytes).map(Cow::Borrowed);
+    }
+    let len = bytes.len() / size;
+    let mut out = Vec::<T>::with_capacity(len);
+    // SAFETY: `out` has room for `len * size == bytes.len()` bytes, the regions
+    // cannot overlap, and `T: Pod` accepts any bit pattern.
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out.as_mut_ptr() as *mut u8, bytes.len());
+        out.set_len(len);
+    }
+    Ok(Cow::Owned(out))
+}
+
 /// Produce an aligned typed view in the middle of a byte slice without copying.
 ///
 /// Returns `(head_bytes, typed_mid, tail_bytes)` where:
@@ -216,7 +262,7 @@ pub fn is_hex(s: &str) -> bool {
 #[inline]
 pub fn from_hex(s: &str) -> NativeResult<Vec<u8>> {
     let s = strip_0x(s);
-    if s.len() % 2 != 0 {
+    if !s.len().is_multiple_of(2) {
         return Err(NativeError::InvalidArgument("from_hex: odd-length input"));
     }
     let bytes = s.as_bytes();
@@ -228,18 +274,16 @@ pub fn from_hex(s: &str) -> NativeResult<Vec<u8>> {
 /// Decode ASCII hex bytes into `out`. `out.len()` must equal `bytes.len()/2`.
 #[inline]
 pub fn decode_into(bytes: &[u8], out: &mut [u8]) -> NativeResult<()> {
-    if bytes.len() % 2 != 0 {
+    if !bytes.len().is_multiple_of(2) {
         return Err(NativeError::InvalidArgument("decode_into: odd-length input"));
     }
     if out.len() != bytes.len() / 2 {
         return Err(NativeError::InvalidArgument("decode_into: output length mismatch"));
     }
-    let mut j = 0usize;
-    for i in (0..bytes.len()).step_by(2) {
-        let hi = hex_to_nybble(bytes[i]).ok_or_else(|| NativeError::InvalidArgument("decode_into: invalid hex"))?;
-        let lo = hex_to_nybble(bytes[i+1]).ok_or_else(|| NativeError::InvalidArgument("decode_into: invalid hex"))?;
-        out[j] = (hi << 4) | lo;
-        j += 1;
+    for (o, pair) in out.iter_mut().zip(bytes.chunks_exact(2)) {
+        let hi = hex_to_nybble(pair[0]).ok_or(NativeError::InvalidArgument("decode_into: invalid hex"))?;
+        let lo = hex_to_nybble(pair[1]).ok_or(NativeError::InvalidArgument("decode_into: invalid hex"))?;
+        *o = (hi << 4) | lo;
     }
     Ok(())
 }
@@ -262,6 +306,29 @@ mod tests {
         assert_eq!(back, &[1,2,3,4]);
     }
 
+    #[test]
+    fn test_cast_or_copy_borrows_aligned_and_copies_misaligned() {
+        let words: Vec<u32> = vec![1, 0xdead_beef, u32::MAX, 7];
+        let bytes = crate::utils::as_u8_slice(&words);
+
+        let view = cast_or_copy::<u32>(bytes).unwrap();
+        assert!(matches!(view, Cow::Borrowed(_)));
+        assert_eq!(&*view, &words[..]);
+
+        // Shift the copy so its start is misaligned for u32.
+        let mut shifted = vec![0u8; bytes.len() + 1];
+        let off = if is_aligned(shifted.as_ptr(), 4) { 1 } else { 0 };
+        let mis = &mut shifted[off..off + bytes.len()];
+        mis.copy_from_slice(bytes);
+        assert!(!is_aligned(mis.as_ptr(), 4));
+        let copy = cast_or_copy::<u32>(mis).unwrap();
+        assert!(matches!(copy, Cow::Owned(_)));
+        assert_eq!(&*copy, &words[..]);
+
+        assert!(cast_or_copy::<u32>(&bytes[..3]).is_err());
+        assert!(cast_or_copy::<[u8; 0]>(bytes).is_err());
+    }
+
     #[test]
     fn test_aligned_view_splits() {
         let mut buf = vec![0u8; 37];
@@ -273,11 +340,12 @@ mod tests {
         for (i, x) in mid.iter_mut().enumerate() {
             *x = (i as u32) ^ 0xA5A5_A5A5;
         }
+        let (head_len, tail_len, mid) = (head.len(), tail.len(), mid.to_vec());
         // Sanity: mid reflects into original buffer
         let (h2, m2, t2) = aligned_view::<u32>(&buf);
-        assert_eq!(head.len(), h2.len());
-        assert_eq!(tail.len(), t2.len());
-        assert_eq!(mid, m2);
+        assert_eq!(head_len, h2.len());
+        assert_eq!(tail_len, t2.len());
+        assert_eq!(&mid[..], m2);
     }
 
     #[test]
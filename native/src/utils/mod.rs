@@ -4,8 +4,12 @@
 //! - Zero-alloc utilities for tight loops (hashing, encoding, DA RS, etc.).
 //! - Safe(ish) wrappers around common `unsafe` slice casts.
 //! - Constant-time primitives for sensitive comparisons.
+//! - A single CSPRNG entry point (`random_bytes` / `random_array`).
 //! - Tiny runtime CPU feature probe with caching.
-//! - Optional parallel helpers (when the `rayon` feature is enabled).
+//! - Over-aligned byte buffers (`AlignedBytes`) for SIMD fast paths.
+//! - Optional parallel helpers (when the `rayon` feature is enabled), including
+//!   order-preserving per-shard hashing (`hash_shards`); `rayon_pool` lets
+//!   embedders bound them to a dedicated pool.
 //!
 //! This module is intentionally dependency-light and `no_std`-friendly in style
 //! (though the crate itself uses `std`). Keep APIs stable—downstream crates and
@@ -15,13 +19,17 @@ use core::{mem, ptr, slice, sync::atomic::{compiler_fence, Ordering}};
 use std::sync::OnceLock;
 
 use crate::error::{NativeError, NativeResult};
+use crate::hash::{hash_ds, Digest32, DsTag};
+
+pub mod bytes;
+pub mod rayon_pool;
 
 /// Round `len` up to the next multiple of `alignment` (must be > 0).
 #[inline]
 pub fn round_up_to(len: usize, alignment: usize) -> usize {
     debug_assert!(alignment > 0);
     if alignment == 0 { return len; }
-    (len + alignment - 1) / alignment * alignment
+    len.div_ceil(alignment) * alignment
 }
 
 /// Alias for clarity when padding buffers to a specific alignment boundary.
@@ -36,6 +44,94 @@ pub fn is_aligned(ptr: *const u8, alignment: usize) -> bool {
     (ptr as usize) & (alignment - 1) == 0
 }
 
+/// Heap byte buffer whose start address is aligned to a caller-chosen boundary.
+///
+/// `Vec<u8>` only guarantees 1-byte alignment, which makes SIMD paths fall back
+/// to unaligned or scalar code. `AlignedBytes` over-aligns the allocation (e.g.
+/// 32 for AVX2, 64 for a cache line) and derefs to `[u8]`, so it can be passed
+/// anywhere a byte slice is expected.
+pub struct AlignedBytes {
+    ptr: ptr::NonNull<u8>,
+    len: usize,
+    align: usize,
+}
+
+// SAFETY: `AlignedBytes` uniquely owns its allocation, like `Vec<u8>`.
+unsafe impl Send for AlignedBytes {}
+// SAFETY: shared access only hands out `&[u8]`.
+unsafe impl Sync for AlignedBytes {}
+
+impl AlignedBytes {
+    /// Allocate `len` zeroed bytes starting on an `align`-byte boundary.
+    ///
+    /// `align` must be a non-zero power of two.
+    pub fn with_capacity(len: usize, align: usize) -> NativeResult<Self> {
+        let layout = std::alloc::Layout::from_size_align(len, align)
+            .map_err(|_| NativeError::InvalidArgument("AlignedBytes: align must be a power of two"))?;
+        let ptr = if len == 0 {
+            // Zero-sized allocations are not allowed; use a dangling but aligned pointer.
+            // SAFETY: `align` is a non-zero power of two, so this is non-null.
+            unsafe { ptr::NonNull::new_unchecked(align as *mut u8) }
+        } else {
+            // SAFETY: `layout` has non-zero size.
+            let raw = unsafe { std::alloc::alloc_zeroed(layout) };
+            ptr::NonNull::new(raw).ok_or(NativeError::Internal("AlignedBytes: allocation failed"))?
+        };
+        Ok(Self { ptr, len, align })
+    }
+
+    /// Alignment requested at allocation time.
+    #[inline]
+    pub fn alignment(&self) -> usize {
+        self.align
+    }
+
+    /// Whether the buffer start is aligned to `alignment` bytes (power of two).
+    #[inline]
+    pub fn is_aligned(&self, alignment: usize) -> bool {
+        is_aligned(self.ptr.as_ptr(), alignment)
+    }
+}
+
+impl core::ops::Deref for AlignedBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for `len` initialized (zeroed) bytes.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl core::ops::DerefMut for AlignedBytes {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: `ptr` is valid for `len` bytes and uniquely borrowed via `&mut self`.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for AlignedBytes {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            // SAFETY: allocated in `with_capacity` with exactly this layout.
+            unsafe {
+                let layout = std::alloc::Layout::from_size_align_unchecked(self.len, self.align);
+                std::alloc::dealloc(self.ptr.as_ptr(), layout);
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for AlignedBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AlignedBytes")
+            .field("len", &self.len)
+            .field("align", &self.align)
+            .finish()
+    }
+}
+
 /// Return `true` if two memory ranges do not overlap.
 ///
 /// # Safety
@@ -56,14 +152,14 @@ pub unsafe fn non_overlapping(a: *const u8, a_len: usize, b: *const u8, b_len: u
 /// types used internally (e.g., fixed-layout structs) this is acceptable.
 #[inline]
 pub fn as_u8_slice<T>(vals: &[T]) -> &[u8] {
-    let len = vals.len() * mem::size_of::<T>();
+    let len = mem::size_of_val(vals);
     unsafe { slice::from_raw_parts(vals.as_ptr() as *const u8, len) }
 }
 
 /// Mutable variant of [`as_u8_slice`].
 #[inline]
 pub fn as_u8_slice_mut<T>(vals: &mut [T]) -> &mut [u8] {
-    let len = vals.len() * mem::size_of::<T>();
+    let len = mem::size_of_val(vals);
     unsafe { slice::from_raw_parts_mut(vals.as_mut_ptr() as *mut u8, len) }
 }
 
@@ -94,6 +190,38 @@ pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
     diff == 0
 }
 
+/// Fill `out` with cryptographically secure random bytes.
+///
+/// All randomness in the crate (salts, nonces, blinding factors) should come
+/// from here rather than ad-hoc RNGs. Backed by the OS CSPRNG (`getrandom`);
+/// panics if the OS source is unavailable, as `rand::thread_rng` would.
+///
+/// With the `deterministic_rng` feature the OS source is swapped for a
+/// reproducible BLAKE3-XOF stream (fixed seed + per-process call counter), so
+/// tests and fixtures get stable output. **Never** enable it in production.
+pub fn random_bytes(out: &mut [u8]) {
+    #[cfg(not(feature = "deterministic_rng"))]
+    getrandom::getrandom(out).expect("OS randomness unavailable");
+
+    #[cfg(feature = "deterministic_rng")]
+    {
+        use std::sync::atomic::AtomicU64;
+        static CALLS: AtomicU64 = AtomicU64::new(0);
+        let n = CALLS.fetch_add(1, Ordering::Relaxed);
+        let mut h = blake3::Hasher::new_derive_key("animica_native deterministic_rng v1");
+        h.update(&n.to_le_bytes());
+        h.finalize_xof().fill(out);
+    }
+}
+
+/// Return `N` secure random bytes; see [`random_bytes`].
+#[inline]
+pub fn random_array<const N: usize>() -> [u8; N] {
+    let mut out = [0u8; N];
+    random_bytes(&mut out);
+    out
+}
+
 /// XOR `src` into `dst` in place. Lengths must match.
 #[inline]
 pub fn xor_in_place(dst: &mut [u8], src: &[u8]) -> NativeResult<()> {
@@ -101,7 +229,7 @@ pub fn xor_in_place(dst: &mut [u8], src: &[u8]) -> NativeResult<()> {
         return Err(NativeError::InvalidArgument("xor_in_place length mismatch"));
     }
     // Optional SIMD path (x86_64 AVX2) for large buffers.
-    #[cfg(all(target_arch = "x86_64"))]
+    #[cfg(target_arch = "x86_64")]
     {
         if cpu_features().avx2 && dst.len() >= 64 {
             // SAFETY: We fall back to scalar path if alignment is poor; we only
@@ -128,27 +256,32 @@ pub fn xor3_into(dst: &mut [u8], a: &[u8], b: &[u8]) -> NativeResult<()> {
     Ok(())
 }
 
-#[cfg(all(target_arch = "x86_64"))]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
 unsafe fn xor_in_place_avx2(dst: &mut [u8], src: &[u8]) {
     use core::arch::x86_64::*;
     let mut i = 0usize;
     let n = dst.len();
     const W: usize = 32; // 256-bit
-    // Main loop on 32B lanes.
-    while i + W <= n {
-        let d_ptr = dst.as_mut_ptr().add(i) as *mut __m256i;
-        let s_ptr = src.as_ptr().add(i) as *const __m256i;
-        // Unaligned loads/stores are fine with AVX2, may cost a cycle if crossing cache lines.
-        let dv = _mm256_loadu_si256(d_ptr);
-        let sv = _mm256_loadu_si256(s_ptr);
-        let x = _mm256_xor_si256(dv, sv);
-        _mm256_storeu_si256(d_ptr, x);
-        i += W;
-    }
-    // Tail
-    while i < n {
-        *dst.get_unchecked_mut(i) ^= *src.get_unchecked(i);
-        i += 1;
+    // SAFETY: caller checked AVX2 support and `dst.len() == src.len()`; every
+    // access below stays within `0..n`.
+    unsafe {
+        // Main loop on 32B lanes.
+        while i + W <= n {
+            let d_ptr = dst.as_mut_ptr().add(i) as *mut __m256i;
+            let s_ptr = src.as_ptr().add(i) as *const __m256i;
+            // Unaligned loads/stores are fine with AVX2, may cost a cycle if crossing cache lines.
+            let dv = _mm256_loadu_si256(d_ptr);
+            let sv = _mm256_loadu_si256(s_ptr);
+            let x = _mm256_xor_si256(dv, sv);
+            _mm256_storeu_si256(d_ptr, x);
+            i += W;
+        }
+        // Tail
+        while i < n {
+            *dst.get_unchecked_mut(i) ^= *src.get_unchecked(i);
+            i += 1;
+        }
     }
 }
 
@@ -170,13 +303,35 @@ pub fn check_len_eq(a: usize, b: usize, context: &'static str) -> NativeResult<(
     Ok(())
 }
 
+/// View `buf` as consecutive fixed-size records (e.g. 32-byte digests),
+/// yielding `&[u8; N]` without copying.
+///
+/// Errors if `N == 0` or `buf.len()` is not a multiple of `N`, so a truncated
+/// trailing record can't be silently dropped.
+#[inline]
+pub fn exact_chunks<const N: usize>(buf: &[u8]) -> NativeResult<impl Iterator<Item = &[u8; N]>> {
+    if N == 0 {
+        return Err(NativeError::InvalidArgument("exact_chunks record size must be > 0"));
+    }
+    if !buf.len().is_multiple_of(N) {
+        return Err(NativeError::InvalidArgument("buffer length is not a multiple of the record size"));
+    }
+    Ok(buf
+        .chunks_exact(N)
+        .map(|c| <&[u8; N]>::try_from(c).expect("chunks_exact yields N-byte chunks")))
+}
+
 /// CPU feature bits cached at first use.
 #[derive(Debug, Clone, Copy)]
 pub struct CpuFeatures {
+    /// x86_64: Supplemental SSE3 (128-bit byte shuffles, `pshufb`).
+    pub ssse3: bool,
     /// x86_64: Advanced Vector Extensions 2 (256-bit integer ops).
     pub avx2: bool,
     /// x86_64: Intel SHA Extensions (SHA-NI). (Note: not widely used here.)
     pub sha_ni: bool,
+    /// x86_64: AVX-512 Foundation (512-bit vectors).
+    pub avx512f: bool,
     /// aarch64: NEON SIMD.
     pub neon: bool,
     /// aarch64: SHA2 crypto extension present.
@@ -190,14 +345,23 @@ static CPU_FEATS: OnceLock<CpuFeatures> = OnceLock::new();
 pub fn cpu_features() -> &'static CpuFeatures {
     CPU_FEATS.get_or_init(|| {
         // Defaults
-        let mut feats = CpuFeatures { avx2: false, sha_ni: false, neon: false, sha2: false };
+        let mut feats = CpuFeatures {
+            ssse3: false,
+            avx2: false,
+            sha_ni: false,
+            avx512f: false,
+            neon: false,
+            sha2: false,
+        };
 
         // x86_64
         #[cfg(target_arch = "x86_64")]
         {
+            feats.ssse3 = std::is_x86_feature_detected!("ssse3");
             feats.avx2 = std::is_x86_feature_detected!("avx2");
             // "sha" for SHA-NI; returns true on CPUs with the SHA extensions.
             feats.sha_ni = std::is_x86_feature_detected!("sha");
+            feats.avx512f = std::is_x86_feature_detected!("avx512f");
         }
 
         // aarch64
@@ -239,16 +403,34 @@ where
     #[cfg(feature = "rayon")]
     {
         use rayon::prelude::*;
-        buf.par_chunks_mut(chunk_size).for_each(|c| f(c));
-        return;
+        rayon_pool::install(|| buf.par_chunks_mut(chunk_size).for_each(f));
     }
 
     // Fallback: sequential
+    #[cfg(not(feature = "rayon"))]
     for c in buf.chunks_mut(chunk_size) {
         f(c);
     }
 }
 
+/// Domain-separated digest of every shard, in input order.
+///
+/// With `rayon`, shards are hashed in parallel through an indexed
+/// `par_iter().collect()`, which keeps `out[i] == hash_ds(tag, &shards[i])`
+/// (unlike `par_bridge`, which does not preserve order).
+pub fn hash_shards(shards: &[Vec<u8>], tag: DsTag) -> Vec<Digest32> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        rayon_pool::install(|| shards.par_iter().map(|s| hash_ds(tag, s)).collect())
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        shards.iter().map(|s| hash_ds(tag, s)).collect()
+    }
+}
+
 /// Copy `src` into `dst`, returning an error if lengths differ.
 /// Safer than `copy_from_slice` when lengths are derived from external inputs.
 #[inline]
@@ -286,6 +468,36 @@ mod tests {
         assert_eq!(&v, &[0, 0, 0, 0]);
     }
 
+    #[test]
+    fn test_random_bytes_fill_and_differ() {
+        // 64 bytes: the odds of an all-zero CSPRNG output are nil.
+        let mut a = [0u8; 64];
+        let mut b = [0u8; 64];
+        random_bytes(&mut a);
+        random_bytes(&mut b);
+        assert_ne!(a, b);
+        // Every byte is written: a fresh fill never leaves the tail zeroed.
+        assert!(a[32..].iter().any(|&x| x != 0) && b[32..].iter().any(|&x| x != 0));
+        let c: [u8; 32] = random_array();
+        let d: [u8; 32] = random_array();
+        assert_ne!(c, d);
+        random_bytes(&mut []);
+    }
+
+    #[test]
+    fn test_exact_chunks() {
+        let buf: Vec<u8> = (0..96u8).collect();
+        let recs: Vec<&[u8; 32]> = exact_chunks::<32>(&buf).unwrap().collect();
+        assert_eq!(recs.len(), 3);
+        assert_eq!(recs[2][0], 64);
+        // Borrowed, not copied.
+        assert!(core::ptr::eq(recs[1].as_ptr(), buf[32..].as_ptr()));
+        assert_eq!(exact_chunks::<32>(&[]).unwrap().count(), 0);
+
+        assert!(exact_chunks::<32>(&buf[..95]).is_err());
+        assert!(exact_chunks::<0>(&buf).is_err());
+    }
+
     #[test]
     fn test_xor() {
         let mut d = [0xAAu8, 0x00, 0xFF];
@@ -301,6 +513,47 @@ mod tests {
         assert!(copy_checked(&mut d, &[1,2]).is_err());
     }
 
+    #[test]
+    fn test_aligned_bytes_alignment() {
+        for align in [1usize, 8, 32, 64, 4096] {
+            for len in [0usize, 1, 31, 1000] {
+                let mut buf = AlignedBytes::with_capacity(len, align).unwrap();
+                assert_eq!(buf.len(), len);
+                assert_eq!(buf.alignment(), align);
+                assert!(buf.is_aligned(align), "len={len} align={align}");
+                assert_eq!(buf.as_ptr() as usize % align, 0);
+                assert!(buf.iter().all(|&b| b == 0));
+                buf.fill(0x5A);
+                assert!(buf.iter().all(|&b| b == 0x5A));
+            }
+        }
+        assert!(AlignedBytes::with_capacity(16, 0).is_err());
+        assert!(AlignedBytes::with_capacity(16, 24).is_err());
+    }
+
+    #[test]
+    fn test_aligned_bytes_feeds_xor() {
+        let mut d = AlignedBytes::with_capacity(256, 32).unwrap();
+        let mut s = AlignedBytes::with_capacity(256, 32).unwrap();
+        for (i, b) in s.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        xor_in_place(&mut d, &s).unwrap();
+        assert_eq!(&d[..], &s[..]);
+    }
+
+    #[test]
+    fn test_hash_shards_ordered() {
+        // Uneven sizes so parallel workers finish out of order.
+        let shards: Vec<Vec<u8>> = (0..257u32)
+            .map(|i| vec![i as u8; (i as usize * 37) % 4096])
+            .collect();
+        let got = hash_shards(&shards, DsTag::DaBlob);
+        let want: Vec<Digest32> = shards.iter().map(|s| hash_ds(DsTag::DaBlob, s)).collect();
+        assert_eq!(got, want);
+        assert!(hash_shards(&[], DsTag::DaBlob).is_empty());
+    }
+
     #[test]
     fn test_cpu_features_singleton() {
         let a = cpu_features() as *const _;
@@ -15,6 +15,9 @@
 //!   calls to `init_global` are benign; if already initialized we treat it as Ok.
 //! - Prefer `with_pool` for isolation in libraries to avoid interfering with a
 //!   host's global pool configuration.
+//! - Embedders can bound the crate's own parallel helpers (hash_shards, NMT
+//!   level folding, ...) with [`set_thread_pool_size`]; they run on that
+//!   dedicated pool via [`install`], or on the global pool when it is unset.
 
 use crate::error::{NativeError, NativeResult};
 
@@ -110,8 +113,89 @@ where
     maybe_par_map_impl(input, threshold, f)
 }
 
+/// Route the crate's parallel helpers onto a dedicated pool of `n` threads.
+///
+/// The pool can be configured once per process: repeating the call with the
+/// same `n` is a no-op, a different `n` is rejected. Without the `rayon`
+/// feature this only validates `n`.
+pub fn set_thread_pool_size(n: usize) -> NativeResult<()> {
+    if n == 0 {
+        return Err(NativeError::InvalidArgument("set_thread_pool_size: n must be >= 1"));
+    }
+    set_thread_pool_size_impl(n)
+}
+
+/// Run `f` on the pool chosen by [`set_thread_pool_size`], or on the Rayon
+/// global pool if none was set. Parallel helpers wrap their rayon work in this.
+#[inline]
+pub fn install<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    install_impl(f)
+}
+
 /* ------------------ cfg(feature = "rayon") impls ------------------ */
 
+#[cfg(feature = "rayon")]
+static CRATE_POOL: std::sync::OnceLock<rayon::ThreadPool> = std::sync::OnceLock::new();
+
+#[cfg(feature = "rayon")]
+fn set_thread_pool_size_impl(n: usize) -> NativeResult<()> {
+    if let Some(pool) = CRATE_POOL.get() {
+        return check_pool_size(pool, n);
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(n)
+        .thread_name(|i| format!("animica-native-{:02}", i + 1))
+        .build()
+        .map_err(|_| NativeError::Internal("failed to build native rayon pool"))?;
+    // Lost a race with another caller: keep theirs, compare sizes.
+    match CRATE_POOL.set(pool) {
+        Ok(()) => Ok(()),
+        Err(_) => check_pool_size(CRATE_POOL.get().expect("pool set"), n),
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn check_pool_size(pool: &rayon::ThreadPool, n: usize) -> NativeResult<()> {
+    if pool.current_num_threads() == n {
+        Ok(())
+    } else {
+        Err(NativeError::InvalidArgument("set_thread_pool_size: pool size already set"))
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+fn set_thread_pool_size_impl(_n: usize) -> NativeResult<()> {
+    // No-op: rayon disabled, helpers are sequential.
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[inline]
+fn install_impl<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    match CRATE_POOL.get() {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+#[inline]
+fn install_impl<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    f()
+}
+
 #[cfg(feature = "rayon")]
 fn init_global_impl(cfg: &PoolConfig) -> NativeResult<()> {
     use rayon::ThreadPoolBuilder;
@@ -167,7 +251,7 @@ impl PoolGuard {
             builder = builder.stack_size(sz);
         }
 
-        let pool = builder.build().map_err(|_| NativeError::Other("failed to build local rayon pool"))?;
+        let pool = builder.build().map_err(|_| NativeError::Internal("failed to build local rayon pool"))?;
         Ok(Self { pool })
     }
 
@@ -210,9 +294,9 @@ where
 {
     use rayon::prelude::*;
     if input.len() >= threshold {
-        input.par_iter().map(|t| f(t)).collect()
+        install(|| input.par_iter().map(&f).collect())
     } else {
-        input.iter().map(|t| f(t)).collect()
+        input.iter().map(f).collect()
     }
 }
 
@@ -223,7 +307,7 @@ where
     U: Send,
     F: Fn(&T) -> U + Sync,
 {
-    input.iter().map(|t| f(t)).collect()
+    input.iter().map(f).collect()
 }
 
 /* ------------------------------ Tests ------------------------------ */
@@ -259,6 +343,27 @@ mod tests {
         assert_eq!(res, 5);
     }
 
+    #[test]
+    fn dedicated_pool_bounds_parallel_helpers() {
+        use crate::hash::{hash_ds, DsTag};
+
+        assert!(set_thread_pool_size(0).is_err());
+        set_thread_pool_size(2).unwrap();
+        set_thread_pool_size(2).unwrap(); // same size: no-op
+
+        #[cfg(feature = "rayon")]
+        {
+            assert!(set_thread_pool_size(3).is_err());
+            assert_eq!(install(rayon::current_num_threads), 2);
+        }
+
+        let shards: Vec<Vec<u8>> = (0..64u8).map(|i| vec![i; 1 + i as usize]).collect();
+        let got = crate::utils::hash_shards(&shards, DsTag::DaBlob);
+        for (s, d) in shards.iter().zip(&got) {
+            assert_eq!(*d, hash_ds(DsTag::DaBlob, s));
+        }
+    }
+
     #[test]
     fn global_init_idempotent() {
         let cfg = PoolConfig::default();
@@ -77,3 +77,26 @@ fn blob_encode_recover_with_random_losses() {
         );
     }
 }
+
+#[test]
+fn bench_api_reconstruct_recovers_partial_set() {
+    let data: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i.wrapping_mul(37) ^ 0x5a; 64]).collect();
+    let full = bench_api::encode(&data, 3);
+
+    let mut partial: Vec<Option<Vec<u8>>> = full.iter().cloned().map(Some).collect();
+    for idx in [0, 2, 5] {
+        partial[idx] = None;
+    }
+    assert_eq!(bench_api::reconstruct(partial, 4, 3), full);
+}
+
+#[test]
+#[should_panic(expected = "need at least k = 4")]
+fn bench_api_reconstruct_panics_on_insufficient_shards() {
+    let data: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 16]).collect();
+    let mut partial: Vec<Option<Vec<u8>>> = bench_api::encode(&data, 2).into_iter().map(Some).collect();
+    for idx in [1, 3, 4] {
+        partial[idx] = None;
+    }
+    bench_api::reconstruct(partial, 4, 2);
+}
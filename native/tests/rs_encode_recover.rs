@@ -2,7 +2,7 @@ mod common;
 
 use animica_native::rs::{self, RsParams};
 
-use common::{XorShift64, DEFAULT_TEST_SEED};
+use common::{choose_k_of_n, XorShift64, DEFAULT_TEST_SEED};
 
 fn make_random_codeword(
     k: usize,
@@ -128,3 +128,30 @@ fn rs_encode_recover_random_messages_exact_recovery() {
         }
     }
 }
+
+#[test]
+fn ffi_and_safe_paths_agree_on_random_inputs() {
+    let mut rng = XorShift64::new(DEFAULT_TEST_SEED ^ 0xFF1_C0DE);
+    for &(k, m, shard_len) in &[(1usize, 1usize, 1usize), (4, 2, 64), (10, 4, 257), (16, 16, 1024)] {
+        let params = RsParams::new(k, m);
+        for _ in 0..4 {
+            let data: Vec<Vec<u8>> = (0..k).map(|_| common::random_bytes(shard_len, &mut rng)).collect();
+            let losses = (rng.next_u32() as usize) % (m + 1);
+            let missing = choose_k_of_n(k + m, losses, &mut rng);
+
+            let shards = rs::ffi_roundtrip_test_helper(params, &data, &missing)
+                .unwrap_or_else(|e| panic!("k={k} m={m} len={shard_len} missing={missing:?}: {e}"));
+            assert_eq!(shards[..k], data[..]);
+        }
+    }
+
+    // Too many erasures fail on both paths rather than diverging.
+    let data = vec![vec![7u8; 8]; 3];
+    assert!(matches!(
+        rs::ffi_roundtrip_test_helper(RsParams::new(3, 2), &data, &[0, 1, 2]),
+        Err(rs::RsError::NotEnoughShards)
+    ));
+    // Bad pointers are rejected at the ABI boundary.
+    let code = animica_native::animica_rs_encode(3, 2, std::ptr::null_mut(), 8, 40);
+    assert_eq!(code, animica_native::ffi_codes::INVALID_ARGUMENT);
+}
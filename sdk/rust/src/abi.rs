@@ -21,12 +21,22 @@
 //! Validation checks:
 //! - Contract/function/event/error names must be non-empty and match `[A-Za-z_][A-Za-z0-9_]*`.
 //! - No duplicate function/event/error names within the ABI.
+//! - No two functions share a 4-byte selector (`keccak256("name(types)")[..4]`),
+//!   which would make call decoding ambiguous.
 //! - Parameter names must be unique within a function/event and valid identifiers (empty allowed for returns).
 //! - Parameter types must be recognized (`bool`, signed/unsigned ints, `bytes`, `bytes<N>`, `string`,
 //!   `address`, and `<type>[]` arrays).
 //! - Event `indexed` parameters must not be dynamic (`bytes`, `string`, arrays).
 //!
-//! This module is intentionally conservative; encoding/decoding is handled elsewhere.
+//! This module is intentionally conservative. It encodes call data
+//! ([`Function::encode_call`], [`encode_values`]) and single-word values
+//! ([`AbiValue::encode_word`]), and decodes revert payloads ([`Abi::decode_revert`]).
+//!
+//! ### Addresses
+//! An `address` is carried on-chain as one 32-byte word holding the
+//! `sha3_256(pubkey)` hash from the bech32m `anim1…` payload. The algorithm id
+//! is not part of the word, so turning a word back into an address needs the
+//! expected `alg_id` (see [`decode_address`]).
 
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
@@ -127,6 +137,22 @@ impl AbiType {
     }
 }
 
+impl std::fmt::Display for AbiType {
+    /// Canonical type token, as used in signatures (e.g. `u64`, `bytes32`, `string[]`).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbiType::Bool => f.write_str("bool"),
+            AbiType::U { bits } => write!(f, "u{bits}"),
+            AbiType::I { bits } => write!(f, "i{bits}"),
+            AbiType::Bytes => f.write_str("bytes"),
+            AbiType::FixedBytes(n) => write!(f, "bytes{n}"),
+            AbiType::String => f.write_str("string"),
+            AbiType::Address => f.write_str("address"),
+            AbiType::Array(inner) => write!(f, "{inner}[]"),
+        }
+    }
+}
+
 /// Parse a type string into `AbiType`.
 pub fn parse_type(s: &str) -> Option<AbiType> {
     // Arrays: recursive `<inner>[]`
@@ -162,12 +188,155 @@ pub fn parse_type(s: &str) -> Option<AbiType> {
     }
 }
 
+// ---------- Values ------------------------------------------------------------
+
+/// Concrete value for an ABI parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    Bool(bool),
+    /// Unsigned integer (values wider than 128 bits are not representable here).
+    U(u128),
+    /// Signed integer (two's complement on the wire).
+    I(i128),
+    Bytes(Vec<u8>),
+    FixedBytes(Vec<u8>),
+    String(String),
+    /// Canonical (lowercase bech32m) `anim1…` address string.
+    Address(String),
+    Array(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    /// Build an `Address` value, validating and canonicalizing the bech32m string.
+    pub fn address(addr: &str) -> Result<Self> {
+        let (alg_id, hash) = crate::wallet::validate_address(addr)?;
+        Ok(AbiValue::Address(decode_address(&hash, alg_id)))
+    }
+
+    /// Encode a static value into its 32-byte ABI word.
+    pub fn encode_word(&self) -> Result<[u8; 32]> {
+        let mut w = [0u8; 32];
+        match self {
+            AbiValue::Bool(b) => w[31] = *b as u8,
+            AbiValue::U(v) => w[16..].copy_from_slice(&v.to_be_bytes()),
+            AbiValue::I(v) => {
+                if *v < 0 {
+                    w = [0xff; 32];
+                }
+                w[16..].copy_from_slice(&v.to_be_bytes());
+            }
+            AbiValue::FixedBytes(b) => {
+                if b.is_empty() || b.len() > 32 {
+                    return Err(Error::Abi(format!("bytesN length out of range: {}", b.len())));
+                }
+                w[..b.len()].copy_from_slice(b);
+            }
+            AbiValue::Address(a) => w = encode_address(a)?,
+            AbiValue::Bytes(_) | AbiValue::String(_) | AbiValue::Array(_) => {
+                return Err(Error::Abi("dynamic value has no single-word encoding".into()))
+            }
+        }
+        Ok(w)
+    }
+
+    /// Decode a static value of type `t` from a 32-byte ABI word.
+    ///
+    /// Addresses need the algorithm id to re-form the bech32m string; use
+    /// [`AbiValue::address_from_word`] for those.
+    pub fn decode_word(t: &AbiType, word: &[u8; 32]) -> Result<Self> {
+        match t {
+            AbiType::Bool => match word[31] {
+                0 | 1 if word[..31].iter().all(|b| *b == 0) => Ok(AbiValue::Bool(word[31] == 1)),
+                _ => Err(Error::Abi("invalid bool word".into())),
+            },
+            AbiType::U { .. } => {
+                if word[..16].iter().any(|b| *b != 0) {
+                    return Err(Error::Abi("unsigned value exceeds 128 bits".into()));
+                }
+                let mut b = [0u8; 16];
+                b.copy_from_slice(&word[16..]);
+                Ok(AbiValue::U(u128::from_be_bytes(b)))
+            }
+            AbiType::I { .. } => {
+                let mut b = [0u8; 16];
+                b.copy_from_slice(&word[16..]);
+                let v = i128::from_be_bytes(b);
+                let ext = if v < 0 { 0xff } else { 0x00 };
+                if word[..16].iter().any(|x| *x != ext) {
+                    return Err(Error::Abi("signed value exceeds 128 bits".into()));
+                }
+                Ok(AbiValue::I(v))
+            }
+            AbiType::FixedBytes(n) if *n <= 32 => {
+                Ok(AbiValue::FixedBytes(word[..*n as usize].to_vec()))
+            }
+            AbiType::Address => Err(Error::Abi(
+                "address words need an alg_id; use AbiValue::address_from_word".into(),
+            )),
+            _ => Err(Error::Abi("type has no single-word encoding".into())),
+        }
+    }
+
+    /// Decode an `address` word into a canonical `Address` value for `alg_id`.
+    pub fn address_from_word(word: &[u8; 32], alg_id: u16) -> Self {
+        AbiValue::Address(decode_address(word, alg_id))
+    }
+}
+
+/// Encode a bech32m `anim1…` address into its on-chain 32-byte pubkey-hash word.
+pub fn encode_address(addr: &str) -> Result<[u8; 32]> {
+    let (_alg_id, hash) = crate::wallet::validate_address(addr)?;
+    Ok(hash)
+}
+
+/// Re-form the canonical `anim1…` address from an on-chain word and its `alg_id`.
+pub fn decode_address(word: &[u8; 32], alg_id: u16) -> String {
+    crate::address::Address { alg_id, hash: *word }.encode()
+}
+
 impl Param {
     pub fn parsed_type(&self) -> Result<AbiType> {
         parse_type(&self.typ).ok_or_else(|| Error::Abi(format!("unknown type: {}", self.typ)))
     }
 }
 
+// ---------- Signature strings -------------------------------------------------
+
+/// Split `name(params) rest` into its trimmed parts.
+fn split_signature(sig: &str) -> Result<(&str, &str, &str)> {
+    let bad = || Error::Abi(format!("malformed signature: '{sig}'"));
+    let (name, after) = sig.trim().split_once('(').ok_or_else(bad)?;
+    let (params, rest) = after.split_once(')').ok_or_else(bad)?;
+    Ok((name.trim(), params.trim(), rest.trim()))
+}
+
+/// Parse a comma-separated `type [indexed] [name]` list (`indexed` only if `allow_indexed`).
+fn parse_params(list: &str, allow_indexed: bool, sig: &str) -> Result<Vec<Param>> {
+    if list.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    list.split(',')
+        .map(|item| {
+            let mut tokens = item.split_whitespace();
+            let typ = tokens
+                .next()
+                .ok_or_else(|| Error::Abi(format!("empty parameter in '{sig}'")))?;
+            let typ = parse_type(typ)
+                .ok_or_else(|| Error::Abi(format!("unknown type '{typ}' in '{sig}'")))?
+                .to_string();
+            let mut next = tokens.next();
+            let indexed = allow_indexed && next == Some("indexed");
+            if indexed {
+                next = tokens.next();
+            }
+            if let Some(extra) = tokens.next() {
+                return Err(Error::Abi(format!("unexpected '{extra}' in parameter '{}' of '{sig}'", item.trim())));
+            }
+            Ok(Param { name: next.unwrap_or_default().to_string(), typ, indexed, extra: BTreeMap::new() })
+        })
+        .collect()
+}
+
 // ---------- Validation --------------------------------------------------------
 
 fn is_ident(s: &str) -> bool {
@@ -220,6 +389,22 @@ impl Abi {
             }
         }
 
+        self.validate_selectors()
+    }
+
+    /// Reject functions whose 4-byte selectors collide.
+    pub fn validate_selectors(&self) -> Result<()> {
+        let mut by_selector: BTreeMap<[u8; 4], &Function> = BTreeMap::new();
+        for f in &self.functions {
+            if let Some(prev) = by_selector.insert(f.selector(), f) {
+                return Err(Error::Abi(format!(
+                    "selector collision 0x{}: '{}' and '{}'",
+                    hex::encode(f.selector()),
+                    prev.signature(),
+                    f.signature()
+                )));
+            }
+        }
         Ok(())
     }
 
@@ -240,6 +425,60 @@ impl Abi {
 }
 
 impl Function {
+    /// Parse a human-readable signature such as `inc(u64 delta) -> (u64)`.
+    ///
+    /// Parameter names are optional; the `-> (...)` return list may be omitted
+    /// (or given as a single bare type). The result is validated like a JSON ABI entry.
+    pub fn parse_signature(sig: &str) -> Result<Self> {
+        let (name, inputs, rest) = split_signature(sig)?;
+        let outputs = match rest.strip_prefix("->").map(str::trim) {
+            None if rest.is_empty() => vec![],
+            None => return Err(Error::Abi(format!("unexpected '{rest}' after parameters in '{sig}'"))),
+            Some(outs) => {
+                let list = outs.strip_prefix('(').and_then(|o| o.strip_suffix(')')).unwrap_or(outs);
+                parse_params(list, false, sig)?
+            }
+        };
+        let f = Function {
+            name: name.to_string(),
+            inputs: parse_params(inputs, false, sig)?,
+            outputs,
+            payable: false,
+            extra: BTreeMap::new(),
+        };
+        f.validate(false)?;
+        Ok(f)
+    }
+
+    /// Canonical signature text, e.g. `inc(u64)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, canonical_types(&self.inputs))
+    }
+
+    /// 4-byte selector: first bytes of `keccak256(signature)`.
+    pub fn selector(&self) -> [u8; 4] {
+        crate::utils::hash::selector4(&self.signature())
+    }
+
+    /// Calldata for a call: selector followed by the head/tail-encoded `args`.
+    ///
+    /// Supports every static type, `bytes`, `string` and one-level `<type>[]`
+    /// arrays of those; nested arrays are rejected.
+    pub fn encode_call(&self, args: &[AbiValue]) -> Result<Vec<u8>> {
+        if args.len() != self.inputs.len() {
+            return Err(Error::Abi(format!(
+                "function '{}' expects {} argument(s), got {}",
+                self.name,
+                self.inputs.len(),
+                args.len()
+            )));
+        }
+        let types = self.inputs.iter().map(Param::parsed_type).collect::<Result<Vec<_>>>()?;
+        let mut out = self.selector().to_vec();
+        out.extend_from_slice(&encode_values(&types, args)?);
+        Ok(out)
+    }
+
     pub fn validate(&self, is_event: bool) -> Result<()> {
         if self.name.is_empty() || !is_ident(&self.name) {
             return Err(Error::Abi(format!("invalid function name: {}", self.name)));
@@ -252,7 +491,7 @@ impl Function {
                     p.name, self.name
                 )));
             }
-            if !seen.insert(p.name.clone()) {
+            if !p.name.is_empty() && !seen.insert(p.name.clone()) {
                 return Err(Error::Abi(format!(
                     "duplicate param '{}' in function '{}'",
                     p.name, self.name
@@ -286,6 +525,24 @@ impl Function {
 }
 
 impl Event {
+    /// Parse a human-readable signature such as `Incremented(u64 indexed by)`.
+    ///
+    /// Each parameter is `type [indexed] [name]`; dynamic types cannot be indexed.
+    pub fn parse_signature(sig: &str) -> Result<Self> {
+        let (name, inputs, rest) = split_signature(sig)?;
+        if !rest.is_empty() {
+            return Err(Error::Abi(format!("unexpected '{rest}' after parameters in '{sig}'")));
+        }
+        let e = Event {
+            name: name.to_string(),
+            inputs: parse_params(inputs, true, sig)?,
+            anonymous: false,
+            extra: BTreeMap::new(),
+        };
+        e.validate()?;
+        Ok(e)
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() || !is_ident(&self.name) {
             return Err(Error::Abi(format!("invalid event name: {}", self.name)));
@@ -303,6 +560,16 @@ impl Event {
 }
 
 impl AbiError {
+    /// Canonical signature text, e.g. `RevertReason(string)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, canonical_types(&self.inputs))
+    }
+
+    /// 4-byte selector prefixing revert data for this error.
+    pub fn selector(&self) -> [u8; 4] {
+        crate::utils::hash::selector4(&self.signature())
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() || !is_ident(&self.name) {
             return Err(Error::Abi(format!("invalid error name: {}", self.name)));
@@ -340,6 +607,218 @@ impl Abi {
     pub fn to_pretty_json(&self) -> Result<String> {
         Ok(serde_json::to_string_pretty(self)?)
     }
+
+    /// JSON Schema (draft 2020-12) describing the ABI document format, for
+    /// editors and validators checking hand-written ABIs.
+    ///
+    /// The `type` pattern covers the canonical (lowercase) spelling accepted by
+    /// [`parse_type`]. Structural rules the schema can't express (duplicate
+    /// names, selector collisions, dynamic indexed params) are still only
+    /// caught by [`Abi::validate`].
+    pub fn json_schema() -> serde_json::Value {
+        const IDENT: &str = "^[A-Za-z_][A-Za-z0-9_]*$";
+        const TYPE: &str = r"^(bool|bytes|string|address|u(8|16|32|64|128|256)|i(64|128)|bytes([1-9]|[1-9][0-9]|1[0-9]{2}|2[0-4][0-9]|25[0-6]))(\[\])*$";
+        let params = serde_json::json!({
+            "type": "array",
+            "items": { "$ref": "#/$defs/param" }
+        });
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Animica contract ABI",
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "pattern": IDENT },
+                "functions": { "type": "array", "items": { "$ref": "#/$defs/function" } },
+                "events": { "type": "array", "items": { "$ref": "#/$defs/event" } },
+                "errors": { "type": "array", "items": { "$ref": "#/$defs/error" } }
+            },
+            "$defs": {
+                "param": {
+                    "type": "object",
+                    "required": ["name", "type"],
+                    "properties": {
+                        "name": { "type": "string", "pattern": "^([A-Za-z_][A-Za-z0-9_]*)?$" },
+                        "type": { "type": "string", "pattern": TYPE },
+                        "indexed": { "type": "boolean" }
+                    }
+                },
+                "function": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string", "pattern": IDENT },
+                        "inputs": params,
+                        "outputs": params,
+                        "payable": { "type": "boolean" }
+                    }
+                },
+                "event": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string", "pattern": IDENT },
+                        "inputs": params,
+                        "anonymous": { "type": "boolean" }
+                    }
+                },
+                "error": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string", "pattern": IDENT },
+                        "inputs": params
+                    }
+                }
+            }
+        })
+    }
+
+    /// Human-readable reason for revert `data` (selector + ABI-encoded args).
+    ///
+    /// Matches the ABI's declared errors by selector and renders them as
+    /// `Name(arg, ...)`; falls back to [`decode_error_string`]. Returns `None`
+    /// for unknown selectors or args this module can't decode (arrays).
+    pub fn decode_revert(&self, data: &[u8]) -> Option<String> {
+        if data.len() >= 4 {
+            if let Some(e) = self.errors.iter().find(|e| e.selector() == data[..4]) {
+                let args = decode_flat_args(&e.inputs, &data[4..])?;
+                return Some(format!("{}({})", e.name, args.join(", ")));
+            }
+        }
+        decode_error_string(data)
+    }
+}
+
+/// Comma-joined canonical type tokens of `params` (unknown types kept verbatim, lowercased).
+fn canonical_types(params: &[Param]) -> String {
+    let types: Vec<String> = params
+        .iter()
+        .map(|p| match p.parsed_type() {
+            Ok(t) => t.to_string(),
+            Err(_) => p.typ.trim().to_ascii_lowercase(),
+        })
+        .collect();
+    types.join(",")
+}
+
+/// Head/tail ABI encoding of a value tuple: static values inline as one word,
+/// dynamic ones as an offset word pointing into the tail.
+///
+/// Array elements must be static, `bytes` or `string` (one level of `[]`).
+pub fn encode_values(types: &[AbiType], values: &[AbiValue]) -> Result<Vec<u8>> {
+    if types.len() != values.len() {
+        return Err(Error::Abi(format!("expected {} value(s), got {}", types.len(), values.len())));
+    }
+    let mut head = Vec::with_capacity(32 * values.len());
+    let mut tail = Vec::new();
+    for (i, (t, v)) in types.iter().zip(values).enumerate() {
+        let mismatch = || Error::Abi(format!("argument #{i}: value {v:?} does not match type {t}"));
+        if !t.is_dynamic() {
+            if !static_matches(t, v) {
+                return Err(mismatch());
+            }
+            head.extend_from_slice(&v.encode_word()?);
+            continue;
+        }
+        let offset = (32 * values.len() + tail.len()) as u128;
+        head.extend_from_slice(&AbiValue::U(offset).encode_word()?);
+        match (t, v) {
+            (AbiType::Bytes, AbiValue::Bytes(b)) => push_padded(&mut tail, b),
+            (AbiType::String, AbiValue::String(s)) => push_padded(&mut tail, s.as_bytes()),
+            (AbiType::Array(inner), AbiValue::Array(items)) => {
+                if matches!(**inner, AbiType::Array(_)) {
+                    return Err(Error::Abi(format!("argument #{i}: nested array type {t} is not supported")));
+                }
+                tail.extend_from_slice(&AbiValue::U(items.len() as u128).encode_word()?);
+                let inner_types = vec![(**inner).clone(); items.len()];
+                tail.extend_from_slice(&encode_values(&inner_types, items)?);
+            }
+            _ => return Err(mismatch()),
+        }
+    }
+    head.extend_from_slice(&tail);
+    Ok(head)
+}
+
+/// Whether `v` is a well-formed value of the static type `t`.
+fn static_matches(t: &AbiType, v: &AbiValue) -> bool {
+    match (t, v) {
+        (AbiType::Bool, AbiValue::Bool(_)) | (AbiType::Address, AbiValue::Address(_)) => true,
+        (AbiType::U { bits }, AbiValue::U(x)) => *bits >= 128 || *x >> *bits == 0,
+        (AbiType::I { bits }, AbiValue::I(x)) => {
+            *bits >= 128 || (*x >= -(1i128 << (*bits - 1)) && *x < (1i128 << (*bits - 1)))
+        }
+        (AbiType::FixedBytes(n), AbiValue::FixedBytes(b)) => b.len() == *n as usize,
+        _ => false,
+    }
+}
+
+/// Length word followed by `b` zero-padded to a multiple of 32 bytes.
+fn push_padded(out: &mut Vec<u8>, b: &[u8]) {
+    out.extend_from_slice(&AbiValue::U(b.len() as u128).encode_word().expect("u128 word"));
+    out.extend_from_slice(b);
+    out.resize(out.len() + (32 - b.len() % 32) % 32, 0);
+}
+
+/// Decode the conventional `Error(string)` revert payload into its message.
+pub fn decode_error_string(data: &[u8]) -> Option<String> {
+    let sel = crate::utils::hash::selector4("Error(string)");
+    if data.len() < 4 || data[..4] != sel {
+        return None;
+    }
+    let bytes = dynamic_at(&data[4..], 0)?;
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// 32-byte word at byte offset `off`.
+fn word_at(data: &[u8], off: usize) -> Option<[u8; 32]> {
+    data.get(off..off.checked_add(32)?)?.try_into().ok()
+}
+
+/// Word at `off` read as a (small) offset/length.
+fn usize_at(data: &[u8], off: usize) -> Option<usize> {
+    let w = word_at(data, off)?;
+    if w[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    usize::try_from(u64::from_be_bytes(w[24..].try_into().ok()?)).ok()
+}
+
+/// Payload of the dynamic `bytes`/`string` whose offset is in the head word at `head`.
+fn dynamic_at(data: &[u8], head: usize) -> Option<&[u8]> {
+    let off = usize_at(data, head)?;
+    let len = usize_at(data, off)?;
+    let start = off.checked_add(32)?;
+    data.get(start..start.checked_add(len)?)
+}
+
+/// Render a flat (no arrays) ABI tuple as display strings.
+fn decode_flat_args(params: &[Param], data: &[u8]) -> Option<Vec<String>> {
+    let mut out = Vec::with_capacity(params.len());
+    for (i, p) in params.iter().enumerate() {
+        let t = p.parsed_type().ok()?;
+        let head = i * 32;
+        let rendered = match t {
+            AbiType::Bytes | AbiType::String => {
+                let bytes = dynamic_at(data, head)?;
+                match (t, std::str::from_utf8(bytes)) {
+                    (AbiType::String, Ok(s)) => format!("{s:?}"),
+                    _ => format!("0x{}", hex::encode(bytes)),
+                }
+            }
+            AbiType::Array(_) => return None,
+            AbiType::Address => format!("0x{}", hex::encode(word_at(data, head)?)),
+            _ => match AbiValue::decode_word(&t, &word_at(data, head)?).ok()? {
+                AbiValue::Bool(b) => b.to_string(),
+                AbiValue::U(v) => v.to_string(),
+                AbiValue::I(v) => v.to_string(),
+                AbiValue::FixedBytes(b) => format!("0x{}", hex::encode(b)),
+                _ => return None,
+            },
+        };
+        out.push(rendered);
+    }
+    Some(out)
 }
 
 // ---------- Tests -------------------------------------------------------------
@@ -414,6 +893,66 @@ mod tests {
         assert!(abi.validate().is_err());
     }
 
+    #[test]
+    fn reject_selector_collision() {
+        // Crafted pair: both signatures hash to selector 0xaf7da443.
+        let probe = |name: &str| Function {
+            name: name.into(),
+            inputs: vec![Param { name: "x".into(), typ: "u64".into(), indexed: false, extra: BTreeMap::new() }],
+            outputs: vec![],
+            payable: false,
+            extra: BTreeMap::new(),
+        };
+        let a = probe("probe_64964");
+        let b = probe("probe_174470");
+        assert_eq!(a.selector(), [0xaf, 0x7d, 0xa4, 0x43]);
+        assert_eq!(a.selector(), b.selector());
+
+        let abi = Abi {
+            name: None,
+            functions: vec![a, b],
+            events: vec![],
+            errors: vec![],
+            extra: BTreeMap::new(),
+        };
+        let msg = abi.validate().unwrap_err().to_string();
+        assert!(msg.contains("probe_64964(u64)") && msg.contains("probe_174470(u64)"), "{msg}");
+    }
+
+    /// ABI-encode a single `string` argument after `sel`.
+    fn string_payload(sel: [u8; 4], msg: &str) -> Vec<u8> {
+        let mut out = sel.to_vec();
+        out.extend_from_slice(&AbiValue::U(32).encode_word().unwrap());
+        out.extend_from_slice(&AbiValue::U(msg.len() as u128).encode_word().unwrap());
+        let mut body = msg.as_bytes().to_vec();
+        body.resize(msg.len().div_ceil(32) * 32, 0);
+        out.extend_from_slice(&body);
+        out
+    }
+
+    #[test]
+    fn decode_revert_reasons() {
+        let abi = Abi {
+            name: None,
+            functions: vec![],
+            events: vec![],
+            errors: vec![AbiError {
+                name: "RevertReason".into(),
+                inputs: vec![Param { name: "msg".into(), typ: "string".into(), indexed: false, extra: BTreeMap::new() }],
+                extra: BTreeMap::new(),
+            }],
+            extra: BTreeMap::new(),
+        };
+        let custom = string_payload(abi.errors[0].selector(), "too low");
+        assert_eq!(abi.decode_revert(&custom).as_deref(), Some(r#"RevertReason("too low")"#));
+
+        let std_err = string_payload(crate::utils::hash::selector4("Error(string)"), "nope");
+        assert_eq!(abi.decode_revert(&std_err).as_deref(), Some("nope"));
+        assert_eq!(decode_error_string(&std_err).as_deref(), Some("nope"));
+
+        assert_eq!(abi.decode_revert(&[0xde, 0xad, 0xbe, 0xef]), None);
+    }
+
     #[test]
     fn reject_indexed_dynamic_event_param() {
         let abi = Abi {
@@ -430,4 +969,160 @@ mod tests {
         };
         assert!(abi.validate().is_err());
     }
+
+    #[test]
+    fn address_word_roundtrip() {
+        let addr = crate::address::Address { alg_id: 0x0103, hash: [0x5a; 32] }.encode();
+        assert!(addr.starts_with("anim1"));
+
+        let v = AbiValue::address(&addr.to_uppercase()).unwrap();
+        assert_eq!(v, AbiValue::Address(addr.clone()));
+
+        let word = v.encode_word().unwrap();
+        assert_eq!(word, [0x5a; 32]);
+        assert_eq!(AbiValue::address_from_word(&word, 0x0103), v);
+    }
+
+    #[test]
+    fn address_rejects_invalid() {
+        assert!(AbiValue::address("anim1notanaddress").is_err());
+        assert!(encode_address("0x1111111111111111111111111111111111111111").is_err());
+    }
+
+    #[test]
+    fn static_words_roundtrip() {
+        let cases = [
+            (AbiType::Bool, AbiValue::Bool(true)),
+            (AbiType::U { bits: 64 }, AbiValue::U(42)),
+            (AbiType::I { bits: 64 }, AbiValue::I(-7)),
+            (AbiType::FixedBytes(4), AbiValue::FixedBytes(vec![1, 2, 3, 4])),
+        ];
+        for (t, v) in cases {
+            let w = v.encode_word().unwrap();
+            assert_eq!(AbiValue::decode_word(&t, &w).unwrap(), v);
+        }
+    }
+
+    fn func(name: &str, types: &[&str]) -> Function {
+        Function {
+            name: name.into(),
+            inputs: types
+                .iter()
+                .map(|t| Param { name: String::new(), typ: (*t).into(), indexed: false, extra: BTreeMap::new() })
+                .collect(),
+            outputs: vec![],
+            payable: false,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    fn word(n: u128) -> Vec<u8> {
+        AbiValue::U(n).encode_word().unwrap().to_vec()
+    }
+
+    #[test]
+    fn selector_uses_normalized_types() {
+        let f = func("set", &[" U64 ", "Bytes32[]"]);
+        assert_eq!(f.signature(), "set(u64,bytes32[])");
+        assert_eq!(f.selector(), crate::utils::hash::selector4("set(u64,bytes32[])"));
+    }
+
+    #[test]
+    fn encode_call_head_tail_layout() {
+        let f = func("put", &["u64", "string", "u8[]", "bool"]);
+        let data = f
+            .encode_call(&[
+                AbiValue::U(7),
+                AbiValue::String("hi".into()),
+                AbiValue::Array(vec![AbiValue::U(1), AbiValue::U(2)]),
+                AbiValue::Bool(true),
+            ])
+            .unwrap();
+
+        let mut expected = f.selector().to_vec();
+        expected.extend(word(7));
+        expected.extend(word(4 * 32)); // string tail right after the 4-word head
+        expected.extend(word(6 * 32)); // array after the string's length + one data word
+        expected.extend(word(1));
+        expected.extend(word(2));
+        let mut hi = b"hi".to_vec();
+        hi.resize(32, 0);
+        expected.extend(hi);
+        expected.extend(word(2));
+        expected.extend(word(1));
+        expected.extend(word(2));
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn encode_call_rejects_bad_arguments() {
+        let f = func("inc", &["u8"]);
+        let err = f.encode_call(&[]).unwrap_err().to_string();
+        assert!(err.contains("function 'inc' expects 1 argument(s), got 0"), "{err}");
+
+        assert!(f.encode_call(&[AbiValue::U(256)]).is_err(), "out of range for u8");
+        assert!(f.encode_call(&[AbiValue::Bool(true)]).is_err(), "type mismatch");
+        assert!(func("f", &["bytes4"]).encode_call(&[AbiValue::FixedBytes(vec![1; 3])]).is_err());
+        assert!(func("f", &["u8[][]"])
+            .encode_call(&[AbiValue::Array(vec![AbiValue::Array(vec![])])])
+            .is_err());
+    }
+
+    #[test]
+    fn parse_function_signatures() {
+        let f = Function::parse_signature("inc(u64 delta) -> (u64)").unwrap();
+        assert_eq!(f.name, "inc");
+        assert_eq!((f.inputs[0].name.as_str(), f.inputs[0].typ.as_str()), ("delta", "u64"));
+        assert_eq!(f.outputs.len(), 1);
+        assert!(f.outputs[0].name.is_empty());
+
+        let f = Function::parse_signature("batch(Bytes32[] ids, bytes4, string)").unwrap();
+        assert_eq!(f.signature(), "batch(bytes32[],bytes4,string)");
+        assert!(f.inputs[1].name.is_empty() && f.inputs[2].name.is_empty());
+        assert!(f.outputs.is_empty());
+
+        assert_eq!(Function::parse_signature("get() -> u64").unwrap().outputs[0].typ, "u64");
+        assert!(Function::parse_signature("get()").unwrap().inputs.is_empty());
+
+        for bad in ["inc", "inc(u64", "inc(u65)", "inc(u64 a b)", "inc(u64 indexed a)", "inc() u64", "1x()"] {
+            assert!(Function::parse_signature(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn parse_event_signatures() {
+        let e = Event::parse_signature("Incremented(u64 indexed by, address who, bytes32 indexed)").unwrap();
+        assert_eq!(e.name, "Incremented");
+        assert!(e.inputs[0].indexed && e.inputs[0].name == "by");
+        assert!(!e.inputs[1].indexed && e.inputs[1].name == "who");
+        assert!(e.inputs[2].indexed && e.inputs[2].name.is_empty());
+
+        assert!(Event::parse_signature("Logged(string indexed msg)").is_err(), "dynamic indexed");
+        assert!(Event::parse_signature("Logged(u64[] indexed xs)").is_err(), "array indexed");
+        assert!(Event::parse_signature("Logged(u64) -> (u64)").is_err());
+    }
+
+    #[test]
+    fn json_schema_accepts_known_good_abi() {
+        let schema = jsonschema::JSONSchema::compile(&Abi::json_schema()).expect("valid schema");
+        let good = serde_json::json!({
+            "name": "Counter",
+            "functions": [
+                {"name": "inc", "inputs": [{"name": "delta", "type": "u64"}], "outputs": []},
+                {"name": "get", "inputs": [], "outputs": [{"name": "", "type": "u64"}]},
+                {"name": "batch", "inputs": [{"name": "xs", "type": "bytes32[]"}], "payable": true}
+            ],
+            "events": [{"name": "Incremented", "inputs": [{"name": "by", "type": "u64", "indexed": true}]}],
+            "errors": [{"name": "RevertReason", "inputs": [{"name": "msg", "type": "string"}]}]
+        });
+        assert!(schema.is_valid(&good));
+        assert!(Abi::from_json_str(&good.to_string()).is_ok());
+
+        let mut bad = good.clone();
+        bad["functions"][0]["inputs"][0]["type"] = "u63".into();
+        assert!(!schema.is_valid(&bad));
+        let mut bad = good;
+        bad["events"][0]["name"] = "1nc".into();
+        assert!(!schema.is_valid(&bad));
+    }
 }
@@ -106,6 +106,17 @@ impl Address {
     }
 }
 
+/// Derive the bech32m `anim1…` address string for `(alg_id, public_key)`.
+pub fn derive_address(alg_id: u16, public_key: &[u8]) -> Result<String> {
+    Ok(Address::from_public_key(alg_id, public_key).encode())
+}
+
+/// Validate a bech32m address string and return `(alg_id, sha3_256(pubkey))`.
+pub fn validate_address(s: &str) -> Result<(u16, [u8; 32])> {
+    let addr = Address::decode(s)?;
+    Ok((addr.alg_id, addr.hash))
+}
+
 impl Display for Address {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.write_str(&self.encode())
@@ -122,7 +133,7 @@ impl FromStr for Address {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::RngCore;
+    use crate::utils::random::random_bytes;
 
     const DILITHIUM3: u16 = 0x0103;
     const SPHINCS_128S: u16 = 0x0201;
@@ -131,7 +142,7 @@ mod tests {
     fn roundtrip_from_pubkey() {
         // Fake public key bytes
         let mut pk = vec![0u8; 1952];
-        rand::thread_rng().fill_bytes(&mut pk);
+        random_bytes(&mut pk);
 
         let addr = Address::from_public_key(DILITHIUM3, &pk);
         let s = addr.encode();
@@ -7,12 +7,16 @@
 //! Implemented calls:
 //! - aicf.listProviders         → list providers
 //! - aicf.getProvider           → get one provider
-//! - aicf.listJobs (optional)   → list jobs (if exposed)
+//! - aicf.listJobs (optional)   → list jobs one page at a time (`JobQuery` → `JobPage`)
 //! - aicf.getJob                → fetch a job by id
+//! - watch_job                  → poll aicf.getJob as a stream until a terminal status
+//! - subscribe_job              → `aicf.jobUpdates` over WS (falls back to watch_job)
 //! - aicf.getResult             → fetch a result record by task id
 //! - aicf.getBalance            → provider/accounting balance (if exposed)
 //! - aicf.claimPayout           → claim payouts (if exposed)
 //! - enqueue_ai / enqueue_quantum (dev helper; tries RPC, then REST)
+//! - enqueue_ai_await / enqueue_quantum_await → enqueue, then poll aicf.getResult
+//!   with backoff until the result lands (job failure statuses end the wait early)
 //!
 //! Notes:
 //! * The enqueue methods are primarily for dev/test flows. On production
@@ -21,11 +25,16 @@
 
 use crate::error::{Error, Result};
 use crate::rpc::http::JsonRpcClient;
+use crate::rpc::ws::WsClient;
 use reqwest::{Client as Http, StatusCode, Url};
 use serde::{Deserialize, Serialize};
+use futures::Stream;
 use serde_json::{json, Value as JsonValue};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::time::{sleep, Instant};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -67,6 +76,92 @@ pub struct JobRecord {
     pub extra: serde_json::Map<String, JsonValue>,
 }
 
+impl JobRecord {
+    /// Whether the job has reached a final status (no further transitions).
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status.to_ascii_lowercase().as_str(),
+            "completed" | "failed" | "cancelled" | "canceled" | "expired" | "rejected"
+        )
+    }
+}
+
+/// Filter + paging parameters for `aicf.listJobs`; unset fields are omitted.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requester: Option<String>,
+    /// Page size (server default if unset).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// `next_cursor` from the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl JobQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+    pub fn requester(mut self, requester: impl Into<String>) -> Self {
+        self.requester = Some(requester.into());
+        self
+    }
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+/// One page of `aicf.listJobs`. A node answering with a bare array (no
+/// paging) parses as a single, final page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "JobPageWire")]
+pub struct JobPage {
+    pub jobs: Vec<JobRecord>,
+    /// Cursor for the next page; `None` once exhausted.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JobPageWire {
+    #[serde(rename_all = "camelCase")]
+    Page {
+        #[serde(default)]
+        jobs: Vec<JobRecord>,
+        #[serde(default)]
+        next_cursor: Option<String>,
+    },
+    Bare(Vec<JobRecord>),
+}
+
+impl From<JobPageWire> for JobPage {
+    fn from(w: JobPageWire) -> Self {
+        match w {
+            JobPageWire::Page { jobs, next_cursor } => JobPage { jobs, next_cursor },
+            JobPageWire::Bare(jobs) => JobPage { jobs, next_cursor: None },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResultRecord {
@@ -91,11 +186,35 @@ pub struct EnqueueResponse {
     pub extra: serde_json::Map<String, JsonValue>,
 }
 
+/// Job updates from [`AICFClient::subscribe_job`]: pushed over WS when available,
+/// polled otherwise. Ends after the first terminal status.
+pub struct JobSubscription {
+    inner: Pin<Box<dyn Stream<Item = Result<JobRecord>> + Send>>,
+    websocket: bool,
+}
+
+impl JobSubscription {
+    /// Whether updates are pushed over WebSocket (`false`: polling fallback).
+    pub fn is_websocket(&self) -> bool {
+        self.websocket
+    }
+}
+
+impl Stream for JobSubscription {
+    type Item = Result<JobRecord>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
 #[derive(Clone)]
 pub struct AICFClient {
     rpc: JsonRpcClient,
     http: Option<Http>,
     rest_base: Option<Url>,
+    ws: Option<WsClient>,
+    poll_interval: Duration,
     retries: usize,
     backoff: Duration,
 }
@@ -107,6 +226,8 @@ impl AICFClient {
             rpc: JsonRpcClient::new(rpc_url)?,
             http: None,
             rest_base: None,
+            ws: None,
+            poll_interval: Duration::from_secs(2),
             retries: 3,
             backoff: Duration::from_millis(250),
         })
@@ -124,6 +245,18 @@ impl AICFClient {
         Ok(self)
     }
 
+    /// Use `ws` for push updates in [`AICFClient::subscribe_job`].
+    pub fn with_ws(mut self, ws: WsClient) -> Self {
+        self.ws = Some(ws);
+        self
+    }
+
+    /// Polling interval for [`AICFClient::subscribe_job`] without WS (default 2s).
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
     /// Tune retry behavior for REST fallbacks (RPC retries are handled by the RPC client).
     pub fn with_retries(mut self, retries: usize, backoff: Duration) -> Self {
         self.retries = retries;
@@ -164,6 +297,7 @@ impl AICFClient {
     // -------------------------------- Jobs -----------------------------------
 
     /// Try JSON-RPC aicf.listJobs if available. Some deployments do not expose it.
+    #[deprecated(note = "unpaginated; use `list_jobs_page` or `list_jobs_all` with a `JobQuery`")]
     pub async fn list_jobs(&self, filter: Option<JsonValue>) -> Result<Vec<JobRecord>> {
         self.rpc
             .call("aicf.listJobs", json!([filter.unwrap_or(json!({}))]))
@@ -171,6 +305,20 @@ impl AICFClient {
             .or_else(|e| Err(Error::Rpc(format!("aicf.listJobs: {e}"))))
     }
 
+    /// Fetch one page of jobs matching `query` via aicf.listJobs.
+    pub async fn list_jobs_page(&self, query: &JobQuery) -> Result<JobPage> {
+        self.rpc
+            .call("aicf.listJobs", json!([query]))
+            .await
+            .map_err(|e| Error::Rpc(format!("aicf.listJobs: {e}")))
+    }
+
+    /// Fetch every job matching `query`, following `next_cursor` from
+    /// `query.cursor` (or the first page) until the node stops returning one.
+    pub async fn list_jobs_all(&self, query: JobQuery) -> Result<Vec<JobRecord>> {
+        collect_job_pages(query, |q| async move { self.list_jobs_page(&q).await }).await
+    }
+
     pub async fn get_job(&self, job_id: &str) -> Result<JobRecord> {
         // Prefer RPC. If method missing and REST base is set, try REST.
         match self
@@ -191,6 +339,51 @@ impl AICFClient {
         }
     }
 
+    /// Poll `get_job` every `interval` (plus up to 10% jitter) and yield the job
+    /// record each time its status changes, ending after a terminal status.
+    ///
+    /// This mirrors the WS subscription shape for deployments without WS. Failed
+    /// polls are retried using the client's retry/backoff settings; once those are
+    /// exhausted the error is yielded and the stream ends.
+    pub fn watch_job<'a>(
+        &'a self,
+        job_id: &'a str,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<JobRecord>> + 'a {
+        poll_job_stream(interval, self.retries, self.backoff, None, move || self.get_job(job_id))
+    }
+
+    /// Stream updates for `job_id` until it reaches a terminal status.
+    ///
+    /// With a WS client attached ([`AICFClient::with_ws`]) this subscribes to
+    /// the `aicf.jobUpdates` topic filtered by id, unsubscribing once the job
+    /// is terminal. If there is no WS client or the node rejects the
+    /// subscription, it falls back to [`AICFClient::watch_job`] at the
+    /// configured [`poll interval`](AICFClient::with_poll_interval); so does a
+    /// WS stream that closes before the job finishes.
+    pub async fn subscribe_job(&self, job_id: &str) -> Result<JobSubscription> {
+        let this = self.clone();
+        let id = job_id.to_string();
+        let polled = move |last_status: Option<String>| {
+            let (interval, retries, backoff) = (this.poll_interval, this.retries, this.backoff);
+            let fetch = move || {
+                let (this, id) = (this.clone(), id.clone());
+                async move { this.get_job(&id).await }
+            };
+            poll_job_stream(interval, retries, backoff, last_status, fetch)
+        };
+        if let Some(ws) = &self.ws {
+            let params = json!(["aicf.jobUpdates", { "id": job_id }]);
+            if let Ok(sub) = ws.subscribe_typed::<JobRecord>("subscribe", "unsubscribe", params).await {
+                return Ok(JobSubscription {
+                    inner: Box::pin(job_updates(sub, job_id.to_string(), polled)),
+                    websocket: true,
+                });
+            }
+        }
+        Ok(JobSubscription { inner: Box::pin(polled(None)), websocket: false })
+    }
+
     pub async fn get_result(&self, task_id: &str) -> Result<ResultRecord> {
         match self
             .rpc
@@ -208,7 +401,8 @@ impl AICFClient {
                         .map_err(|e| Error::Http(format!("url: {e}")))?;
                     return self.get_with_retries_json::<ResultRecord>(http, url).await;
                 }
-                Err(Error::Rpc(format!("aicf.getResult: {e}")))
+                // Passed through as-is so `await_result` can tell "not ready" apart.
+                Err(e)
             }
         }
     }
@@ -291,6 +485,60 @@ impl AICFClient {
         }
     }
 
+    /// [`enqueue_ai`](Self::enqueue_ai), then wait for its result (see
+    /// [`await_result`](Self::await_result)).
+    pub async fn enqueue_ai_await(
+        &self,
+        model: &str,
+        prompt: &str,
+        max_units: Option<u64>,
+        meta: Option<JsonValue>,
+        timeout: Duration,
+        poll_every: Duration,
+    ) -> Result<ResultRecord> {
+        let task = self.enqueue_ai(model, prompt, max_units, meta).await?;
+        self.await_result(&task.task_id, timeout, poll_every).await
+    }
+
+    /// [`enqueue_quantum`](Self::enqueue_quantum), then wait for its result (see
+    /// [`await_result`](Self::await_result)).
+    pub async fn enqueue_quantum_await(
+        &self,
+        circuit: JsonValue,
+        shots: u32,
+        max_units: Option<u64>,
+        meta: Option<JsonValue>,
+        timeout: Duration,
+        poll_every: Duration,
+    ) -> Result<ResultRecord> {
+        let task = self.enqueue_quantum(circuit, shots, max_units, meta).await?;
+        self.await_result(&task.task_id, timeout, poll_every).await
+    }
+
+    /// Poll `aicf.getResult` for `task_id` until it returns a record, starting at
+    /// `poll_every` and doubling up to 8× between attempts.
+    ///
+    /// While no result is available (not found, or a retryable error) the job
+    /// is checked via `aicf.getJob`; a failed/cancelled/expired/rejected status
+    /// ends the wait with `Error::Aicf`. Any other `get_result` error, e.g. an
+    /// auth failure or a record that doesn't decode, is returned as is. Gives
+    /// up with `Error::AicfTimeout(task_id)` once `timeout` elapses.
+    pub async fn await_result(
+        &self,
+        task_id: &str,
+        timeout: Duration,
+        poll_every: Duration,
+    ) -> Result<ResultRecord> {
+        await_result_with(
+            task_id,
+            timeout,
+            poll_every,
+            || self.get_result(task_id),
+            || self.get_job(task_id),
+        )
+        .await
+    }
+
     // ------------------------------ Internals ---------------------------------
 
     async fn enqueue_rest(&self, path: &str, payload: JsonValue) -> Result<EnqueueResponse> {
@@ -359,7 +607,7 @@ impl AICFClient {
                             .await
                             .map_err(|e| Error::Http(format!("parse json: {e}")));
                     } else if resp.status() == StatusCode::NOT_FOUND {
-                        return Err(Error::Http("not found".into()));
+                        return Err(Error::NotFound("aicf REST resource"));
                     } else if should_retry_status(resp.status()) && attempt <= self.retries {
                         sleep(self.backoff).await;
                         continue;
@@ -380,6 +628,187 @@ impl AICFClient {
     }
 }
 
+/// Polling core behind [`AICFClient::watch_job`], generic over the fetch so it
+/// can be driven without a node.
+///
+/// With `last_status` (a status the caller already has) the first poll waits
+/// one interval and an unchanged status is not yielded again.
+fn poll_job_stream<F, Fut>(
+    interval: Duration,
+    retries: usize,
+    backoff: Duration,
+    last_status: Option<String>,
+    fetch: F,
+) -> impl Stream<Item = Result<JobRecord>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<JobRecord>>,
+{
+    struct State<F> {
+        fetch: F,
+        last_status: Option<String>,
+        done: bool,
+    }
+
+    let init = State { fetch, last_status, done: false };
+    futures::stream::unfold(init, move |mut st| async move {
+        if st.done {
+            return None;
+        }
+        loop {
+            if st.last_status.is_some() {
+                sleep(jittered(interval)).await;
+            }
+
+            let mut attempt = 0usize;
+            let job = loop {
+                match (st.fetch)().await {
+                    Ok(job) => break job,
+                    Err(_) if attempt < retries => {
+                        attempt += 1;
+                        sleep(backoff).await;
+                    }
+                    Err(e) => {
+                        st.done = true;
+                        return Some((Err(e), st));
+                    }
+                }
+            };
+
+            if st.last_status.as_deref() == Some(job.status.as_str()) {
+                continue;
+            }
+            st.last_status = Some(job.status.clone());
+            st.done = job.is_terminal();
+            return Some((Ok(job), st));
+        }
+    })
+}
+
+/// Paging core behind [`AICFClient::list_jobs_all`], generic over the fetch so
+/// it can be driven without a node.
+async fn collect_job_pages<F, Fut>(mut query: JobQuery, mut fetch: F) -> Result<Vec<JobRecord>>
+where
+    F: FnMut(JobQuery) -> Fut,
+    Fut: Future<Output = Result<JobPage>>,
+{
+    let mut jobs = Vec::new();
+    loop {
+        let page = fetch(query.clone()).await?;
+        jobs.extend(page.jobs);
+        match page.next_cursor.filter(|c| !c.is_empty()) {
+            Some(next) if query.cursor.as_deref() == Some(next.as_str()) => {
+                return Err(Error::Rpc(format!("aicf.listJobs: cursor did not advance: {next}")));
+            }
+            Some(next) => query.cursor = Some(next),
+            None => return Ok(jobs),
+        }
+    }
+}
+
+/// Waiting core behind [`AICFClient::await_result`], generic over the fetches
+/// so it can be driven without a node.
+async fn await_result_with<R, RFut, J, JFut>(
+    task_id: &str,
+    timeout: Duration,
+    poll_every: Duration,
+    mut get_result: R,
+    mut get_job: J,
+) -> Result<ResultRecord>
+where
+    R: FnMut() -> RFut,
+    RFut: Future<Output = Result<ResultRecord>>,
+    J: FnMut() -> JFut,
+    JFut: Future<Output = Result<JobRecord>>,
+{
+    let deadline = Instant::now() + timeout;
+    let max_delay = poll_every * 8;
+    let mut delay = poll_every;
+    loop {
+        match get_result().await {
+            Ok(result) => return Ok(result),
+            Err(e) if !result_pending(&e) => return Err(e),
+            Err(_) => {}
+        }
+        // Not ready (or not reachable yet): a terminal job with no result
+        // other than "completed" will never produce one.
+        if let Ok(job) = get_job().await {
+            if job.is_terminal() && !job.status.eq_ignore_ascii_case("completed") {
+                return Err(Error::Aicf(format!("job {task_id} ended with status {}", job.status)));
+            }
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Error::AicfTimeout(task_id.to_string()));
+        }
+        sleep(std::cmp::min(delay, deadline - now)).await;
+        delay = std::cmp::min(delay * 2, max_delay);
+    }
+}
+
+/// Whether a `get_result` error just means "no result yet": a missing record
+/// or a transient failure. Everything else would fail again on every poll.
+fn result_pending(e: &Error) -> bool {
+    matches!(e, Error::NotFound(_) | Error::HttpStatus { status: 404, .. }) || e.is_retryable()
+}
+
+/// Pushed updates for `job_id` (others are skipped), ending after a terminal
+/// status. The upstream is dropped (unsubscribing a WS subscription) right
+/// after that item.
+///
+/// If the upstream ends first, the rest comes from `polled`, which is given
+/// the last pushed status.
+fn job_updates<S, F, P>(updates: S, job_id: String, polled: F) -> impl Stream<Item = Result<JobRecord>>
+where
+    S: Stream<Item = Result<JobRecord>> + Unpin,
+    F: FnOnce(Option<String>) -> P,
+    P: Stream<Item = Result<JobRecord>>,
+{
+    use futures::StreamExt;
+
+    enum Source<S, F, P> {
+        Pushed { updates: S, polled: F, last_status: Option<String> },
+        Polled(Pin<Box<P>>),
+        Done,
+    }
+
+    let init: Source<S, F, P> = Source::Pushed { updates, polled, last_status: None };
+    futures::stream::unfold(init, move |src| {
+        let job_id = job_id.clone();
+        async move {
+            let (mut updates, polled, mut last_status) = match src {
+                Source::Pushed { updates, polled, last_status } => (updates, polled, last_status),
+                Source::Polled(mut rest) => return rest.next().await.map(|item| (item, Source::Polled(rest))),
+                Source::Done => return None,
+            };
+            loop {
+                match updates.next().await {
+                    Some(Ok(job)) if job.id != job_id => continue,
+                    Some(Ok(job)) if job.is_terminal() => return Some((Ok(job), Source::Done)),
+                    Some(item) => {
+                        if let Ok(job) = &item {
+                            last_status = Some(job.status.clone());
+                        }
+                        return Some((item, Source::Pushed { updates, polled, last_status }));
+                    }
+                    None => {
+                        drop(updates);
+                        let mut rest = Box::pin(polled(last_status));
+                        return rest.next().await.map(|item| (item, Source::Polled(rest)));
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// `interval` plus up to 10% random jitter, so many watchers don't poll in lockstep.
+fn jittered(interval: Duration) -> Duration {
+    let max_ms = (interval.as_millis() as u64) / 10;
+    interval + Duration::from_millis(fastrand::u64(0..=max_ms))
+}
+
 fn should_retry_status(s: StatusCode) -> bool {
     s.is_server_error()
         || s == StatusCode::TOO_MANY_REQUESTS
@@ -452,4 +881,214 @@ mod tests {
         assert_eq!(j.id, "job1");
         assert!(j.extra.contains_key("unknown"));
     }
+
+    #[test]
+    fn job_query_and_page_wire_format() {
+        let q = JobQuery::new().kind("AI").status("Running").limit(50);
+        assert_eq!(serde_json::to_value(&q).unwrap(), json!({ "kind": "AI", "status": "Running", "limit": 50 }));
+        assert_eq!(serde_json::to_value(JobQuery::new()).unwrap(), json!({}));
+
+        let job = json!({ "id": "j1", "kind": "AI", "status": "Queued" });
+        let page: JobPage = serde_json::from_value(json!({ "jobs": [job], "nextCursor": "c2" })).unwrap();
+        assert_eq!(page.jobs.len(), 1);
+        assert_eq!(page.next_cursor.as_deref(), Some("c2"));
+
+        // Older nodes answer with a bare array: a single final page.
+        let page: JobPage = serde_json::from_value(json!([job, job])).unwrap();
+        assert_eq!(page.jobs.len(), 2);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn list_jobs_all_follows_cursor() {
+        let job = |id: &str| -> JobRecord {
+            serde_json::from_value(json!({ "id": id, "kind": "AI", "status": "Completed" })).unwrap()
+        };
+        let mut seen = Vec::new();
+        let fetch = |q: JobQuery| {
+            seen.push(q.cursor.clone());
+            let page = match q.cursor.as_deref() {
+                None => JobPage { jobs: vec![job("a"), job("b")], next_cursor: Some("p2".into()) },
+                Some("p2") => JobPage { jobs: vec![job("c")], next_cursor: None },
+                Some(other) => panic!("unexpected cursor {other}"),
+            };
+            async move { Ok(page) }
+        };
+        let jobs = collect_job_pages(JobQuery::new().limit(2), fetch).await.unwrap();
+        let ids: Vec<_> = jobs.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(seen, [None, Some("p2".to_string())]);
+
+        let stuck = |_q: JobQuery| async { Ok(JobPage { jobs: vec![], next_cursor: Some("same".into()) }) };
+        assert!(collect_job_pages(JobQuery::new().cursor("same"), stuck).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn job_updates_filter_by_id_and_end_on_terminal() {
+        use futures::StreamExt;
+
+        let job = |id: &str, status: &str| -> Result<JobRecord> {
+            Ok(serde_json::from_value(json!({ "id": id, "kind": "AI", "status": status })).unwrap())
+        };
+        let pushed = futures::stream::iter(vec![
+            job("job1", "Queued"),
+            job("other", "Completed"),
+            Err(Error::Serde("decode notification: bad".into())),
+            job("job1", "Running"),
+            job("job1", "Completed"),
+            job("job1", "Running"), // never reached
+        ]);
+        let never_polled = |_| -> futures::stream::Empty<Result<JobRecord>> { unreachable!() };
+        let seen: Vec<_> = job_updates(pushed, "job1".into(), never_polled)
+            .map(|r| r.map(|j| j.status).unwrap_or_else(|_| "err".into()))
+            .collect()
+            .await;
+        assert_eq!(seen, ["Queued", "err", "Running", "Completed"]);
+    }
+
+    #[tokio::test]
+    async fn job_updates_fall_back_to_polling_when_the_push_stream_ends() {
+        use futures::StreamExt;
+
+        let job = |status: &str| -> Result<JobRecord> {
+            Ok(serde_json::from_value(json!({ "id": "job1", "kind": "AI", "status": status })).unwrap())
+        };
+        // The socket goes away while the job is still running.
+        let pushed = futures::stream::iter(vec![job("Queued"), job("Running")]);
+        let polled = move |last: Option<String>| {
+            assert_eq!(last.as_deref(), Some("Running"));
+            let mut polls = vec![job("Completed"), job("Running")];
+            poll_job_stream(Duration::from_millis(1), 0, Duration::ZERO, last, move || {
+                let r = polls.pop().unwrap();
+                async move { r }
+            })
+        };
+        let seen: Vec<_> = job_updates(pushed, "job1".into(), polled).map(|r| r.unwrap().status).collect().await;
+        assert_eq!(seen, ["Queued", "Running", "Completed"]);
+    }
+
+    #[tokio::test]
+    async fn await_result_polls_until_ready_or_failed() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let job = |status: &str| -> Result<JobRecord> {
+            Ok(serde_json::from_value(json!({ "id": "task1", "kind": "AI", "status": status })).unwrap())
+        };
+        let record: ResultRecord = serde_json::from_value(json!({ "taskId": "task1", "status": "Completed" })).unwrap();
+        let ms = Duration::from_millis;
+
+        // Result shows up on the third poll.
+        let calls = AtomicUsize::new(0);
+        let get_result = || {
+            let r = match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(Error::NotFound("aicf REST resource")),
+                1 => Err(Error::Network("connection reset".into())),
+                _ => Ok(record.clone()),
+            };
+            async move { r }
+        };
+        let got = await_result_with("task1", ms(500), ms(1), get_result, || async { job("Running") }).await;
+        assert!(got.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // A failed job short-circuits long before the timeout.
+        let missing = || async { Err::<ResultRecord, _>(Error::NotFound("aicf REST resource")) };
+        let err = await_result_with("task1", Duration::from_secs(60), ms(1), missing, || async { job("Failed") })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Aicf(ref m) if m.contains("task1") && m.contains("Failed")), "{err}");
+
+        // Still running at the deadline: timeout carrying the task id.
+        let err = await_result_with("task1", ms(20), ms(1), missing, || async { job("Running") })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::AicfTimeout(ref id) if id == "task1"), "{err}");
+
+        // A non-retryable error (here a 401) is returned on the first poll.
+        let calls = AtomicUsize::new(0);
+        let unauthorized = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err::<ResultRecord, _>(Error::HttpStatus { status: 401, body: "no token".into() }) }
+        };
+        let err = await_result_with("task1", Duration::from_secs(60), ms(1), unauthorized, || async { job("Running") })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::HttpStatus { status: 401, .. }), "{err}");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_job_without_ws_polls() {
+        let c = AICFClient::new("http://127.0.0.1:9").unwrap().with_poll_interval(Duration::from_millis(10));
+        let sub = c.subscribe_job("job1").await.unwrap();
+        assert!(!sub.is_websocket());
+    }
+
+    #[tokio::test]
+    async fn subscribe_job_streams_ws_updates_until_terminal() {
+        use crate::rpc::ws::WsClientBuilder;
+        use crate::test_util::serve_ws;
+        use futures::StreamExt;
+        use std::sync::{Arc, Mutex};
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        let mock = serve_ws(move |_| {
+            let log = log.clone();
+            move |req: &JsonValue| {
+                log.lock().unwrap().push(req.clone());
+                let mut out = vec![json!({"jsonrpc": "2.0", "id": req["id"], "result": "sub-7"})];
+                if req["method"] == "subscribe" {
+                    for (id, status) in [("job1", "Queued"), ("job2", "Failed"), ("job1", "Running"), ("job1", "Completed")] {
+                        let job = json!({ "id": id, "kind": "AI", "status": status });
+                        out.push(json!({"subscription": "sub-7", "data": job}));
+                    }
+                }
+                Some(out)
+            }
+        })
+        .await;
+
+        // The updates can beat `attach` to the reader; buffer them so none are lost.
+        let ws = WsClientBuilder::from_str(&mock.url).unwrap().replay_buffer(8).build().await.unwrap();
+        let c = AICFClient::new("http://127.0.0.1:9").unwrap().with_ws(ws);
+        let sub = c.subscribe_job("job1").await.unwrap();
+        assert!(sub.is_websocket());
+        let seen: Vec<String> = tokio::time::timeout(Duration::from_secs(2), sub.map(|r| r.unwrap().status).collect())
+            .await
+            .unwrap();
+        assert_eq!(seen, ["Queued", "Running", "Completed"]);
+
+        // Dropping the finished subscription unsubscribes it.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["params"], json!(["aicf.jobUpdates", { "id": "job1" }]));
+        assert!(requests.iter().any(|r| r["method"] == "unsubscribe" && r["params"] == json!(["sub-7"])));
+    }
+
+    #[tokio::test]
+    async fn watch_job_yields_transitions_then_ends() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let job = |status: &str| -> JobRecord {
+            serde_json::from_value(json!({ "id": "job1", "kind": "AI", "status": status })).unwrap()
+        };
+        // Mock node: Running, Running (unchanged → not yielded), a transient error, then Completed.
+        let calls = AtomicUsize::new(0);
+        let fetch = || {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            let r = match n {
+                0 | 1 => Ok(job("Running")),
+                2 => Err(Error::Http("503".into())),
+                _ => Ok(job("Completed")),
+            };
+            async move { r }
+        };
+
+        let stream = poll_job_stream(Duration::from_millis(1), 2, Duration::from_millis(1), None, fetch);
+        let seen: Vec<String> = stream.map(|r| r.unwrap().status).collect().await;
+        assert_eq!(seen, vec!["Running", "Completed"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
 }
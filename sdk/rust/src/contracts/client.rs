@@ -5,6 +5,7 @@
 //! - Read-only calls via JSON-RPC `state.call`
 //! - Heuristic gas estimation for write calls
 //! - Send write transactions (sign → submit → await receipt)
+//! - Deploy code with ABI-encoded constructor args and recover the new address
 //!
 //! This mirrors the Python/TypeScript SDK ergonomics.
 //!
@@ -15,12 +16,13 @@
 //! If your node build doesn't expose `state.call`, you can still use
 //! `encode_call_data` and send the bytes through your own pipeline.
 
-use crate::abi::{decode_return, encode_call, Abi};
+use crate::abi::{decode_return, encode_call, encode_values, Abi, AbiType, AbiValue};
+use crate::contracts::deployer::{DeployOptions, Deployer};
 use crate::error::{Error, Result};
 use crate::rpc::http::RpcClient;
 use crate::tx::build::{build_call, estimate_gas_call, GasEstimate};
 use crate::tx::send::{build_signed_envelope, send_and_wait};
-use crate::types::{AccessListItem, Receipt, Tx};
+use crate::types::{AccessListItem, Receipt, Tx, TxStatus};
 use crate::wallet::signer::TxSigner;
 use serde_json::json;
 use std::time::Duration;
@@ -216,10 +218,109 @@ impl<'r> ContractClient<'r> {
         let _env = build_signed_envelope(&tx, opts.signer)?; // sanity: ensure encoding+signing succeeds
         send_and_wait(self.rpc, &tx, opts.signer, opts.poll_every, opts.timeout).await
     }
+
+    // ------------------------------ Deploy -----------------------------------
+
+    /// ABI-encode constructor arguments against the ABI's `constructor` entry.
+    ///
+    /// An ABI without a `constructor` function takes no arguments; `args` must
+    /// then be `null` or `[]`, and the encoding is empty.
+    pub fn encode_constructor_args(abi: &Abi, args: &serde_json::Value) -> Result<Vec<u8>> {
+        let given: &[serde_json::Value] = match args {
+            serde_json::Value::Null => &[],
+            serde_json::Value::Array(a) => a,
+            _ => return Err(Error::Abi("constructor args must be a JSON array".into())),
+        };
+        let inputs = abi.function("constructor").map(|f| f.inputs.as_slice()).unwrap_or(&[]);
+        if given.len() != inputs.len() {
+            return Err(Error::Abi(format!(
+                "constructor expects {} args, got {}",
+                inputs.len(),
+                given.len()
+            )));
+        }
+        let mut types = Vec::with_capacity(inputs.len());
+        let mut values = Vec::with_capacity(inputs.len());
+        for (p, v) in inputs.iter().zip(given) {
+            let t = p.parsed_type()?;
+            values.push(json_to_abi_value(&t, v)?);
+            types.push(t);
+        }
+        encode_values(&types, &values)
+    }
+
+    /// Deploy `bytecode` with ABI-encoded `constructor_args` through
+    /// [`Deployer::deploy_code_and_wait`] (estimate gas → build → sign → send →
+    /// await receipt).
+    ///
+    /// Returns `(contract_address, tx_hash)`. Fails if the receipt is not
+    /// `SUCCESS` or carries no `contract_address`. Bind a client to the result
+    /// with [`ContractClient::new`].
+    pub async fn deploy<S: TxSigner>(
+        rpc: &'r RpcClient,
+        opts: DeployOptions<'_, S>,
+        bytecode: &[u8],
+        abi: &Abi,
+        constructor_args: &serde_json::Value,
+    ) -> Result<(String, String)> {
+        let ctor_data = Self::encode_constructor_args(abi, constructor_args)?;
+        let (tx_hash, rcpt) = Deployer::new(rpc).deploy_code_and_wait(bytecode, &ctor_data, opts).await?;
+        if rcpt.status != TxStatus::SUCCESS {
+            return Err(Error::RpcResponse(format!(
+                "deploy tx {tx_hash} failed with status {:?}",
+                rcpt.status
+            )));
+        }
+        let address = rcpt.contract_address.ok_or_else(|| {
+            Error::RpcResponse(format!("deploy receipt for {tx_hash} has no contract_address"))
+        })?;
+        Ok((address, tx_hash))
+    }
 }
 
 // --------------------------------- Helpers -----------------------------------
 
+/// Convert a JSON argument into an [`AbiValue`] of type `t`.
+///
+/// Integers accept JSON numbers or decimal / `0x`-hex strings (for values past
+/// `u64`); byte types take `0x`-hex strings.
+fn json_to_abi_value(t: &AbiType, v: &serde_json::Value) -> Result<AbiValue> {
+    let bad = || Error::Abi(format!("cannot encode {v} as {t:?}"));
+    Ok(match t {
+        AbiType::Bool => AbiValue::Bool(v.as_bool().ok_or_else(bad)?),
+        AbiType::U { .. } => AbiValue::U(match v {
+            serde_json::Value::Number(n) => n.as_u64().ok_or_else(bad)? as u128,
+            serde_json::Value::String(s) => match s.strip_prefix("0x") {
+                Some(h) => u128::from_str_radix(h, 16).map_err(|_| bad())?,
+                None => s.parse().map_err(|_| bad())?,
+            },
+            _ => return Err(bad()),
+        }),
+        AbiType::I { .. } => AbiValue::I(match v {
+            serde_json::Value::Number(n) => n.as_i64().ok_or_else(bad)? as i128,
+            serde_json::Value::String(s) => s.parse().map_err(|_| bad())?,
+            _ => return Err(bad()),
+        }),
+        AbiType::Bytes | AbiType::FixedBytes(_) => {
+            let b = v.as_str().and_then(hex_to_bytes).ok_or_else(bad)?;
+            match t {
+                AbiType::FixedBytes(n) if b.len() != *n as usize => return Err(bad()),
+                AbiType::FixedBytes(_) => AbiValue::FixedBytes(b),
+                _ => AbiValue::Bytes(b),
+            }
+        }
+        AbiType::String => AbiValue::String(v.as_str().ok_or_else(bad)?.to_string()),
+        AbiType::Address => AbiValue::address(v.as_str().ok_or_else(bad)?)?,
+        AbiType::Array(inner) => AbiValue::Array(
+            v.as_array()
+                .ok_or_else(bad)?
+                .iter()
+                .map(|x| json_to_abi_value(inner, x))
+                .collect::<Result<_>>()?,
+        ),
+    })
+}
+
 fn to_hex_prefixed(b: &[u8]) -> String {
     let mut s = String::with_capacity(2 + b.len() * 2);
     s.push_str("0x");
@@ -252,6 +353,7 @@ fn u128_to_hex(v: u128) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{serve_http, Response};
     use crate::types::AccessListItem;
 
     #[test]
@@ -294,4 +396,74 @@ mod tests {
         assert_eq!(tx.gas_price, 1_000);
         assert_eq!(tx.value, 0);
     }
+
+    #[test]
+    fn constructor_args_use_head_tail_layout() {
+        let abi = Abi::from_json_str(
+            r#"{"functions":[{"name":"constructor","inputs":[
+                {"name":"supply","type":"u64"},{"name":"symbol","type":"string"}
+            ],"outputs":[]}]}"#,
+        )
+        .unwrap();
+        let enc = ContractClient::encode_constructor_args(&abi, &json!([42, "hi"])).unwrap();
+        assert_eq!(enc.len(), 4 * 32);
+        assert_eq!(enc[31], 42);
+        assert_eq!(enc[63], 64, "string offset points past the two head words");
+        assert_eq!(enc[95], 2, "string length");
+        assert_eq!(&enc[96..98], b"hi");
+        assert!(enc[98..].iter().all(|b| *b == 0));
+
+        assert!(ContractClient::encode_constructor_args(&abi, &json!([1])).is_err());
+        let no_ctor = Abi::from_json_str(r#"{"functions":[]}"#).unwrap();
+        assert!(ContractClient::encode_constructor_args(&no_ctor, &json!(null)).unwrap().is_empty());
+    }
+
+    struct DummySigner;
+    impl TxSigner for DummySigner {
+        fn alg_id(&self) -> u16 { 0x0103 }
+        fn public_key(&self) -> &[u8] { b"pk" }
+        fn sign(&self, _domain: &[u8], _msg: &[u8]) -> Result<Vec<u8>> {
+            Ok(vec![0u8; 32])
+        }
+    }
+
+    /// Mock node: `tx.sendRawTransaction` returns a hash, `tx.getTransactionReceipt`
+    /// a successful deploy receipt carrying `contract_address`.
+    fn spawn_deploy_node() -> String {
+        serve_http(|req| {
+            let req = req.json();
+            let result = match req["method"].as_str() {
+                Some("tx.sendRawTransaction") => json!("0xdeadbeef"),
+                Some("tx.getTransactionReceipt") => json!({
+                    "tx_hash": "0xdeadbeef",
+                    "status": "SUCCESS",
+                    "gas_used": 123_456,
+                    "block_number": 9,
+                    "contract_address": "anim1contract",
+                    "logs": [],
+                }),
+                _ => json!(null),
+            };
+            Response::rpc_result(&req, result)
+        })
+        .url
+    }
+
+    #[tokio::test]
+    async fn deploy_returns_address_from_receipt() {
+        let rpc = RpcClient::new(&spawn_deploy_node());
+        let abi = Abi::from_json_str(
+            r#"{"functions":[{"name":"constructor","inputs":[{"name":"start","type":"u64"}],"outputs":[]}]}"#,
+        )
+        .unwrap();
+        let signer = DummySigner;
+        let mut opts = DeployOptions::with_defaults(1, "anim1from...", 0, 1_000, &signer);
+        opts.poll_every = Duration::from_millis(10);
+        opts.timeout = Duration::from_secs(2);
+
+        let (address, tx_hash) =
+            ContractClient::deploy(&rpc, opts, &[0xc0, 0xde], &abi, &json!([5])).await.unwrap();
+        assert_eq!(address, "anim1contract");
+        assert_eq!(tx_hash, "0xdeadbeef");
+    }
 }
@@ -120,7 +120,7 @@ impl<'r> Deployer<'r> {
     /// Heuristic gas estimation for deploy payload + access list.
     pub fn estimate_gas(&self, manifest: &JsonValue, code: &[u8], access_list: &[AccessListItem]) -> Result<GasEstimate> {
         let payload = self.encode_payload(manifest, code)?;
-        Ok(estimate_gas_deploy(&payload, access_list))
+        Ok(estimate_gas_deploy(&payload, &[], access_list))
     }
 
     /// Build a deploy transaction (unsigned).
@@ -145,6 +145,7 @@ impl<'r> Deployer<'r> {
             gas_price,
             gas_limit,
             payload,
+            b"",
             access_list,
         ))
     }
@@ -157,26 +158,42 @@ impl<'r> Deployer<'r> {
         &self,
         manifest: &JsonValue,
         code: &[u8],
-        mut opts: DeployOptions<'_, S>,
+        opts: DeployOptions<'_, S>,
+    ) -> Result<(String, Receipt)> {
+        let payload = self.encode_payload(manifest, code)?;
+        self.deploy_code_and_wait(&payload, &[], opts).await
+    }
+
+    /// Deploy raw `code` followed by ABI-encoded `constructor_data`:
+    /// (estimate gas) → build → sign → send → await receipt.
+    ///
+    /// The shared tail of [`Self::deploy_and_wait`] and
+    /// `ContractClient::deploy`; returns `(tx_hash, receipt)` whatever the
+    /// receipt status.
+    pub async fn deploy_code_and_wait<S: TxSigner>(
+        &self,
+        code: &[u8],
+        constructor_data: &[u8],
+        opts: DeployOptions<'_, S>,
     ) -> Result<(String, Receipt)> {
         // Determine gas limit
         let gas_limit = match opts.gas_limit {
             Some(gl) => gl,
-            None => self.estimate_gas(manifest, code, opts.access_list)?.suggested_limit,
+            None => estimate_gas_deploy(code, constructor_data, opts.access_list).suggested_limit,
         };
 
         // Build tx
-        let tx = self.build_tx(
+        let tx = build_deploy(
             opts.chain_id,
             opts.from,
+            opts.value,
             opts.nonce,
             opts.gas_price,
             gas_limit,
-            opts.value,
-            manifest,
             code,
+            constructor_data,
             opts.access_list.to_vec(),
-        )?;
+        );
 
         // Sign quickly to surface encoding/sign issues early
         let _env = build_signed_envelope(&tx, opts.signer)?;
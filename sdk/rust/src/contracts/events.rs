@@ -2,20 +2,25 @@
 //!
 //! This module provides:
 //! - `EventDecoder` — build from ABI JSON and decode `Receipt.logs` entries
-//! - `EventFilter`  — simple in-memory filters (by address/name)
+//! - `EventFilter`  — simple in-memory filters (by address/name/indexed value)
 //! - `DecodedEvent` — structured output (name + named params)
+//! - `ErrorDecoder` — decode revert data against the ABI's `errors` array
+//!   (4-byte selector + ABI tuple), falling back to the standard `Error(string)`
 //!
 //! ### Assumptions
 //! - Event topic0 = `keccak256("Name(type1,type2,...)")` (names and `indexed` flags
 //!   are not part of the signature), like Ethereum.
 //! - Topics 1.. encode **indexed** parameters (static types are directly encoded
 //!   as 32-byte words; dynamic types are stored as `keccak256(value)` and are
-//!   surfaced as `<param>_hash` hex strings).
+//!   surfaced as `<param>_hash` hex strings). If the caller knows candidate
+//!   values, `decode_log_with_preimages` also recovers the real value.
 //! - Non-indexed parameters are ABI-encoded in the `data` field (tuple layout).
 //!
 //! Supported types for decoding: `uint<M>`, `int<M>`, `bool`, `address`,
-//! `bytes`, `bytes<M>`, and `string`. Fixed/dynamic arrays are **not** decoded
-//! in this version and will return an error if encountered.
+//! `bytes`, `bytes<M>`, `string`, and one- or two-dimensional dynamic arrays of
+//! the static ones (`uint256[]`, `address[][]`), decoded into JSON arrays.
+//! Fixed-size arrays (`T[N]`) are **not** supported and return an error.
+//! Indexed arrays are hashed like `bytes`/`string` and surface as `<param>_hash`.
 //!
 //! The ABI JSON is expected to contain an `events` array with items like:
 //! ```json
@@ -29,9 +34,10 @@
 //! If your node exposes a filter RPC, you can still use this decoder to parse
 //! the returned logs into strongly-typed JSON values.
 
+use crate::abi::{decode_error_string, AbiValue};
 use crate::error::{Error, Result};
 use crate::types::{Receipt, LogEvent};
-use crate::utils::hash::keccak256;
+use crate::utils::hash::{keccak256, selector4};
 use hex::FromHex;
 use serde::Serialize;
 use serde_json::{json, Map as JsonMap, Value as JsonValue};
@@ -52,11 +58,28 @@ pub struct DecodedEvent {
     pub raw: LogEvent,
 }
 
+/// Decoded custom error (revert reason) with named parameters.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedError {
+    /// Error name from ABI (`"Error"` for the standard `Error(string)` reason).
+    pub name: String,
+    /// Named parameters (JSON-friendly; large integers are decimal strings).
+    /// Unnamed params are keyed `arg<i>`; `Error(string)` uses `reason`.
+    pub params: JsonMap<String, JsonValue>,
+    /// The 4-byte selector as `0x`-hex.
+    pub selector: String,
+}
+
 /// In-memory event filter (client-side convenience).
+///
+/// All set criteria must hold. `indexed` constraints are checked against the
+/// raw topics by [`EventDecoder::decode_receipt`]; events without an indexed
+/// param of that name never match.
 #[derive(Debug, Clone, Default)]
 pub struct EventFilter<'a> {
     pub address: Option<&'a str>,
     pub name: Option<&'a str>,
+    pub indexed: Vec<(&'a str, AbiValue)>,
 }
 
 impl<'a> EventFilter<'a> {
@@ -71,6 +94,13 @@ impl<'a> EventFilter<'a> {
         self.name = Some(name);
         self
     }
+    /// Require indexed param `name` to equal `value` (encoded as its topic:
+    /// the ABI word for static types, `keccak256` of the contents for dynamic ones).
+    /// Addresses may be given as the `0x`-hex form decoded events carry.
+    pub fn indexed(mut self, name: &'a str, value: AbiValue) -> Self {
+        self.indexed.push((name, value));
+        self
+    }
     fn matches(&self, ev: &DecodedEvent) -> bool {
         if let Some(a) = self.address {
             if ev.address != a { return false; }
@@ -115,6 +145,29 @@ impl EventDecoder {
 
     /// Decode a single `LogEvent`. Returns `None` if `topic0` doesn't match any event.
     pub fn decode_log(&self, log: &LogEvent) -> Result<Option<DecodedEvent>> {
+        self.decode_log_inner(log, None)
+    }
+
+    /// Like `decode_log`, but recovers indexed dynamic (`string`/`bytes`) params
+    /// from known preimages. Indexed arrays only ever surface as `<name>_hash`.
+    ///
+    /// `preimages` maps a param name to its candidate value. When
+    /// `keccak256(candidate)` equals the indexed topic, the param is filled in
+    /// under its own name (decoded as for non-indexed data) in addition to the
+    /// usual `<name>_hash`. Non-matching candidates are ignored.
+    pub fn decode_log_with_preimages(
+        &self,
+        log: &LogEvent,
+        preimages: &HashMap<String, Vec<u8>>,
+    ) -> Result<Option<DecodedEvent>> {
+        self.decode_log_inner(log, Some(preimages))
+    }
+
+    fn decode_log_inner(
+        &self,
+        log: &LogEvent,
+        preimages: Option<&HashMap<String, Vec<u8>>>,
+    ) -> Result<Option<DecodedEvent>> {
         if log.topics.is_empty() {
             return Err(Error::Abi("log has no topics".into()));
         }
@@ -146,6 +199,14 @@ impl EventDecoder {
                 // Dynamic types are hashed in topics: expose as "<name>_hash"
                 if inp.t.is_dynamic() {
                     params.insert(format!("{}_hash", &inp.name), JsonValue::String(normalize_hex(word_hex)));
+                    let known = preimages
+                        .filter(|_| !matches!(inp.t, AbiType::Array(_)))
+                        .and_then(|m| m.get(&inp.name));
+                    if let (Some(pre), Some(word)) = (known, hex_to_word(word_hex)) {
+                        if keccak256(pre) == word {
+                            params.insert(inp.name.clone(), decode_dynamic(&inp.t, pre));
+                        }
+                    }
                     continue;
                 }
 
@@ -160,7 +221,8 @@ impl EventDecoder {
         let non_indexed: Vec<_> = spec.inputs.iter().filter(|p| !p.indexed).collect();
         let data_bytes = hex_to_bytes(&log.data)
             .ok_or_else(|| Error::InvalidHex(log.data.clone()))?;
-        let decoded_vals = decode_tuple(&non_indexed, &data_bytes)?;
+        let types: Vec<&AbiType> = non_indexed.iter().map(|p| &p.t).collect();
+        let decoded_vals = decode_tuple(&types, &data_bytes)?;
 
         for (p, v) in non_indexed.iter().zip(decoded_vals.into_iter()) {
             params.insert(p.name.clone(), v);
@@ -176,16 +238,112 @@ impl EventDecoder {
 
     /// Decode all logs in a receipt; optionally filter the results.
     pub fn decode_receipt(&self, rcpt: &Receipt, filter: Option<&EventFilter<'_>>) -> Result<Vec<DecodedEvent>> {
+        let expected = match filter {
+            Some(f) => f
+                .indexed
+                .iter()
+                .map(|(name, v)| Ok((*name, topic_word(v)?)))
+                .collect::<Result<Vec<_>>>()?,
+            None => Vec::new(),
+        };
         let mut out = Vec::new();
         for log in &rcpt.logs {
             if let Some(ev) = self.decode_log(log)? {
-                if filter.map(|f| f.matches(&ev)).unwrap_or(true) {
+                if filter.map(|f| f.matches(&ev)).unwrap_or(true) && self.topics_match(&ev, &expected) {
                     out.push(ev);
                 }
             }
         }
         Ok(out)
     }
+
+    /// Whether each `(param, word)` names an indexed param of `ev` whose topic is `word`.
+    fn topics_match(&self, ev: &DecodedEvent, expected: &[(&str, [u8; 32])]) -> bool {
+        if expected.is_empty() {
+            return true;
+        }
+        let spec = match ev.raw.topics.first().and_then(|t| hex_to_word(t)).and_then(|t0| self.by_topic0.get(&t0)) {
+            Some(idx) => &self.specs[*idx],
+            None => return false,
+        };
+        expected.iter().all(|(name, word)| {
+            let position = spec.inputs.iter().filter(|p| p.indexed).position(|p| p.name == *name);
+            position
+                .and_then(|k| ev.raw.topics.get(k + 1))
+                .and_then(|t| hex_to_word(t))
+                .is_some_and(|t| t == *word)
+        })
+    }
+}
+
+/// Decoder for revert data, built from an ABI's `errors` array.
+#[derive(Clone)]
+pub struct ErrorDecoder {
+    specs: Vec<ErrorSpec>,
+}
+
+impl ErrorDecoder {
+    /// Build from an ABI JSON object; a missing `errors` array means only the
+    /// standard `Error(string)` reason is recognized.
+    pub fn from_abi_json(abi_json: &JsonValue) -> Result<Self> {
+        let errors = match abi_json.get("errors") {
+            None => return Ok(Self { specs: Vec::new() }),
+            Some(v) => v
+                .as_array()
+                .ok_or_else(|| Error::Abi("'errors' must be an array".into()))?,
+        };
+        let specs = errors.iter().map(ErrorSpec::from_json).collect::<Result<_>>()?;
+        Ok(Self { specs })
+    }
+
+    /// Decode `0x`-hex revert data. Returns `None` when it is shorter than a
+    /// selector or no declared error (nor `Error(string)`) matches.
+    pub fn decode_revert(&self, data_hex: &str) -> Result<Option<DecodedError>> {
+        let data = hex_to_bytes(data_hex).ok_or_else(|| Error::InvalidHex(data_hex.to_string()))?;
+        if data.len() < 4 {
+            return Ok(None);
+        }
+        let (sel, body) = data.split_at(4);
+        let selector = format!("0x{}", hex::encode(sel));
+
+        if let Some(spec) = self.specs.iter().find(|s| s.selector == sel) {
+            let types: Vec<&AbiType> = spec.inputs.iter().map(|(_, t)| t).collect();
+            let values = decode_tuple(&types, body)?;
+            let params = spec.inputs.iter().map(|(n, _)| n.clone()).zip(values).collect();
+            return Ok(Some(DecodedError { name: spec.name.clone(), params, selector }));
+        }
+
+        if selector4("Error(string)") == *sel {
+            // Same reading as `Abi::decode_revert`'s fallback.
+            let reason = decode_error_string(&data)
+                .ok_or_else(|| Error::Abi("malformed Error(string) revert data".into()))?;
+            let mut params = JsonMap::new();
+            params.insert("reason".into(), JsonValue::String(reason));
+            return Ok(Some(DecodedError { name: "Error".into(), params, selector }));
+        }
+        Ok(None)
+    }
+}
+
+impl Receipt {
+    /// Lazily decode this receipt's logs with `decoder`, one log per `next()`.
+    ///
+    /// Items mirror [`EventDecoder::decode_log`]: `Ok(None)` for logs the ABI
+    /// doesn't describe. Nothing past the last item pulled is decoded, so
+    /// indexers can stop at the first event they care about:
+    ///
+    /// ```ignore
+    /// let hit = rcpt
+    ///     .decoded_logs(&dec)
+    ///     .filter_map(|r| r.transpose())
+    ///     .find(|r| r.as_ref().map_or(true, |ev| filter.matches(ev)));
+    /// ```
+    pub fn decoded_logs<'a>(
+        &'a self,
+        decoder: &'a EventDecoder,
+    ) -> impl Iterator<Item = Result<Option<DecodedEvent>>> + 'a {
+        self.logs.iter().map(move |log| decoder.decode_log(log))
+    }
 }
 
 // ---------------------------- Internal modeling ------------------------------
@@ -239,6 +397,46 @@ impl EventSpec {
     }
 }
 
+#[derive(Clone)]
+struct ErrorSpec {
+    name: String,
+    /// `(param name or "arg<i>", type)`.
+    inputs: Vec<(String, AbiType)>,
+    selector: [u8; 4],
+}
+
+impl ErrorSpec {
+    fn from_json(v: &JsonValue) -> Result<Self> {
+        let name = v.get("name")
+            .and_then(|x| x.as_str())
+            .ok_or_else(|| Error::Abi("error missing name".into()))?
+            .to_string();
+
+        let inputs_val = match v.get("inputs") {
+            None => &[][..],
+            Some(x) => x
+                .as_array()
+                .ok_or_else(|| Error::Abi(format!("error {} inputs must be an array", name)))?,
+        };
+
+        let mut inputs = Vec::with_capacity(inputs_val.len());
+        for (i, inp) in inputs_val.iter().enumerate() {
+            let in_name = match inp.get("name").and_then(|x| x.as_str()) {
+                Some(n) if !n.is_empty() => n.to_string(),
+                _ => format!("arg{}", i),
+            };
+            let tstr = inp.get("type")
+                .and_then(|x| x.as_str())
+                .ok_or_else(|| Error::Abi(format!("error {} input missing type", name)))?;
+            inputs.push((in_name, AbiType::parse(tstr)?));
+        }
+
+        let tokens: Vec<String> = inputs.iter().map(|(_, t)| t.signature_token()).collect();
+        let selector = selector4(&format!("{}({})", name, tokens.join(",")));
+        Ok(Self { name, inputs, selector })
+    }
+}
+
 // ------------------------------- ABI types -----------------------------------
 
 #[derive(Clone)]
@@ -250,11 +448,30 @@ enum AbiType {
     Bytes,         // dynamic
     String,        // dynamic
     FixedBytes(usize),
-    // Arrays are not supported in this version.
+    /// Dynamic array `T[]` (at most two levels, static scalar elements).
+    Array(Box<AbiType>),
 }
 
 impl AbiType {
     fn parse(s: &str) -> Result<Self> {
+        if let Some(inner) = s.strip_suffix("[]") {
+            let inner = Self::parse(inner)?;
+            let elem = match &inner {
+                AbiType::Array(elem) => &**elem,
+                elem => elem,
+            };
+            if elem.is_dynamic() {
+                return Err(Error::Abi(format!(
+                    "event decoder supports only 1-D/2-D arrays of static types: {}",
+                    s
+                )));
+            }
+            return Ok(Self::Array(Box::new(inner)));
+        }
+        // fixed-size arrays not supported here
+        if s.ends_with(']') {
+            return Err(Error::Abi(format!("fixed-size array types not supported in event decoder: {}", s)));
+        }
         if s == "bool" { return Ok(Self::Bool); }
         if s == "address" { return Ok(Self::Address); }
         if s == "bytes" { return Ok(Self::Bytes); }
@@ -276,36 +493,25 @@ impl AbiType {
             if n == 0 || n > 256 || n % 8 != 0 { return Err(Error::Abi(format!("intN out of range: {}", s))); }
             return Ok(Self::Int(n));
         }
-        // arrays not supported here
-        if s.ends_with("]") {
-            return Err(Error::Abi(format!("array types not supported in event decoder: {}", s)));
-        }
         Err(Error::Abi(format!("unsupported type: {}", s)))
     }
 
     fn is_dynamic(&self) -> bool {
-        matches!(self, AbiType::Bytes | AbiType::String)
+        matches!(self, AbiType::Bytes | AbiType::String | AbiType::Array(_))
     }
 
-    /// Token text used in event signature hashing.
-    fn signature_token(&self) -> &'static str {
+    /// Canonical type text used in event and error signature hashing
+    /// (`uint` → `uint256`, sizes always spelled out).
+    fn signature_token(&self) -> String {
         match self {
-            AbiType::Uint(n) => match *n {
-                256 => "uint256",
-                _ => "uint", // most contracts normalize, but we keep a conservative token
-            },
-            AbiType::Int(n) => match *n {
-                256 => "int256",
-                _ => "int",
-            },
-            AbiType::Bool => "bool",
-            AbiType::Address => "address",
-            AbiType::Bytes => "bytes",
-            AbiType::String => "string",
-            AbiType::FixedBytes(n) => match *n {
-                32 => "bytes32",
-                _ => "bytes", // hashing uses canonical text; bytesN is acceptable too
-            },
+            AbiType::Uint(n) => format!("uint{}", n),
+            AbiType::Int(n) => format!("int{}", n),
+            AbiType::Bool => "bool".into(),
+            AbiType::Address => "address".into(),
+            AbiType::Bytes => "bytes".into(),
+            AbiType::String => "string".into(),
+            AbiType::FixedBytes(n) => format!("bytes{}", n),
+            AbiType::Array(inner) => format!("{}[]", inner.signature_token()),
         }
     }
 }
@@ -329,6 +535,40 @@ fn hex_to_word(s: &str) -> Option<[u8; 32]> {
     Some(out)
 }
 
+/// Topic word for an indexed `value`: its ABI word if static, else `keccak256`
+/// of the contents (array elements concatenated as words).
+fn topic_word(value: &AbiValue) -> Result<[u8; 32]> {
+    match value {
+        AbiValue::Bytes(b) => Ok(keccak256(b)),
+        AbiValue::String(s) => Ok(keccak256(s.as_bytes())),
+        AbiValue::Array(items) => {
+            let mut pre = Vec::with_capacity(32 * items.len());
+            for item in items {
+                pre.extend_from_slice(&static_word(item)?);
+            }
+            Ok(keccak256(&pre))
+        }
+        _ => static_word(value),
+    }
+}
+
+/// ABI word for a static `value`. A `0x`-hex address (the form the decoder
+/// emits) is its 20 bytes left-padded, so decoded values round-trip into
+/// filters; bech32m addresses keep their full 32-byte word.
+fn static_word(value: &AbiValue) -> Result<[u8; 32]> {
+    match value {
+        AbiValue::Address(a) if a.starts_with("0x") => {
+            let b = hex_to_bytes(a)
+                .filter(|b| b.len() == 20)
+                .ok_or_else(|| Error::Abi(format!("hex address must be 20 bytes: {}", a)))?;
+            let mut w = [0u8; 32];
+            w[12..].copy_from_slice(&b);
+            Ok(w)
+        }
+        _ => value.encode_word(),
+    }
+}
+
 fn decode_word_static(t: &AbiType, word: &[u8; 32]) -> Result<JsonValue> {
     match t {
         AbiType::Bool => Ok(JsonValue::Bool(word[31] == 1)),
@@ -350,7 +590,7 @@ fn decode_word_static(t: &AbiType, word: &[u8; 32]) -> Result<JsonValue> {
             let v = &word[0..*n];
             Ok(JsonValue::String(format!("0x{}", hex::encode(v))))
         }
-        AbiType::Bytes | AbiType::String => {
+        AbiType::Bytes | AbiType::String | AbiType::Array(_) => {
             Err(Error::Abi("dynamic types cannot be decoded from indexed topic (only hash is present)".into()))
         }
     }
@@ -382,63 +622,83 @@ fn be_twos_complement_to_decimal_str(word: &[u8; 32]) -> String {
     signed.to_string()
 }
 
+/// JSON value for a dynamic (`bytes`/`string`) payload. Non-UTF-8 strings
+/// fall back to hex.
+fn decode_dynamic(t: &AbiType, bytes: &[u8]) -> JsonValue {
+    match t {
+        AbiType::String => match std::str::from_utf8(bytes) {
+            Ok(s) => JsonValue::String(s.to_string()),
+            Err(_) => JsonValue::String(format!("0x{}", hex::encode(bytes))),
+        },
+        _ => JsonValue::String(format!("0x{}", hex::encode(bytes))),
+    }
+}
+
 /// Decode ABI tuple payload for non-indexed params.
-fn decode_tuple(params: &[&EventInput], data: &[u8]) -> Result<Vec<JsonValue>> {
-    // Standard ABI: head = 32 * N bytes; dynamic items have offsets into tail.
-    // We only support flat tuples of supported scalar/dynamic types (no arrays).
-    let n = params.len();
-    let head_len = 32 * n;
+///
+/// Standard ABI: head = 32 * N bytes; dynamic items hold offsets (relative to
+/// the start of `data`) into the tail. Array contents are themselves such a
+/// tuple of `len` elements, so this recurses for `T[]` and `T[][]`.
+fn decode_tuple(types: &[&AbiType], data: &[u8]) -> Result<Vec<JsonValue>> {
+    let n = types.len();
+    let head_len = n
+        .checked_mul(32)
+        .ok_or_else(|| Error::Abi("event data length overflow".into()))?;
     if data.len() < head_len {
         return Err(Error::Abi(format!("event data too short: {} < {}", data.len(), head_len)));
     }
 
     let mut results = Vec::with_capacity(n);
+    for (i, t) in types.iter().enumerate() {
+        let mut head = [0u8; 32];
+        head.copy_from_slice(&data[i * 32..(i + 1) * 32]);
+        if !t.is_dynamic() {
+            results.push(decode_word_static(t, &head)?);
+            continue;
+        }
 
-    // First pass: collect head words and potential offsets
-    let mut heads: Vec<[u8; 32]> = Vec::with_capacity(n);
-    for i in 0..n {
-        let mut w = [0u8; 32];
-        w.copy_from_slice(&data[i * 32..(i + 1) * 32]);
-        heads.push(w);
-    }
-
-    for (i, inp) in params.iter().enumerate() {
-        let t = &inp.t;
-        if t.is_dynamic() {
-            // Read offset (in bytes) from start of data
-            let off = num_bigint::BigUint::from_bytes_be(&heads[i]).to_usize().ok_or_else(|| {
-                Error::Abi("dynamic offset too large".into())
-            })?;
-            if off + 32 > data.len() {
-                return Err(Error::Abi(format!("dynamic offset out of bounds: {}", off)));
+        // Read offset (in bytes) from start of data, then the length word there.
+        let off = word_to_usize(&head).ok_or_else(|| Error::Abi("dynamic offset too large".into()))?;
+        if data.len() < 32 || off > data.len() - 32 {
+            return Err(Error::Abi(format!("dynamic offset out of bounds: {}", off)));
+        }
+        let mut len_word = [0u8; 32];
+        len_word.copy_from_slice(&data[off..off + 32]);
+        let len = word_to_usize(&len_word).ok_or_else(|| Error::Abi("dynamic length too large".into()))?;
+        let body = &data[off + 32..];
+
+        match t {
+            AbiType::Array(inner) => {
+                // Every element takes at least one head word; check before
+                // sizing the type list from the untrusted length.
+                if len.checked_mul(32).is_none_or(|n| n > body.len()) {
+                    return Err(Error::Abi(format!("array length out of bounds: {}+{}", off, len)));
+                }
+                let elems = decode_tuple(&vec![&**inner; len], body)?;
+                results.push(JsonValue::Array(elems));
             }
-            // length at offset
-            let mut len_word = [0u8; 32];
-            len_word.copy_from_slice(&data[off..off + 32]);
-            let len = num_bigint::BigUint::from_bytes_be(&len_word).to_usize().ok_or_else(|| {
-                Error::Abi("dynamic length too large".into())
-            })?;
-            if off + 32 + len > data.len() {
-                return Err(Error::Abi(format!("dynamic data out of bounds: {}+{}", off, len)));
+            _ => {
+                if len > body.len() {
+                    return Err(Error::Abi(format!("dynamic data out of bounds: {}+{}", off, len)));
+                }
+                results.push(decode_dynamic(t, &body[..len]));
             }
-            let bytes = &data[off + 32..off + 32 + len];
-            let val = match t {
-                AbiType::Bytes => JsonValue::String(format!("0x{}", hex::encode(bytes))),
-                AbiType::String => match std::str::from_utf8(bytes) {
-                    Ok(s) => JsonValue::String(s.to_string()),
-                    Err(_) => JsonValue::String(format!("0x{}", hex::encode(bytes))),
-                },
-                _ => unreachable!(),
-            };
-            results.push(val);
-        } else {
-            results.push(decode_word_static(t, &heads[i])?);
         }
     }
 
     Ok(results)
 }
 
+/// Offset/length word as `usize`; `None` if it does not fit in 64 bits.
+fn word_to_usize(word: &[u8; 32]) -> Option<usize> {
+    if word[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    let mut b = [0u8; 8];
+    b.copy_from_slice(&word[24..]);
+    usize::try_from(u64::from_be_bytes(b)).ok()
+}
+
 // ------------------------------- Public utils --------------------------------
 
 /// Decode all logs in `receipt` using `abi_json` and return only those matching `filter` if provided.
@@ -510,9 +770,7 @@ mod tests {
             address: addr.to_string(),
             topics: vec![t0, from_t, to_t],
             data: format!("0x{}", hex::encode(data)),
-            index: Some(0),
-            block_number: Some(1),
-            tx_hash: None,
+            extra: Default::default(),
         }
     }
 
@@ -554,6 +812,302 @@ mod tests {
         assert!(f3.matches(&ev));
     }
 
+    #[test]
+    fn indexed_string_recovered_from_matching_preimage() {
+        let abi = json!({
+            "events": [{
+                "name": "Tagged",
+                "inputs": [
+                    {"name":"tag","type":"string","indexed":true},
+                    {"name":"value","type":"uint256","indexed":false}
+                ]
+            }]
+        });
+        let dec = EventDecoder::from_abi_json(&abi).unwrap();
+
+        let t0 = format!("0x{}", hex::encode(keccak256(b"Tagged(string,uint256)")));
+        let tag_hash = format!("0x{}", hex::encode(keccak256(b"gold")));
+        let mut data = [0u8; 32];
+        data[31] = 7;
+        let log = LogEvent {
+            address: "anim1tagged...".to_string(),
+            topics: vec![t0, tag_hash.clone()],
+            data: format!("0x{}", hex::encode(data)),
+            extra: Default::default(),
+        };
+
+        // Correct preimage: real value filled in, hash kept.
+        let mut pre = HashMap::new();
+        pre.insert("tag".to_string(), b"gold".to_vec());
+        let ev = dec.decode_log_with_preimages(&log, &pre).unwrap().unwrap();
+        assert_eq!(ev.params.get("tag").unwrap(), &json!("gold"));
+        assert_eq!(ev.params.get("tag_hash").unwrap(), &json!(tag_hash));
+        assert_eq!(ev.params.get("value").unwrap(), &json!("7"));
+
+        // Wrong preimage: only the hash is surfaced.
+        pre.insert("tag".to_string(), b"silver".to_vec());
+        let ev = dec.decode_log_with_preimages(&log, &pre).unwrap().unwrap();
+        assert!(ev.params.get("tag").is_none());
+        assert_eq!(ev.params.get("tag_hash").unwrap(), &json!(tag_hash));
+    }
+
+    #[test]
+    fn decoded_logs_stop_at_target_event() {
+        let dec = EventDecoder::from_abi_json(&sample_abi()).unwrap();
+        let transfer = build_transfer_log("0x1111111111111111111111111111111111111111",
+                                          "0x2222222222222222222222222222222222222222",
+                                          5,
+                                          "anim1target...");
+        let unknown = LogEvent { topics: vec![format!("0x{}", "ab".repeat(32))], ..transfer.clone() };
+        // A log with no topics is a decode error if ever reached.
+        let broken = LogEvent { topics: vec![], ..transfer.clone() };
+        let rcpt = Receipt {
+            tx_hash: "0xtx".into(),
+            status: crate::types::TxStatus::SUCCESS,
+            gas_used: 21_000,
+            block_hash: None,
+            block_number: Some(1),
+            contract_address: None,
+            logs: vec![unknown, transfer, broken.clone(), broken],
+            extra: Default::default(),
+        };
+
+        let mut it = rcpt.decoded_logs(&dec);
+        let hit = it.by_ref()
+            .filter_map(|r| r.transpose())
+            .find(|r| matches!(r, Ok(ev) if ev.name == "Transfer"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(hit.address, "anim1target...");
+        // Both broken logs are still pending; pulling one surfaces its error.
+        assert!(it.next().unwrap().is_err());
+        assert_eq!(it.count(), 1);
+    }
+
+    #[test]
+    fn decode_receipt_filters_on_indexed_values() {
+        let dec = EventDecoder::from_abi_json(&sample_abi()).unwrap();
+        let from = "0x1111111111111111111111111111111111111111";
+        let to_a = "0x2222222222222222222222222222222222222222";
+        let to_b = "0x3333333333333333333333333333333333333333";
+        let rcpt = Receipt {
+            tx_hash: "0xtx".into(),
+            status: crate::types::TxStatus::SUCCESS,
+            gas_used: 21_000,
+            block_hash: None,
+            block_number: Some(1),
+            contract_address: None,
+            logs: vec![
+                build_transfer_log(from, to_b, 2, "anim1c..."),
+                build_transfer_log(from, to_a, 1, "anim1c..."),
+            ],
+            extra: Default::default(),
+        };
+
+        let f = EventFilter::new()
+            .name("Transfer")
+            .indexed("to", AbiValue::Address(to_a.into()));
+        let got = dec.decode_receipt(&rcpt, Some(&f)).unwrap();
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].params.get("value").unwrap(), &json!("1"));
+
+        // A decoded address feeds straight back into a filter.
+        let decoded_to = got[0].params.get("to").unwrap().as_str().unwrap().to_string();
+        let round = EventFilter::new().indexed("to", AbiValue::Address(decoded_to));
+        assert_eq!(dec.decode_receipt(&rcpt, Some(&round)).unwrap().len(), 1);
+
+        // Constraints AND together; a param the event doesn't index never matches.
+        let f2 = f.clone().indexed("from", AbiValue::Address(to_b.into()));
+        assert!(dec.decode_receipt(&rcpt, Some(&f2)).unwrap().is_empty());
+        let f3 = EventFilter::new().indexed("value", AbiValue::U(1));
+        assert!(dec.decode_receipt(&rcpt, Some(&f3)).unwrap().is_empty());
+
+        let short = EventFilter::new().indexed("to", AbiValue::Address("0x2222".into()));
+        assert!(dec.decode_receipt(&rcpt, Some(&short)).is_err());
+    }
+
+    #[test]
+    fn dynamic_indexed_values_compare_by_hash() {
+        assert_eq!(topic_word(&AbiValue::String("gold".into())).unwrap(), keccak256(b"gold"));
+        assert_eq!(topic_word(&AbiValue::U(7)).unwrap()[31], 7);
+        let arr = AbiValue::Array(vec![AbiValue::U(9)]);
+        assert_eq!(topic_word(&arr).unwrap(), keccak256(u256_word(9)));
+    }
+
+    #[test]
+    fn error_decoder_matches_declared_errors_and_error_string() {
+        let abi = json!({
+            "events": [],
+            "errors": [
+                {"name": "Insufficient", "inputs": [
+                    {"name": "have", "type": "uint256"},
+                    {"name": "", "type": "address"}
+                ]},
+                {"name": "Denied", "inputs": [{"name": "why", "type": "string"}]},
+                {"name": "Small", "inputs": [
+                    {"name": "n", "type": "uint64"},
+                    {"name": "tag", "type": "bytes4"},
+                    {"name": "total", "type": "uint"}
+                ]}
+            ]
+        });
+        let dec = ErrorDecoder::from_abi_json(&abi).unwrap();
+
+        let sel = |sig: &str| keccak256(sig.as_bytes())[..4].to_vec();
+        let mut who = [0u8; 32];
+        who[12..].copy_from_slice(&[0xab; 20]);
+        let data = [sel("Insufficient(uint256,address)"), u256_word(3).to_vec(), who.to_vec()].concat();
+        let err = dec.decode_revert(&format!("0x{}", hex::encode(&data))).unwrap().unwrap();
+        assert_eq!(err.name, "Insufficient");
+        assert_eq!(err.selector, format!("0x{}", hex::encode(sel("Insufficient(uint256,address)"))));
+        assert_eq!(err.params.get("have").unwrap(), &json!("3"));
+        assert_eq!(err.params.get("arg1").unwrap(), &json!(format!("0x{}", "ab".repeat(20))));
+
+        // Selectors hash the canonical type text, sizes spelled out.
+        let mut tag = [0u8; 32];
+        tag[..4].copy_from_slice(b"abcd");
+        let data = [sel("Small(uint64,bytes4,uint256)"), u256_word(5).to_vec(), tag.to_vec(), u256_word(6).to_vec()].concat();
+        let err = dec.decode_revert(&format!("0x{}", hex::encode(&data))).unwrap().unwrap();
+        assert_eq!(err.name, "Small");
+        assert_eq!(err.params.get("n").unwrap(), &json!("5"));
+        assert_eq!(err.params.get("tag").unwrap(), &json!("0x61626364"));
+
+        // Standard Error(string) fallback
+        let mut reason = b"nope".to_vec();
+        reason.resize(32, 0);
+        let data = [sel("Error(string)"), u256_word(0x20).to_vec(), u256_word(4).to_vec(), reason].concat();
+        let err = dec.decode_revert(&format!("0x{}", hex::encode(&data))).unwrap().unwrap();
+        assert_eq!(err.name, "Error");
+        assert_eq!(err.params.get("reason").unwrap(), &json!("nope"));
+        assert_eq!(decode_error_string(&data).as_deref(), Some("nope"));
+        assert!(dec.decode_revert(&format!("0x{}", hex::encode(&data[..36]))).is_err());
+
+        // Unknown selector / too short
+        assert!(dec.decode_revert("0xdeadbeef").unwrap().is_none());
+        assert!(dec.decode_revert("0x").unwrap().is_none());
+        assert!(dec.decode_revert("0xzz").is_err());
+    }
+
+    fn u256_word(v: u64) -> [u8; 32] {
+        let mut w = [0u8; 32];
+        w[24..].copy_from_slice(&v.to_be_bytes());
+        w
+    }
+
+    fn words(ws: &[[u8; 32]]) -> String {
+        format!("0x{}", hex::encode(ws.concat()))
+    }
+
+    #[test]
+    fn decodes_uint256_array_and_address_array() {
+        let abi = json!({
+            "events": [{
+                "name": "Batch",
+                "inputs": [
+                    {"name":"to","type":"address[]","indexed":false},
+                    {"name":"amounts","type":"uint256[]","indexed":false}
+                ]
+            }]
+        });
+        let dec = EventDecoder::from_abi_json(&abi).unwrap();
+
+        let mut a1 = [0u8; 32];
+        a1[12..].copy_from_slice(&[0x11; 20]);
+        let mut a2 = [0u8; 32];
+        a2[12..].copy_from_slice(&[0x22; 20]);
+        let mut big = [0u8; 32];
+        big[0] = 0x01; // 2^248, well past u128
+        // head: offsets 0x40 and 0xa0; tails: [len, a1, a2], [len, 5, big]
+        let data = words(&[
+            u256_word(0x40), u256_word(0xa0),
+            u256_word(2), a1, a2,
+            u256_word(2), u256_word(5), big,
+        ]);
+        let log = LogEvent {
+            address: "anim1batch...".to_string(),
+            topics: vec![format!("0x{}", hex::encode(keccak256(b"Batch(address[],uint256[])")))],
+            data,
+            extra: Default::default(),
+        };
+
+        let ev = dec.decode_log(&log).unwrap().unwrap();
+        assert_eq!(
+            ev.params.get("to").unwrap(),
+            &json!([format!("0x{}", "11".repeat(20)), format!("0x{}", "22".repeat(20))])
+        );
+        let expected_big = num_bigint::BigUint::from_bytes_be(&big).to_str_radix(10);
+        assert_eq!(ev.params.get("amounts").unwrap(), &json!(["5", expected_big]));
+    }
+
+    #[test]
+    fn decodes_nested_arrays_and_hashes_indexed_arrays() {
+        let abi = json!({
+            "events": [{
+                "name": "Grid",
+                "inputs": [
+                    {"name":"ids","type":"uint256[]","indexed":true},
+                    {"name":"rows","type":"uint64[][]","indexed":false}
+                ]
+            }]
+        });
+        let dec = EventDecoder::from_abi_json(&abi).unwrap();
+
+        // rows = [[1, 2], []]: outer [len=2, off0=0x40, off1=0xa0] relative to the
+        // element area, then [2, 1, 2] and [0].
+        let data = words(&[
+            u256_word(0x20),
+            u256_word(2), u256_word(0x40), u256_word(0xa0),
+            u256_word(2), u256_word(1), u256_word(2),
+            u256_word(0),
+        ]);
+        let ids_hash = format!("0x{}", hex::encode(keccak256([u256_word(9)].concat())));
+        let log = LogEvent {
+            address: "anim1grid...".to_string(),
+            topics: vec![
+                format!("0x{}", hex::encode(keccak256(b"Grid(uint256[],uint64[][])"))),
+                ids_hash.clone(),
+            ],
+            data,
+            extra: Default::default(),
+        };
+
+        let ev = dec.decode_log(&log).unwrap().unwrap();
+        assert_eq!(ev.params.get("rows").unwrap(), &json!([["1", "2"], []]));
+        assert_eq!(ev.params.get("ids_hash").unwrap(), &json!(ids_hash));
+        assert!(ev.params.get("ids").is_none());
+
+        assert!(AbiType::parse("uint256[][][]").is_err());
+        assert!(AbiType::parse("string[]").is_err());
+        assert!(AbiType::parse("uint256[3]").is_err());
+    }
+
+    #[test]
+    fn array_length_beyond_data_is_rejected_without_allocating() {
+        let abi = json!({
+            "events": [{
+                "name": "Batch",
+                "inputs": [{"name":"amounts","type":"uint256[]","indexed":false}]
+            }]
+        });
+        let dec = EventDecoder::from_abi_json(&abi).unwrap();
+        let topics = vec![format!("0x{}", hex::encode(keccak256(b"Batch(uint256[])")))];
+        let log = |len: [u8; 32]| LogEvent {
+            address: "anim1batch...".to_string(),
+            topics: topics.clone(),
+            data: words(&[u256_word(0x20), len, u256_word(1)]),
+            extra: Default::default(),
+        };
+
+        // A length whose byte size overflows, and one far past the data, must
+        // fail on the bounds check rather than on the allocation.
+        assert!(dec.decode_log(&log(u256_word(u64::MAX))).is_err());
+        assert!(dec.decode_log(&log(u256_word(1 << 40))).is_err());
+        // One more element than the data holds.
+        assert!(dec.decode_log(&log(u256_word(2))).is_err());
+        let ev = dec.decode_log(&log(u256_word(1))).unwrap().unwrap();
+        assert_eq!(ev.params.get("amounts").unwrap(), &json!(["1"]));
+    }
+
     // Test helper visibility
     use super::{hex_to_bytes as _hex_to_bytes};
     fn hex_to_bytes(s: &str) -> Option<Vec<u8>> { _hex_to_bytes(s) }
@@ -4,20 +4,87 @@
 //! - `POST /da/blob?ns=<u32>`    — submit raw blob bytes; returns commitment/receipt
 //! - `GET  /da/blob/{commitment}` — fetch raw blob bytes (exact payload)
 //! - `GET  /da/blob/{commitment}/proof` — fetch light-client proof JSON
+//! - `GET  /da/namespace/{ns}?height=<u64>[&cursor=..]` — list a namespace's blobs
+//!   at a height, one page at a time (`{"blobs": [...], "next": <cursor|null>}`)
 //!
 //! These endpoints are mounted alongside the JSON-RPC service, so you can point
 //! this client at the same base URL (e.g. `http://127.0.0.1:8545`).
 //!
 //! The response shapes are intentionally loose/future-proof (serde_json::Value)
-//! except for the common commitment/namespace/size receipt fields.
+//! except for the common commitment/namespace/size receipt fields. Proofs can be
+//! fetched either raw (`get_proof`) or as a typed [`DaProof`] (`get_proof_typed`).
+//!
+//! Uploads default to raw octet-stream with a JSON-hex fallback on HTTP 415;
+//! [`DAClient::with_upload_format`] pins one [`UploadFormat`] instead. Large
+//! blobs can be streamed from an `AsyncRead` with [`DAClient::post_blob_stream`]
+//! (octet-stream only, no retries).
+//!
+//! For erasure-coded blobs, `fetch_k_shards` GETs shards concurrently and
+//! returns as soon as `k` have arrived, cancelling the outstanding requests.
+//!
+//! With the `nmt` feature, [`DAClient::get_blob_verified`] checks the blob
+//! against its NMT proof locally (via `animica_native::nmt`) instead of trusting
+//! the server; [`verify_blob`] runs the same check offline and reports proof
+//! shapes it cannot check as [`DaError::UnsupportedProof`]. [`DAClient::reconstruct_blob`]
+//! rebuilds a blob from surviving erasure-coded shards (`animica_native::rs`)
+//! and verifies it the same way.
+//!
+//! Failures are reported as a typed [`DaError`] (converts into the crate
+//! [`Error::Da`](crate::error::Error::Da)), so callers can tell a missing blob
+//! from an overloaded server without matching on strings.
 
-use crate::error::{Error, Result};
+use crate::error::Result;
+use futures::stream::{FuturesUnordered, StreamExt};
 use reqwest::{Client, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::time::Duration;
 use tokio::time::sleep;
 
+/// Error returned by [`DAClient`] operations. Each variant carries the
+/// human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DaError {
+    /// The blob or proof does not exist (HTTP 404).
+    #[error("{0}")]
+    NotFound(String),
+    /// The service is overloaded or failing (5xx / 429 after retries), or not
+    /// enough shards could be fetched.
+    #[error("{0}")]
+    Unavailable(String),
+    /// The server rejected the upload encoding (HTTP 415).
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+    /// Unexpected status, or a body that does not parse as expected.
+    #[error("{0}")]
+    BadResponse(String),
+    /// The request could not be built or sent (bad URL, connect, I/O).
+    #[error("{0}")]
+    Transport(String),
+    /// The fetched blob does not hash to the requested commitment.
+    #[error("{0}")]
+    CommitmentMismatch(String),
+    /// The proof has a shape or algorithm local verification cannot check
+    /// (see [`verify_blob`]).
+    #[error("{0}")]
+    UnsupportedProof(String),
+}
+
+impl DaError {
+    /// Classify a non-success HTTP status; `msg` becomes the variant message.
+    fn from_status(status: StatusCode, msg: String) -> Self {
+        if status == StatusCode::NOT_FOUND {
+            DaError::NotFound(msg)
+        } else if status == StatusCode::UNSUPPORTED_MEDIA_TYPE {
+            DaError::UnsupportedMediaType(msg)
+        } else if should_retry_status(status) {
+            DaError::Unavailable(msg)
+        } else {
+            DaError::BadResponse(msg)
+        }
+    }
+}
+
 /// Result of a DA blob POST. Mirrors the common fields exposed by the service.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaPutResult {
@@ -32,6 +99,188 @@ pub struct DaPutResult {
     pub extra: serde_json::Map<String, JsonValue>,
 }
 
+/// One blob record from a namespace listing ([`DAClient::get_namespace`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaBlob {
+    /// Commitment / NMT root (0x-hex).
+    pub commitment: String,
+    /// Blob size in bytes.
+    pub size: u64,
+    /// Blob bytes, when the server inlines them (0x-hex on the wire).
+    #[serde(default, with = "opt_hex", skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<u8>>,
+    /// Any extra fields the server returns (kept for forward-compat).
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, JsonValue>,
+}
+
+/// One page of `/da/namespace/{ns}`.
+#[derive(Deserialize)]
+struct NamespacePage {
+    #[serde(default)]
+    blobs: Vec<DaBlob>,
+    #[serde(default)]
+    next: Option<String>,
+}
+
+/// Half-open range of leaf indices `[start, end)` covered by a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaLeafRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Typed NMT inclusion proof for a blob, as served by `/da/blob/{commitment}/proof`.
+///
+/// Only the fields local verification needs are typed; the full server response
+/// is kept in `raw` so newer fields are not lost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaProof {
+    /// Commitment / NMT root (0x-hex).
+    pub commitment: String,
+    /// Namespace id (u32).
+    pub namespace: u32,
+    /// Sibling nodes along the NMT path (0x-hex, leaf to root order).
+    #[serde(alias = "nodes")]
+    pub proof_nodes: Vec<String>,
+    /// Leaf indices covered by this proof.
+    pub leaf_range: DaLeafRange,
+    /// Total original blob size in bytes.
+    #[serde(alias = "size")]
+    pub blob_size: u64,
+    /// Untouched server response (filled by [`DaProof::from_json`]).
+    #[serde(skip)]
+    pub raw: JsonValue,
+}
+
+impl DaProof {
+    /// Parse a proof from the server's JSON, keeping the original value in `raw`.
+    pub fn from_json(v: JsonValue) -> Result<Self, DaError> {
+        let mut proof: DaProof = serde_json::from_value(v.clone())
+            .map_err(|e| DaError::BadResponse(format!("parse DA proof: {e}")))?;
+        proof.raw = v;
+        Ok(proof)
+    }
+}
+
+/// Check `bytes` against `commitment` using the server's proof JSON, under
+/// `animica_native::nmt`'s tree rules (BLAKE3 node hashing, 8-byte namespaces,
+/// odd levels padded by duplicating the last node).
+///
+/// The proof must name the same commitment and namespace and describe a blob
+/// of `bytes.len()` bytes. Only **single-leaf** proofs are checked: the
+/// `leaf_range` covers one index, the whole blob is that leaf (tagged with
+/// `namespace`, big-endian in the low 4 bytes of the 8-byte NMT namespace),
+/// and it is folded up through `proof_nodes` with `nmt::verify`. Sibling
+/// sides follow from the bits of `leaf_range.start`; a one-leaf tree has no
+/// nodes.
+///
+/// Proof nodes are hex `min_ns ‖ max_ns ‖ hash(32)`, with 8-byte namespaces or
+/// 4-byte (u32) ones widened like the leaf namespace.
+///
+/// Returns `Ok(false)` when the recomputation disagrees. `animica_native::nmt`
+/// has no multi-leaf range proofs, so any `leaf_range` wider than one leaf
+/// (such as the `4..7` range in the DA fixture) is [`DaError::UnsupportedProof`],
+/// as is a proof whose `algo` is not a BLAKE3 NMT; malformed inputs are other
+/// errors.
+#[cfg(feature = "nmt")]
+pub fn verify_blob(commitment: &str, namespace: u32, bytes: &[u8], proof_json: &JsonValue) -> Result<bool, DaError> {
+    use animica_native::nmt::{self, Root};
+
+    let proof = DaProof::from_json(proof_json.clone())?;
+    let want = commitment.strip_prefix("0x").unwrap_or(commitment);
+    let want: [u8; 32] = hex::decode(want)
+        .map_err(|e| DaError::BadResponse(format!("commitment is not hex: {e}")))?
+        .try_into()
+        .map_err(|_| DaError::BadResponse(format!("commitment is not 32 bytes: {commitment}")))?;
+    if !proof.commitment.trim_start_matches("0x").eq_ignore_ascii_case(&hex::encode(want)) {
+        return Err(DaError::BadResponse(format!(
+            "proof is for commitment {}, not {commitment}",
+            proof.commitment
+        )));
+    }
+    if let Some(algo) = proof.raw.get("algo").and_then(JsonValue::as_str) {
+        if !algo.to_ascii_lowercase().contains("blake3") {
+            return Err(DaError::UnsupportedProof(format!("proof algo {algo} is not a BLAKE3 NMT")));
+        }
+    }
+    if proof.namespace != namespace || proof.blob_size != bytes.len() as u64 {
+        return Ok(false);
+    }
+
+    let ns = ns_from_u32(namespace);
+    let DaLeafRange { start, end } = proof.leaf_range;
+    match end.checked_sub(start) {
+        Some(1) => {
+            if proof.proof_nodes.len() > 64 {
+                return Err(DaError::BadResponse(format!("{} proof nodes", proof.proof_nodes.len())));
+            }
+            let path = proof
+                .proof_nodes
+                .iter()
+                .enumerate()
+                .map(|(level, hex)| parse_proof_node(hex, (start >> level) & 1 == 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            // Parents carry min/max of their children, so the root window is
+            // the extremes over the leaf and every sibling.
+            let min_ns = path.iter().map(|n| n.min_ns).fold(ns, std::cmp::min);
+            let max_ns = path.iter().map(|n| n.max_ns).fold(ns, std::cmp::max);
+            let root = Root { min_ns, max_ns, hash: want };
+            Ok(nmt::verify(&root, ns, bytes, &nmt::Proof { path }))
+        }
+        Some(0) | None => Err(DaError::BadResponse(format!("empty leaf range {start}..{end}"))),
+        Some(_) => Err(DaError::UnsupportedProof(format!(
+            "range proof over leaves {start}..{end} with {} sibling nodes (only single-leaf proofs are checked)",
+            proof.proof_nodes.len()
+        ))),
+    }
+}
+
+/// `namespace` as an 8-byte NMT namespace: big-endian in the low 4 bytes.
+#[cfg(feature = "nmt")]
+fn ns_from_u32(namespace: u32) -> animica_native::nmt::Ns {
+    let mut ns = [0u8; 8];
+    ns[4..].copy_from_slice(&namespace.to_be_bytes());
+    ns
+}
+
+/// Decode a hex `min_ns ‖ max_ns ‖ hash(32)` sibling (8- or 4-byte namespaces).
+#[cfg(feature = "nmt")]
+fn parse_proof_node(node: &str, is_left: bool) -> Result<animica_native::nmt::ProofNode, DaError> {
+    let raw = hex::decode(node.strip_prefix("0x").unwrap_or(node))
+        .map_err(|e| DaError::BadResponse(format!("proof node is not hex: {e}")))?;
+    let (min_ns, max_ns, hash) = match raw.len() {
+        48 => (raw[..8].try_into().unwrap(), raw[8..16].try_into().unwrap(), &raw[16..]),
+        40 => {
+            let ns = |b: &[u8]| ns_from_u32(u32::from_be_bytes(b.try_into().unwrap()));
+            (ns(&raw[..4]), ns(&raw[4..8]), &raw[8..])
+        }
+        n => return Err(DaError::BadResponse(format!("proof node is {n} bytes, expected 48 or 40"))),
+    };
+    Ok(animica_native::nmt::ProofNode { is_left, min_ns, max_ns, hash: hash.try_into().unwrap() })
+}
+
+/// Body encoding for [`DAClient::post_blob`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadFormat {
+    /// Raw bytes, `Content-Type: application/octet-stream`.
+    Octet,
+    /// JSON envelope `{"namespace": <u32>, "data": "0x<hex>"}`.
+    JsonHex,
+    /// `multipart/form-data` with a `namespace` text field and a `data` file part.
+    Multipart,
+}
+
+impl UploadFormat {
+    fn label(self) -> &'static str {
+        match self {
+            UploadFormat::Octet => "octet",
+            UploadFormat::JsonHex => "json",
+            UploadFormat::Multipart => "multipart",
+        }
+    }
+}
+
 /// Data Availability REST client.
 #[derive(Clone)]
 pub struct DAClient {
@@ -40,23 +289,26 @@ pub struct DAClient {
     timeout: Duration,
     retries: usize,
     backoff: Duration,
+    /// `None`: octet-stream with a JSON fallback on 415.
+    upload_format: Option<UploadFormat>,
 }
 
 impl DAClient {
     /// Create a new client from a base URL (e.g. "http://127.0.0.1:8545").
-    pub fn new(base_url: &str) -> Result<Self> {
+    pub fn new(base_url: &str) -> Result<Self, DaError> {
         let base = Url::parse(base_url)
-            .map_err(|e| Error::Http(format!("invalid base URL: {e}")))?;
+            .map_err(|e| DaError::Transport(format!("invalid base URL: {e}")))?;
         let http = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
-            .map_err(|e| Error::Http(format!("http client build: {e}")))?;
+            .map_err(|e| DaError::Transport(format!("http client build: {e}")))?;
         Ok(Self {
             base,
             http,
             timeout: Duration::from_secs(30),
             retries: 3,
             backoff: Duration::from_millis(250),
+            upload_format: None,
         })
     }
 
@@ -78,16 +330,26 @@ impl DAClient {
         self
     }
 
-    fn url(&self, path: &str) -> Result<Url> {
+    /// Always upload blobs as `format`, disabling the octet → JSON fallback.
+    pub fn with_upload_format(mut self, format: UploadFormat) -> Self {
+        self.upload_format = Some(format);
+        self
+    }
+
+    fn url(&self, path: &str) -> Result<Url, DaError> {
         self.base
             .join(path)
-            .map_err(|e| Error::Http(format!("url join: {e}")))
+            .map_err(|e| DaError::Transport(format!("url join: {e}")))
     }
 
     /// POST a blob under a namespace. Returns the server's receipt containing the
-    /// `commitment` (hex), `namespace`, and `size`. The request sends the raw
-    /// bytes with `Content-Type: application/octet-stream` and a `ns` query param.
-    pub async fn post_blob<B>(&self, namespace: u32, data: B) -> Result<DaPutResult>
+    /// `commitment` (hex), `namespace`, and `size`. The namespace travels as the
+    /// `ns` query param; the body encoding follows [`DAClient::with_upload_format`].
+    ///
+    /// Without an explicit format, the raw bytes are sent as
+    /// `application/octet-stream` and, if the server answers `415 Unsupported
+    /// Media Type`, retried once as a JSON-hex envelope.
+    pub async fn post_blob<B>(&self, namespace: u32, data: B) -> Result<DaPutResult, DaError>
     where
         B: AsRef<[u8]>,
     {
@@ -96,130 +358,274 @@ impl DAClient {
             let mut qp = url.query_pairs_mut();
             qp.append_pair("ns", &namespace.to_string());
         }
+        let body = data.as_ref();
 
-        // Try octet-stream; if server responds 415, fall back to JSON envelope.
-        let body = data.as_ref().to_vec();
-
-        // Attempt 1: octet-stream
-        match self
-            .with_retries_post_octet(url.clone(), body.clone())
-            .await
-        {
-            Ok(v) => return Ok(v),
-            Err(Error::Http(ref s)) if s.contains("415") => {
-                // fall through to JSON path
+        if let Some(format) = self.upload_format {
+            return self.with_retries_post(url, format, namespace, body).await;
+        }
+        match self.with_retries_post(url.clone(), UploadFormat::Octet, namespace, body).await {
+            Err(DaError::UnsupportedMediaType(_)) => {
+                self.with_retries_post(url, UploadFormat::JsonHex, namespace, body).await
             }
-            Err(e) => return Err(e),
+            other => other,
         }
+    }
 
-        // Attempt 2: JSON envelope (some deployments may prefer this)
-        let payload = serde_json::json!({
-            "namespace": namespace,
-            "data": format!("0x{}", hex::encode(&body)),
-        });
-        self.with_retries_post_json(url, payload).await
+    /// POST a blob of exactly `len` bytes streamed from `reader`, without
+    /// buffering it in memory. Returns the same receipt as [`DAClient::post_blob`].
+    ///
+    /// The body is always sent as `application/octet-stream` (no JSON fallback
+    /// or configured format), and **retries are disabled**: a partially consumed
+    /// stream cannot be replayed, so any failure is returned to the caller, who
+    /// may reopen the source and call again. A reader yielding more or fewer
+    /// than `len` bytes fails the request.
+    pub async fn post_blob_stream<R>(&self, namespace: u32, reader: R, len: u64) -> Result<DaPutResult, DaError>
+    where
+        R: tokio::io::AsyncRead + Send + 'static,
+    {
+        let mut url = self.url("/da/blob")?;
+        url.query_pairs_mut().append_pair("ns", &namespace.to_string());
+        let body = reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(reader));
+
+        let resp = self.http.post(url)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", len.to_string())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| DaError::Transport(format!("DA POST(stream) error: {e}")))?;
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(DaError::from_status(status, format!(
+                "DA POST(stream) failed: {}",
+                resp.text().await.unwrap_or_else(|_| "<no body>".into())
+            )));
+        }
+        let json = resp.json::<serde_json::Map<String, JsonValue>>().await
+            .map_err(|e| DaError::BadResponse(format!("parse DA POST json: {e}")))?;
+        parse_put_result(json)
     }
 
     /// GET raw blob bytes by `commitment` (0x-hex).
-    pub async fn get_blob(&self, commitment: &str) -> Result<Vec<u8>> {
+    pub async fn get_blob(&self, commitment: &str) -> Result<Vec<u8>, DaError> {
         let safe = percent_encode(commitment);
         let url = self.url(&format!("/da/blob/{safe}"))?;
         self.with_retries_get_bytes(url).await
     }
 
     /// GET availability proof JSON for a blob commitment.
-    pub async fn get_proof(&self, commitment: &str) -> Result<JsonValue> {
+    pub async fn get_proof(&self, commitment: &str) -> Result<JsonValue, DaError> {
         let safe = percent_encode(commitment);
         let url = self.url(&format!("/da/blob/{safe}/proof"))?;
         self.with_retries_get_json(url).await
     }
 
-    // --------------------------- Retry wrappers ------------------------------
+    /// GET availability proof for a blob commitment, parsed into a [`DaProof`].
+    pub async fn get_proof_typed(&self, commitment: &str) -> Result<DaProof, DaError> {
+        DaProof::from_json(self.get_proof(commitment).await?)
+    }
 
-    async fn with_retries_post_octet(&self, url: Url, body: Vec<u8>) -> Result<DaPutResult> {
-        let mut attempt = 0usize;
-        loop {
-            attempt += 1;
-            let resp = self
-                .http
-                .post(url.clone())
-                .header("Content-Type", "application/octet-stream")
-                .body(body.clone())
-                .send()
-                .await;
+    /// GET a blob and its proof, and check locally that the bytes hash to
+    /// `commitment` under `namespace` (see [`verify_blob`]).
+    ///
+    /// A disagreement is reported as [`DaError::CommitmentMismatch`].
+    #[cfg(feature = "nmt")]
+    pub async fn get_blob_verified(&self, commitment: &str, namespace: u32) -> Result<Vec<u8>, DaError> {
+        let (bytes, proof) = futures::try_join!(self.get_blob(commitment), self.get_proof(commitment))?;
+        if verify_blob(commitment, namespace, &bytes, &proof)? {
+            Ok(bytes)
+        } else {
+            Err(DaError::CommitmentMismatch(format!(
+                "blob {commitment} (ns {namespace}, {} bytes) does not match its commitment",
+                bytes.len()
+            )))
+        }
+    }
 
-            match resp {
-                Ok(r) => {
-                    if r.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE {
-                        return Err(Error::Http("415 unsupported media type".into()));
-                    }
-                    if r.status().is_success() {
-                        let json = r.json::<serde_json::Map<String, JsonValue>>().await
-                            .map_err(|e| Error::Http(format!("parse DA POST json: {e}")))?;
-                        let commitment = json.get("commitment")
-                            .and_then(|v| v.as_str())
-                            .ok_or_else(|| Error::Http("missing commitment in response".into()))?
-                            .to_string();
-                        let namespace = json.get("namespace")
-                            .and_then(|v| v.as_u64())
-                            .ok_or_else(|| Error::Http("missing namespace in response".into()))? as u32;
-                        let size = json.get("size")
-                            .and_then(|v| v.as_u64())
-                            .ok_or_else(|| Error::Http("missing size in response".into()))?;
-                        let extra = json.into_iter().filter(|(k,_)| k != "commitment" && k != "namespace" && k != "size")
-                            .collect();
-                        return Ok(DaPutResult { commitment, namespace, size, extra });
-                    } else if should_retry_status(r.status()) && attempt <= self.retries {
-                        sleep(self.backoff).await;
-                        continue;
-                    } else {
-                        return Err(Error::Http(format!(
-                            "DA POST failed: {}",
-                            r.text().await.unwrap_or_else(|_| "<no body>".into())
-                        )));
-                    }
+    /// List every blob in `namespace` at block `height`, following the `next`
+    /// cursor across pages until the server stops returning one.
+    pub async fn get_namespace(&self, namespace: u32, height: u64) -> Result<Vec<DaBlob>, DaError> {
+        let mut blobs = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut url = self.url(&format!("/da/namespace/{namespace}"))?;
+            {
+                let mut qp = url.query_pairs_mut();
+                qp.append_pair("height", &height.to_string());
+                if let Some(c) = &cursor {
+                    qp.append_pair("cursor", c);
                 }
-                Err(e) => {
-                    if attempt <= self.retries {
-                        sleep(self.backoff).await;
-                        continue;
+            }
+            let page: NamespacePage = serde_json::from_value(self.with_retries_get_json(url).await?)
+                .map_err(|e| DaError::BadResponse(format!("parse namespace page: {e}")))?;
+            blobs.extend(page.blobs);
+            match page.next.filter(|n| !n.is_empty()) {
+                Some(next) if cursor.as_deref() == Some(next.as_str()) => {
+                    return Err(DaError::BadResponse(format!("namespace cursor did not advance: {next}")));
+                }
+                Some(next) => cursor = Some(next),
+                None => return Ok(blobs),
+            }
+        }
+    }
+
+    /// Rebuild the blob behind `commitment` from surviving RS shards.
+    ///
+    /// `shards` holds all `k + m` slots (`None` for missing ones) laid out as by
+    /// `animica_native::rs::layout::Layout::with_default_align` and stored with
+    /// `encoding`. The original length and namespace come from the blob's
+    /// proof, which is then used to check the result with [`verify_blob`].
+    ///
+    /// Too few survivors is [`DaError::Unavailable`]; a rebuilt payload that
+    /// does not match is [`DaError::CommitmentMismatch`], and a proof
+    /// `verify_blob` cannot check is [`DaError::UnsupportedProof`].
+    #[cfg(feature = "nmt")]
+    pub async fn reconstruct_blob(
+        &self,
+        commitment: &str,
+        params: animica_native::rs::RsParams,
+        encoding: animica_native::rs::Encoding,
+        mut shards: Vec<Option<Vec<u8>>>,
+    ) -> Result<Vec<u8>, DaError> {
+        use animica_native::rs::{self, layout::Layout, Encoding, RsError};
+
+        let proof_json = self.get_proof(commitment).await?;
+        let proof = DaProof::from_json(proof_json.clone())?;
+        let size = usize::try_from(proof.blob_size)
+            .map_err(|_| DaError::BadResponse(format!("blob size too large: {}", proof.blob_size)))?;
+        let layout = Layout::with_default_align(size, params.data_shards, params.parity_shards)
+            .map_err(|e| DaError::BadResponse(format!("DA layout: {e}")))?;
+        if let Some(len) = shards.iter().flatten().map(Vec::len).find(|l| *l != layout.shard_len) {
+            return Err(DaError::BadResponse(format!(
+                "shard length {len} does not match layout ({} bytes per shard)",
+                layout.shard_len
+            )));
+        }
+
+        rs::reconstruct(params, &mut shards).map_err(|e| match e {
+            RsError::NotEnoughShards => DaError::Unavailable(format!("reconstruct {commitment}: {e}")),
+            e => DaError::BadResponse(format!("reconstruct {commitment}: {e}")),
+        })?;
+        let payload = if encoding == Encoding::Systematic {
+            layout.trim_to_payload(&shards)
+        } else {
+            let full: Vec<Vec<u8>> = shards.into_iter().flatten().collect();
+            let data = rs::extract_data(params, encoding, &full)
+                .map_err(|e| DaError::BadResponse(format!("extract data: {e}")))?;
+            layout.unshard(&data)
+        }
+        .map_err(|e| DaError::BadResponse(format!("DA layout: {e}")))?;
+
+        if verify_blob(commitment, proof.namespace, &payload, &proof_json)? {
+            Ok(payload)
+        } else {
+            Err(DaError::CommitmentMismatch(format!(
+                "reconstructed blob {commitment} ({} bytes) does not match its commitment",
+                payload.len()
+            )))
+        }
+    }
+
+    /// Fetch shard blobs concurrently, returning the first `k` that succeed as
+    /// `(index into commitments, bytes)` in arrival order.
+    ///
+    /// Outstanding requests are dropped (cancelled) once `k` shards are in.
+    /// Fails as soon as too many fetches have failed for `k` to be reachable.
+    pub async fn fetch_k_shards(
+        &self,
+        commitments: &[String],
+        k: usize,
+    ) -> Result<Vec<(usize, Vec<u8>)>, DaError> {
+        if k > commitments.len() {
+            return Err(DaError::Unavailable(format!(
+                "need {k} shards but only {} commitments given",
+                commitments.len()
+            )));
+        }
+        let mut inflight: FuturesUnordered<_> = commitments
+            .iter()
+            .enumerate()
+            .map(|(i, c)| async move { (i, self.get_blob(c).await) })
+            .collect();
+
+        let mut out = Vec::with_capacity(k);
+        let mut failed = 0usize;
+        let mut last_err = None;
+        while out.len() < k {
+            match inflight.next().await {
+                Some((i, Ok(bytes))) => out.push((i, bytes)),
+                Some((i, Err(e))) => {
+                    failed += 1;
+                    last_err = Some(format!("shard {i}: {e}"));
+                    if commitments.len() - failed < k {
+                        break;
                     }
-                    return Err(Error::Http(format!("DA POST error: {e}")));
                 }
+                None => break,
             }
         }
+        if out.len() < k {
+            return Err(DaError::Unavailable(format!(
+                "only {} of {k} shards fetched ({})",
+                out.len(),
+                last_err.unwrap_or_else(|| "no error".into())
+            )));
+        }
+        Ok(out)
     }
 
-    async fn with_retries_post_json(&self, url: Url, payload: JsonValue) -> Result<DaPutResult> {
+    // --------------------------- Retry wrappers ------------------------------
+
+    async fn with_retries_post(
+        &self,
+        url: Url,
+        format: UploadFormat,
+        namespace: u32,
+        body: &[u8],
+    ) -> Result<DaPutResult, DaError> {
+        let label = format.label();
         let mut attempt = 0usize;
         loop {
             attempt += 1;
-            let resp = self.http.post(url.clone()).json(&payload).send().await;
+            let req = self.http.post(url.clone());
+            let req = match format {
+                UploadFormat::Octet => req
+                    .header("Content-Type", "application/octet-stream")
+                    .body(body.to_vec()),
+                UploadFormat::JsonHex => req.json(&serde_json::json!({
+                    "namespace": namespace,
+                    "data": format!("0x{}", hex::encode(body)),
+                })),
+                UploadFormat::Multipart => {
+                    let part = reqwest::multipart::Part::bytes(body.to_vec())
+                        .file_name("blob")
+                        .mime_str("application/octet-stream")
+                        .map_err(|e| DaError::Transport(format!("multipart part: {e}")))?;
+                    let form = reqwest::multipart::Form::new()
+                        .text("namespace", namespace.to_string())
+                        .part("data", part);
+                    req.multipart(form)
+                }
+            };
 
-            match resp {
+            match req.send().await {
                 Ok(r) => {
-                    if r.status().is_success() {
+                    let status = r.status();
+                    if status.is_success() {
                         let json = r.json::<serde_json::Map<String, JsonValue>>().await
-                            .map_err(|e| Error::Http(format!("parse DA POST json: {e}")))?;
-                        let commitment = json.get("commitment")
-                            .and_then(|v| v.as_str())
-                            .ok_or_else(|| Error::Http("missing commitment in response".into()))?
-                            .to_string();
-                        let namespace = json.get("namespace")
-                            .and_then(|v| v.as_u64())
-                            .ok_or_else(|| Error::Http("missing namespace in response".into()))? as u32;
-                        let size = json.get("size")
-                            .and_then(|v| v.as_u64())
-                            .ok_or_else(|| Error::Http("missing size in response".into()))?;
-                        let extra = json.into_iter().filter(|(k,_)| k != "commitment" && k != "namespace" && k != "size")
-                            .collect();
-                        return Ok(DaPutResult { commitment, namespace, size, extra });
-                    } else if should_retry_status(r.status()) && attempt <= self.retries {
+                            .map_err(|e| DaError::BadResponse(format!("parse DA POST json: {e}")))?;
+                        return parse_put_result(json);
+                    } else if status == StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                        return Err(DaError::UnsupportedMediaType(format!(
+                            "DA POST({label}) failed: {}",
+                            r.text().await.unwrap_or_default()
+                        )));
+                    } else if should_retry_status(status) && attempt <= self.retries {
                         sleep(self.backoff).await;
                         continue;
                     } else {
-                        return Err(Error::Http(format!(
-                            "DA POST(json) failed: {}",
+                        return Err(DaError::from_status(status, format!(
+                            "DA POST({label}) failed: {}",
                             r.text().await.unwrap_or_else(|_| "<no body>".into())
                         )));
                     }
@@ -229,13 +635,13 @@ impl DAClient {
                         sleep(self.backoff).await;
                         continue;
                     }
-                    return Err(Error::Http(format!("DA POST(json) error: {e}")));
+                    return Err(DaError::Transport(format!("DA POST({label}) error: {e}")));
                 }
             }
         }
     }
 
-    async fn with_retries_get_bytes(&self, url: Url) -> Result<Vec<u8>> {
+    async fn with_retries_get_bytes(&self, url: Url) -> Result<Vec<u8>, DaError> {
         let mut attempt = 0usize;
         loop {
             attempt += 1;
@@ -245,14 +651,14 @@ impl DAClient {
                     if r.status().is_success() {
                         return r.bytes().await
                             .map(|b| b.to_vec())
-                            .map_err(|e| Error::Http(format!("DA GET bytes read: {e}")));
+                            .map_err(|e| DaError::Transport(format!("DA GET bytes read: {e}")));
                     } else if should_retry_status(r.status()) && attempt <= self.retries {
                         sleep(self.backoff).await;
                         continue;
                     } else if r.status() == StatusCode::NOT_FOUND {
-                        return Err(Error::Http("blob not found".into()));
+                        return Err(DaError::NotFound("blob not found".into()));
                     } else {
-                        return Err(Error::Http(format!(
+                        return Err(DaError::from_status(r.status(), format!(
                             "DA GET failed: {}",
                             r.text().await.unwrap_or_else(|_| "<no body>".into())
                         )));
@@ -263,13 +669,13 @@ impl DAClient {
                         sleep(self.backoff).await;
                         continue;
                     }
-                    return Err(Error::Http(format!("DA GET error: {e}")));
+                    return Err(DaError::Transport(format!("DA GET error: {e}")));
                 }
             }
         }
     }
 
-    async fn with_retries_get_json(&self, url: Url) -> Result<JsonValue> {
+    async fn with_retries_get_json(&self, url: Url) -> Result<JsonValue, DaError> {
         let mut attempt = 0usize;
         loop {
             attempt += 1;
@@ -278,14 +684,14 @@ impl DAClient {
                 Ok(r) => {
                     if r.status().is_success() {
                         return r.json::<JsonValue>().await
-                            .map_err(|e| Error::Http(format!("DA GET json parse: {e}")));
+                            .map_err(|e| DaError::BadResponse(format!("DA GET json parse: {e}")));
                     } else if should_retry_status(r.status()) && attempt <= self.retries {
                         sleep(self.backoff).await;
                         continue;
                     } else if r.status() == StatusCode::NOT_FOUND {
-                        return Err(Error::Http("proof not found".into()));
+                        return Err(DaError::NotFound("proof not found".into()));
                     } else {
-                        return Err(Error::Http(format!(
+                        return Err(DaError::from_status(r.status(), format!(
                             "DA GET json failed: {}",
                             r.text().await.unwrap_or_else(|_| "<no body>".into())
                         )));
@@ -296,13 +702,50 @@ impl DAClient {
                         sleep(self.backoff).await;
                         continue;
                     }
-                    return Err(Error::Http(format!("DA GET json error: {e}")));
+                    return Err(DaError::Transport(format!("DA GET json error: {e}")));
                 }
             }
         }
     }
 }
 
+fn parse_put_result(json: serde_json::Map<String, JsonValue>) -> Result<DaPutResult, DaError> {
+    let commitment = json.get("commitment")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| DaError::BadResponse("missing commitment in response".into()))?
+        .to_string();
+    let namespace = json.get("namespace")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| DaError::BadResponse("missing namespace in response".into()))? as u32;
+    let size = json.get("size")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| DaError::BadResponse("missing size in response".into()))?;
+    let extra = json.into_iter().filter(|(k,_)| k != "commitment" && k != "namespace" && k != "size")
+        .collect();
+    Ok(DaPutResult { commitment, namespace, size, extra })
+}
+
+/// Serde adapter for optional `0x`-hex byte strings.
+mod opt_hex {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &Option<Vec<u8>>, s: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Some(b) => s.serialize_str(&format!("0x{}", hex::encode(b))),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Vec<u8>>, D::Error> {
+        match Option::<String>::deserialize(d)? {
+            Some(h) => hex::decode(h.strip_prefix("0x").unwrap_or(&h))
+                .map(Some)
+                .map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
 fn should_retry_status(s: StatusCode) -> bool {
     s.is_server_error() || s == StatusCode::TOO_MANY_REQUESTS || s == StatusCode::BAD_GATEWAY || s == StatusCode::SERVICE_UNAVAILABLE || s == StatusCode::GATEWAY_TIMEOUT
 }
@@ -317,6 +760,8 @@ fn percent_encode(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{serve_http, Response};
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn builds_urls() {
@@ -343,4 +788,388 @@ mod tests {
         assert_eq!(v.size, 4096);
         assert!(v.extra.contains_key("nmt_root"));
     }
+
+    /// Blob server: `/da/blob/slow*` answers after 5s, everything else at once
+    /// with the commitment echoed as the body.
+    fn spawn_shard_server() -> String {
+        serve_http(|req| {
+            let id = req.path.trim_start_matches("/da/blob/").to_string();
+            if id.starts_with("slow") {
+                std::thread::sleep(Duration::from_secs(5));
+            }
+            Response::body(200, id).header("Content-Type", "application/octet-stream")
+        })
+        .url
+    }
+
+    #[tokio::test]
+    async fn fetch_k_shards_returns_first_k() {
+        let c = DAClient::new(&spawn_shard_server()).unwrap().with_retries(0);
+        let commitments: Vec<String> = ["slow0", "fast1", "slow2", "fast3", "fast4", "slow5"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let started = std::time::Instant::now();
+        let mut got = c.fetch_k_shards(&commitments, 3).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(4), "must not wait for slow shards");
+
+        got.sort();
+        assert_eq!(got.len(), 3);
+        assert_eq!(
+            got,
+            vec![(1, b"fast1".to_vec()), (3, b"fast3".to_vec()), (4, b"fast4".to_vec())]
+        );
+
+        assert!(c.fetch_k_shards(&commitments, 7).await.is_err());
+    }
+
+    /// `(content-type, body)` of each request an upload mock received.
+    type UploadLog = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+    /// Upload mock: records `(content-type, body)` per request and answers with
+    /// a receipt, or 415 for octet-stream bodies when `reject_octet` is set.
+    fn spawn_upload_server(reject_octet: bool) -> (String, UploadLog) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let mock = serve_http(move |req| {
+            let ctype = req.header("content-type").unwrap_or_default().to_ascii_lowercase();
+            log.lock().unwrap().push((ctype.clone(), req.body.clone()));
+            if reject_octet && ctype == "application/octet-stream" {
+                Response::status(415)
+            } else {
+                Response::json(&serde_json::json!({"commitment": "0xc0ffee", "namespace": 7, "size": 3}))
+            }
+        });
+        (mock.url, seen)
+    }
+
+    #[tokio::test]
+    async fn post_blob_uses_configured_format() {
+        let (url, seen) = spawn_upload_server(false);
+        let c = DAClient::new(&url).unwrap().with_retries(0);
+
+        let r = c.clone().with_upload_format(UploadFormat::Octet).post_blob(7, b"abc").await.unwrap();
+        assert_eq!(r.commitment, "0xc0ffee");
+        c.clone().with_upload_format(UploadFormat::JsonHex).post_blob(7, b"abc").await.unwrap();
+        c.with_upload_format(UploadFormat::Multipart).post_blob(7, b"abc").await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0], ("application/octet-stream".to_string(), b"abc".to_vec()));
+
+        assert_eq!(seen[1].0, "application/json");
+        let env: JsonValue = serde_json::from_slice(&seen[1].1).unwrap();
+        assert_eq!(env, serde_json::json!({"namespace": 7, "data": "0x616263"}));
+
+        assert!(seen[2].0.starts_with("multipart/form-data; boundary="));
+        let form = String::from_utf8_lossy(&seen[2].1);
+        assert!(form.contains("name=\"namespace\"") && form.contains("name=\"data\""));
+        assert!(form.contains("\r\n\r\nabc\r\n"));
+    }
+
+    #[tokio::test]
+    async fn post_blob_falls_back_to_json_only_on_415() {
+        let (url, seen) = spawn_upload_server(true);
+        let c = DAClient::new(&url).unwrap().with_retries(0);
+
+        // Default: octet rejected with 415, JSON envelope accepted.
+        c.post_blob(7, b"abc").await.unwrap();
+        {
+            let seen = seen.lock().unwrap();
+            assert_eq!(seen.len(), 2);
+            assert_eq!(seen[1].0, "application/json");
+        }
+
+        // Explicit octet: the 415 is surfaced with its status, no fallback.
+        let err = c.with_upload_format(UploadFormat::Octet).post_blob(7, b"abc").await.unwrap_err();
+        assert!(matches!(err, DaError::UnsupportedMediaType(_)), "{err}");
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    /// Answers every request with `status` and a short text body.
+    fn spawn_status_server(status: u16) -> String {
+        serve_http(move |_| Response::body(status, "boom")).url
+    }
+
+    #[tokio::test]
+    async fn status_codes_map_to_typed_errors() {
+        let c = DAClient::new(&spawn_status_server(404)).unwrap().with_retries(0);
+        assert_eq!(c.get_blob("0x01").await.unwrap_err(), DaError::NotFound("blob not found".into()));
+        assert_eq!(c.get_proof("0x01").await.unwrap_err(), DaError::NotFound("proof not found".into()));
+
+        let c = DAClient::new(&spawn_status_server(500)).unwrap().with_retries(0);
+        assert_eq!(c.get_blob("0x01").await.unwrap_err(), DaError::Unavailable("DA GET failed: boom".into()));
+        let err = c.post_blob(7, b"abc").await.unwrap_err();
+        assert!(matches!(err, DaError::Unavailable(_)), "{err}");
+
+        // Typed errors survive conversion into the crate error.
+        let top: crate::error::Error = err.into();
+        assert!(matches!(top, crate::error::Error::Da(DaError::Unavailable(_))));
+        assert!(top.is_retryable());
+    }
+
+    #[test]
+    fn proof_from_fixture() {
+        let json: JsonValue = serde_json::from_str(r#"{
+            "commitment": "0x7f3a0c1e9b2d4f6a8c0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e",
+            "namespace": 24,
+            "nodes": [
+                "0x000000180000001811aa22bb33cc44dd55ee66ff7788990011aa22bb33cc44dd55ee66ff77889900",
+                "0x00000018000000ffcafebabecafebabecafebabecafebabecafebabecafebabecafebabecafebabe"
+            ],
+            "leaf_range": { "start": 4, "end": 7 },
+            "size": 1536,
+            "algo": "nmt-sha3-256",
+            "row_roots": ["0x01", "0x02"]
+        }"#).unwrap();
+
+        let p = DaProof::from_json(json).unwrap();
+        assert_eq!(p.namespace, 24);
+        assert_eq!(p.proof_nodes.len(), 2);
+        assert_eq!(p.leaf_range, DaLeafRange { start: 4, end: 7 });
+        assert_eq!(p.blob_size, 1536);
+        assert_eq!(p.raw["algo"], "nmt-sha3-256");
+        assert!(p.raw.get("row_roots").is_some());
+
+        assert!(DaProof::from_json(serde_json::json!({ "commitment": "0x00" })).is_err());
+    }
+
+    #[tokio::test]
+    async fn post_blob_stream_sends_exact_body_without_retry() {
+        let blob: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let (url, seen) = spawn_upload_server(false);
+        let c = DAClient::new(&url).unwrap();
+        let res = c
+            .post_blob_stream(7, std::io::Cursor::new(blob.clone()), blob.len() as u64)
+            .await
+            .unwrap();
+        assert_eq!(res.namespace, 7);
+        {
+            let seen = seen.lock().unwrap();
+            assert_eq!(seen.len(), 1);
+            assert_eq!(seen[0].0, "application/octet-stream");
+            assert_eq!(seen[0].1, blob);
+        }
+
+        // A failing server is hit exactly once: streamed bodies aren't retried.
+        let mock = serve_http(|_| Response::status(503));
+        let c = DAClient::new(&mock.url).unwrap().with_backoff(Duration::from_millis(1));
+        let err = c.post_blob_stream(7, std::io::Cursor::new(vec![1u8; 16]), 16).await.unwrap_err();
+        assert!(matches!(err, DaError::Unavailable(_)), "{err:?}");
+        assert_eq!(mock.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn get_namespace_follows_cursor() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let mock = serve_http(move |req| {
+            let body = if req.path.contains("cursor=p2") {
+                r#"{"blobs":[{"commitment":"0x03","size":0}],"next":null}"#
+            } else {
+                r#"{"blobs":[{"commitment":"0x01","size":2,"data":"0xbeef","row":1},
+                             {"commitment":"0x02","size":5}],"next":"p2"}"#
+            };
+            log.lock().unwrap().push(req.path.clone());
+            Response::body(200, body).header("Content-Type", "application/json")
+        });
+
+        let c = DAClient::new(&mock.url).unwrap();
+        let blobs = c.get_namespace(24, 100).await.unwrap();
+        let ids: Vec<_> = blobs.iter().map(|b| b.commitment.as_str()).collect();
+        assert_eq!(ids, ["0x01", "0x02", "0x03"]);
+        assert_eq!(blobs[0].data.as_deref(), Some(&[0xbe, 0xef][..]));
+        assert_eq!(blobs[0].extra["row"], 1);
+        assert!(blobs[1].data.is_none());
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["/da/namespace/24?height=100", "/da/namespace/24?height=100&cursor=p2"]
+        );
+    }
+
+    /// Proof for a one-leaf tree: `blob` under ns 24 is the root, no nodes.
+    #[cfg(feature = "nmt")]
+    fn blob_and_proof(blob: &[u8]) -> (String, JsonValue) {
+        let root = animica_native::nmt::nmt_root(&[(ns_from_u32(24), blob)]).unwrap();
+        let commitment = format!("0x{}", hex::encode(root.hash));
+        let proof = serde_json::json!({
+            "commitment": commitment,
+            "namespace": 24,
+            "nodes": [],
+            "leaf_range": { "start": 0, "end": 1 },
+            "size": blob.len(),
+        });
+        (commitment, proof)
+    }
+
+    /// Single-leaf proof for `blob` at `index` in a tree of mixed-namespace
+    /// blobs, with the path produced by `animica_native::nmt::open`.
+    #[cfg(feature = "nmt")]
+    fn opened_blob_proof(blob: &[u8], index: usize) -> (String, JsonValue) {
+        use animica_native::nmt;
+
+        let others: Vec<Vec<u8>> = (0..7u8).map(|i| vec![i; 40 + i as usize]).collect();
+        let mut leaves: Vec<(nmt::Ns, &[u8])> =
+            others.iter().enumerate().map(|(i, b)| (ns_from_u32(20 + i as u32), &b[..])).collect();
+        leaves[index] = (ns_from_u32(24), blob);
+        let root = nmt::nmt_root(&leaves).unwrap();
+        let path = nmt::open(&leaves, index).unwrap().path;
+        let nodes: Vec<String> = path
+            .iter()
+            .map(|n| format!("0x{}{}{}", hex::encode(n.min_ns), hex::encode(n.max_ns), hex::encode(n.hash)))
+            .collect();
+        let commitment = format!("0x{}", hex::encode(root.hash));
+        let proof = serde_json::json!({
+            "commitment": commitment,
+            "namespace": 24,
+            "nodes": nodes,
+            "leaf_range": { "start": index, "end": index + 1 },
+            "size": blob.len(),
+        });
+        (commitment, proof)
+    }
+
+    #[cfg(feature = "nmt")]
+    #[test]
+    fn verify_blob_recomputes_nmt_root() {
+        let blob: Vec<u8> = (0..1300u32).map(|i| i as u8).collect();
+        let (commitment, proof) = blob_and_proof(&blob);
+        assert!(verify_blob(&commitment, 24, &blob, &proof).unwrap());
+
+        let mut tampered = blob.clone();
+        tampered[700] ^= 1;
+        assert!(!verify_blob(&commitment, 24, &tampered, &proof).unwrap());
+        assert!(!verify_blob(&commitment, 25, &blob, &proof).unwrap(), "wrong namespace");
+        assert!(!verify_blob(&commitment, 24, &blob[..1299], &proof).unwrap(), "wrong size");
+
+        let other = format!("0x{}", "00".repeat(32));
+        assert!(verify_blob(&other, 24, &blob, &proof).is_err(), "proof for another commitment");
+    }
+
+    #[cfg(feature = "nmt")]
+    #[test]
+    fn verify_blob_checks_sibling_path() {
+        let blob = b"an inner blob of namespace 24".to_vec();
+        for index in [0, 3, 5, 6] {
+            let (commitment, proof) = opened_blob_proof(&blob, index);
+            assert!(verify_blob(&commitment, 24, &blob, &proof).unwrap(), "index {index}");
+
+            let mut tampered = blob.clone();
+            tampered[0] ^= 1;
+            assert!(!verify_blob(&commitment, 24, &tampered, &proof).unwrap());
+
+            // Claiming a different position flips a sibling side. (Bit 1, not
+            // bit 0: the last leaf's level-0 sibling is its own padding copy.)
+            let mut moved = proof.clone();
+            moved["leaf_range"] = serde_json::json!({ "start": index ^ 2, "end": (index ^ 2) + 1 });
+            assert!(!verify_blob(&commitment, 24, &blob, &moved).unwrap());
+
+            let mut bad_node = proof.clone();
+            bad_node["nodes"][0] = "0x1234".into();
+            assert!(matches!(verify_blob(&commitment, 24, &blob, &bad_node), Err(DaError::BadResponse(_))));
+        }
+    }
+
+    #[cfg(feature = "nmt")]
+    #[test]
+    fn verify_blob_rejects_unsupported_proof_shapes() {
+        // A range proof with siblings, as in the `proof_from_fixture` response.
+        let commitment = "0x7f3a0c1e9b2d4f6a8c0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e";
+        let proof = serde_json::json!({
+            "commitment": commitment,
+            "namespace": 24,
+            "nodes": ["0x000000180000001811aa22bb33cc44dd55ee66ff7788990011aa22bb33cc44dd55ee66ff77889900"],
+            "leaf_range": { "start": 4, "end": 7 },
+            "size": 3,
+        });
+        let err = verify_blob(commitment, 24, b"abc", &proof).unwrap_err();
+        assert!(matches!(err, DaError::UnsupportedProof(_)), "{err:?}");
+
+        // Nor is a sibling-less multi-leaf range: there is no rule for how
+        // the blob would be split across those leaves.
+        let blob = b"two leaves?".to_vec();
+        let (root, mut whole) = blob_and_proof(&blob);
+        whole["leaf_range"]["end"] = 2.into();
+        let err = verify_blob(&root, 24, &blob, &whole).unwrap_err();
+        assert!(matches!(err, DaError::UnsupportedProof(_)), "{err:?}");
+
+        let mut sha3 = proof.clone();
+        sha3["leaf_range"] = serde_json::json!({ "start": 4, "end": 5 });
+        sha3["algo"] = "nmt-sha3-256".into();
+        let err = verify_blob(commitment, 24, b"abc", &sha3).unwrap_err();
+        assert!(matches!(err, DaError::UnsupportedProof(_)), "{err:?}");
+    }
+
+    /// Serves `proof` at `/da/blob/{c}/proof` and `blob` at `/da/blob/{c}`.
+    #[cfg(feature = "nmt")]
+    fn spawn_blob_server(proof: JsonValue, blob: Vec<u8>) -> String {
+        serve_http(move |req| {
+            if req.path.ends_with("/proof") {
+                Response::json(&proof)
+            } else {
+                Response::body(200, blob.clone()).header("Content-Type", "application/octet-stream")
+            }
+        })
+        .url
+    }
+
+    #[cfg(feature = "nmt")]
+    #[tokio::test]
+    async fn get_blob_verified_rejects_tampered_bytes() {
+        let (commitment, proof) = opened_blob_proof(b"hello namespaced world", 2);
+        let url = spawn_blob_server(proof, b"hello namespaced w0rld".to_vec());
+
+        let c = DAClient::new(&url).unwrap().with_retries(0);
+        let err = c.get_blob_verified(&commitment, 24).await.unwrap_err();
+        assert!(matches!(err, DaError::CommitmentMismatch(_)), "{err:?}");
+
+        // A blob served with a real sibling path verifies.
+        let blob = b"hello namespaced world".to_vec();
+        let (commitment, proof) = opened_blob_proof(&blob, 5);
+        let c = DAClient::new(&spawn_blob_server(proof, blob.clone())).unwrap().with_retries(0);
+        assert_eq!(c.get_blob_verified(&commitment, 24).await.unwrap(), blob);
+    }
+
+    #[cfg(feature = "nmt")]
+    #[tokio::test]
+    async fn reconstruct_blob_from_surviving_shards() {
+        use animica_native::rs::{self, layout::Layout, Encoding, RsParams};
+
+        let payload: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let (commitment, proof) = blob_and_proof(&payload);
+        let c = DAClient::new(&spawn_blob_server(proof, vec![])).unwrap();
+
+        let params = RsParams::new(4, 2);
+        let layout = Layout::with_default_align(payload.len(), 4, 2).unwrap();
+        let mut shards = layout.shardify(&payload).unwrap();
+        rs::encode_in_place(params, &mut shards).unwrap();
+
+        let survivors = |lost: &[usize], shards: &[Vec<u8>]| -> Vec<Option<Vec<u8>>> {
+            shards.iter().enumerate().map(|(i, s)| (!lost.contains(&i)).then(|| s.clone())).collect()
+        };
+        let got = c.reconstruct_blob(&commitment, params, Encoding::Systematic, survivors(&[0, 3], &shards)).await.unwrap();
+        assert_eq!(got, payload);
+
+        let err = c.reconstruct_blob(&commitment, params, Encoding::Systematic, survivors(&[0, 1, 2], &shards)).await.unwrap_err();
+        assert!(matches!(err, DaError::Unavailable(_)), "{err:?}");
+
+        let mut corrupt = shards.clone();
+        corrupt[1][5] ^= 0xff;
+        let err = c.reconstruct_blob(&commitment, params, Encoding::Systematic, survivors(&[0], &corrupt)).await.unwrap_err();
+        assert!(matches!(err, DaError::CommitmentMismatch(_)), "{err:?}");
+
+        // Same shards, but the blob is one leaf of a larger tree with a sibling path.
+        let (commitment, proof) = opened_blob_proof(&payload, 3);
+        let c = DAClient::new(&spawn_blob_server(proof, vec![])).unwrap();
+        let got = c.reconstruct_blob(&commitment, params, Encoding::Systematic, survivors(&[2, 5], &shards)).await.unwrap();
+        assert_eq!(got, payload);
+        let err = c.reconstruct_blob(&commitment, params, Encoding::Systematic, survivors(&[0], &corrupt)).await.unwrap_err();
+        assert!(matches!(err, DaError::CommitmentMismatch(_)), "{err:?}");
+
+        // Non-systematic shards are unmixed before the payload is read.
+        let mut mixed = layout.shardify(&payload).unwrap();
+        rs::encode_in_place_as(params, Encoding::NonSystematic, &mut mixed).unwrap();
+        let got = c.reconstruct_blob(&commitment, params, Encoding::NonSystematic, survivors(&[1, 4], &mixed)).await.unwrap();
+        assert_eq!(got, payload);
+    }
 }
@@ -135,12 +135,16 @@ pub enum Error {
     // ---- Domain services ---------------------------------------------------
     /// Data Availability client error.
     #[error("da client error: {0}")]
-    Da(String),
+    Da(#[from] crate::da::client::DaError),
 
     /// AI Compute Fund client error.
     #[error("aicf client error: {0}")]
     Aicf(String),
 
+    /// Waiting for an AICF job result timed out.
+    #[error("aicf result not ready within timeout for task {0}")]
+    AicfTimeout(String),
+
     /// Randomness client error.
     #[error("randomness client error: {0}")]
     Randomness(String),
@@ -162,6 +166,10 @@ impl Error {
         match self {
             Network(_) | WebSocket(_) | Timeout(_) => true,
             HttpStatus { status, .. } => (500..=599).contains(status) || *status == 429,
+            Da(e) => {
+                use crate::da::client::DaError;
+                matches!(e, DaError::Unavailable(_) | DaError::Transport(_))
+            }
             Rpc(e) => {
                 // Heuristics: JSON-RPC codes < -32000 are server internal;
                 // -32603 (Internal error) generally retryable; -320xx also often server-side.
@@ -185,8 +193,8 @@ impl Error {
             | PqUnavailable
             | Abi(_)
             | Events(_)
-            | Da(_)
             | Aicf(_)
+            | AicfTimeout(_)
             | Randomness(_)
             | FeatureUnavailable
             | InvalidParams(_) => false,
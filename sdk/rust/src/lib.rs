@@ -31,6 +31,7 @@ pub mod utils {
     pub mod hash;
     pub mod cbor;
     pub mod bech32;
+    pub mod random;
 }
 
 /// Address codec and helpers (bech32m `anim1…`).
@@ -43,8 +44,10 @@ pub mod tx {
     pub mod send;
 }
 
-/// RPC clients (HTTP/WS). Implementations are feature-gated in the module files.
+/// RPC clients (HTTP/WS, plus a `client::Client` facade routing between them).
+/// Implementations are feature-gated in the module files.
 pub mod rpc {
+    pub mod client;
     pub mod http;
     pub mod ws;
 }
@@ -53,6 +56,7 @@ pub mod rpc {
 pub mod wallet {
     pub mod mnemonic;
     pub mod keystore;
+    pub mod path;
     pub mod signer;
 }
 
@@ -91,6 +95,10 @@ pub mod proofs {
     pub mod quantum;
 }
 
+/// Loopback mock servers for the unit tests.
+#[cfg(test)]
+mod test_util;
+
 /// Bundled schema strings generated by `build.rs`.
 ///
 /// These are optional and may contain `"{}"` placeholders when the repository
@@ -12,17 +12,32 @@
 // - We expose a small "bench" estimator to compute `quantum_units` from
 //   (depth × width × shots); networks may tune coefficients on-chain.
 // - We validate obvious bounds (ratios in [0,1], shots > 0, hex lengths).
+// - Trap confidence defaults to the 95% Wilson score lower bound of
+//   passed/total, which stays meaningful for small trap counts.
 //
 // This is **not** a consensus implementation. It produces a reference
-// object that higher-level tooling can convert into the canonical CBOR
-// envelope expected by validators.
+// object plus the canonical CBOR envelope expected by validators:
+//
+//   envelope = [ "animica:v1:quantum", body ]
+//
+// where `body` is the reference as a CBOR map with every map (including
+// free-form `evidence`/`meta`/`extra` values) sorted by encoded key bytes
+// (RFC 8949 §4.2.1), shortest-form integers/floats and definite lengths.
 
 use crate::error::{Error, Result};
+use ciborium::Value as CborValue;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sha3::{Digest, Sha3_256};
 use std::collections::BTreeMap;
 
+/// Domain tag leading the canonical CBOR envelope; matches the native
+/// `DsTag::Quantum` context string.
+pub const QUANTUM_DS_TAG: &str = "animica:v1:quantum";
+
+/// Two-sided 95% normal quantile, the default `z` for trap confidence.
+pub const WILSON_Z_95: f64 = 1.959_963_984_540_054;
+
 /// Provider identity/attestation bundle (opaque to the SDK).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -45,7 +60,8 @@ pub struct QuantumTraps {
     /// Optional ratio; computed when absent on `validate()`.
     #[serde(default)]
     pub ratio: Option<f64>,
-    /// Optional Wilson/Clopper-Pearson lower bound in [0,1].
+    /// Optional lower confidence bound in [0,1]; the 95% Wilson lower bound
+    /// is computed when absent on `validate()`.
     #[serde(default)]
     pub confidence: Option<f64>,
     #[serde(flatten)]
@@ -60,6 +76,26 @@ impl QuantumTraps {
             (self.passed as f64) / (self.total as f64)
         }
     }
+
+    /// Lower bound of the Wilson score interval for the pass rate at normal
+    /// quantile `z` (e.g. [`WILSON_Z_95`]):
+    ///
+    /// ```text
+    /// (p + z²/2n − z·√(p(1−p)/n + z²/4n²)) / (1 + z²/n),   p = passed/total, n = total
+    /// ```
+    ///
+    /// Returns 0.0 when `total == 0`.
+    pub fn wilson_lower_bound(&self, z: f64) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let n = self.total as f64;
+        let p = self.compute_ratio().min(1.0);
+        let z2 = z * z;
+        let centre = p + z2 / (2.0 * n);
+        let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        ((centre - margin) / (1.0 + z2 / n)).clamp(0.0, 1.0)
+    }
 }
 
 /// QoS metrics for the quantum provider/job.
@@ -186,12 +222,12 @@ impl QuantumProofRef {
             if !(0.0..=1.0).contains(&ratio) {
                 return Err(Error::InvalidData(format!("trap ratio out of range: {ratio}")));
             }
-            if let Some(c) = t.confidence {
-                if !(0.0..=1.0).contains(&c) {
-                    return Err(Error::InvalidData(format!("confidence out of range: {c}")));
-                }
+            let confidence = t.confidence.unwrap_or_else(|| t.wilson_lower_bound(WILSON_Z_95));
+            if !(0.0..=1.0).contains(&confidence) {
+                return Err(Error::InvalidData(format!("confidence out of range: {confidence}")));
             }
             t.ratio = Some(ratio);
+            t.confidence = Some(confidence);
         }
         if let Some(q) = self.qos.as_ref() {
             if let Some(a) = q.availability {
@@ -224,6 +260,47 @@ impl QuantumProofRef {
         }
         Ok(())
     }
+
+    /// Byte-exact JSON signing preimage: compact (no insignificant whitespace)
+    /// with object keys sorted at every depth, including inside the free-form
+    /// `evidence`/`meta`/`extra` values. Equivalent references always produce
+    /// the same bytes, whatever order their maps were built in.
+    pub fn to_canonical_json(&self) -> Result<Vec<u8>> {
+        let v = canonicalize(serde_json::to_value(self)?);
+        Ok(serde_json::to_vec(&v)?)
+    }
+
+    /// Canonical CBOR envelope `[QUANTUM_DS_TAG, body]` (see module notes).
+    /// The reference is validated first; derived fields (e.g. the trap ratio)
+    /// are encoded as `validate()` fills them, without mutating `self`.
+    pub fn to_canonical_cbor(&self) -> Result<Vec<u8>> {
+        let mut proof = self.clone();
+        proof.validate()?;
+        let body = CborValue::serialized(&proof).map_err(|e| Error::CborEncode(e.to_string()))?;
+        let envelope = CborValue::Array(vec![CborValue::Text(QUANTUM_DS_TAG.into()), canonicalize_cbor(body)?]);
+        let mut out = Vec::with_capacity(256);
+        ciborium::ser::into_writer(&envelope, &mut out)?;
+        Ok(out)
+    }
+
+    /// Inverse of [`to_canonical_cbor`](Self::to_canonical_cbor). Rejects a
+    /// foreign domain tag, invalid references, and any encoding that is not
+    /// byte-for-byte canonical (so equal proofs always hash equally).
+    pub fn from_canonical_cbor(bytes: &[u8]) -> Result<Self> {
+        let envelope: CborValue = ciborium::de::from_reader(bytes)?;
+        let body = match envelope {
+            CborValue::Array(items) => match <[CborValue; 2]>::try_from(items) {
+                Ok([CborValue::Text(tag), body]) if tag == QUANTUM_DS_TAG => body,
+                _ => return Err(Error::CborDecode(format!("expected [\"{QUANTUM_DS_TAG}\", body] envelope"))),
+            },
+            _ => return Err(Error::CborDecode("quantum proof envelope must be an array".into())),
+        };
+        let proof: Self = body.deserialized().map_err(|e| Error::CborDecode(e.to_string()))?;
+        if proof.to_canonical_cbor()? != bytes {
+            return Err(Error::CborDecode("quantum proof envelope is not canonical".into()));
+        }
+        Ok(proof)
+    }
 }
 
 /// Coefficients for unit estimation from (depth, width, shots).
@@ -274,6 +351,45 @@ fn hex0x(bytes: impl AsRef<[u8]>) -> String {
     format!("0x{}", hex::encode(bytes))
 }
 
+/// Rebuild every object with its keys inserted in sorted order, so the output
+/// is sorted whether or not serde_json's `preserve_order` feature is enabled.
+fn canonicalize(v: JsonValue) -> JsonValue {
+    match v {
+        JsonValue::Object(map) => {
+            let sorted: BTreeMap<String, JsonValue> =
+                map.into_iter().map(|(k, v)| (k, canonicalize(v))).collect();
+            JsonValue::Object(sorted.into_iter().collect())
+        }
+        JsonValue::Array(items) => JsonValue::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
+/// Sort every map by its keys' encoded bytes (RFC 8949 deterministic order);
+/// duplicate keys are an error.
+fn canonicalize_cbor(v: CborValue) -> Result<CborValue> {
+    Ok(match v {
+        CborValue::Map(entries) => {
+            let mut keyed = entries
+                .into_iter()
+                .map(|(k, v)| {
+                    let mut enc = Vec::new();
+                    ciborium::ser::into_writer(&k, &mut enc)?;
+                    Ok((enc, k, canonicalize_cbor(v)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            if keyed.windows(2).any(|w| w[0].0 == w[1].0) {
+                return Err(Error::CborEncode("duplicate map key".into()));
+            }
+            CborValue::Map(keyed.into_iter().map(|(_, k, v)| (k, v)).collect())
+        }
+        CborValue::Array(items) => CborValue::Array(items.into_iter().map(canonicalize_cbor).collect::<Result<_>>()?),
+        CborValue::Tag(tag, inner) => CborValue::Tag(tag, Box::new(canonicalize_cbor(*inner)?)),
+        other => other,
+    })
+}
+
 fn is_hex32(s: &str) -> bool {
     s.starts_with("0x") && s.len() == 66 && s[2..].bytes().all(|b| matches!(b, b'0'..=b'9'|b'a'..=b'f'|b'A'..=b'F'))
 }
@@ -344,6 +460,113 @@ mod tests {
         assert!(pref.output_digest.as_ref().unwrap().starts_with("0x"));
     }
 
+    #[test]
+    fn canonical_json_ignores_key_order() {
+        let a: QuantumProofRef = serde_json::from_str(
+            r#"{"circuitDigest":"0x00","shots":4,
+                "providerCert":{"provider":"qpu:x","evidence":{"z":1,"a":{"y":[{"k":2,"b":3}],"c":null}}},
+                "meta":{"run":"r1","app":{"v":2,"n":"demo"}}}"#,
+        )
+        .unwrap();
+        let b: QuantumProofRef = serde_json::from_str(
+            r#"{ "meta": {"app": {"n": "demo", "v": 2}, "run": "r1"},
+                 "providerCert": {"evidence": {"a": {"c": null, "y": [{"b": 3, "k": 2}]}, "z": 1}, "provider": "qpu:x"},
+                 "shots": 4, "circuitDigest": "0x00" }"#,
+        )
+        .unwrap();
+
+        let ca = a.to_canonical_json().unwrap();
+        assert_eq!(ca, b.to_canonical_json().unwrap());
+        let text = String::from_utf8(ca).unwrap();
+        assert!(!text.contains(' ') && !text.contains('\n'));
+        assert!(text.contains(r#""evidence":{"a":{"c":null,"y":[{"b":3,"k":2}]},"z":1}"#));
+    }
+
+    fn golden_ref() -> QuantumProofRef {
+        let cert = QuantumProviderCert {
+            provider: "qpu:x".into(),
+            evidence: json!({ "pk": "ab", "alg": 1 }),
+            extra: BTreeMap::new(),
+        };
+        let traps = QuantumTraps { passed: 3, total: 4, ratio: None, confidence: None, extra: BTreeMap::new() };
+        QuantumProofRef::from_circuit(b"circuit", 8, cert, Some(traps), None, None).with_units(42)
+    }
+
+    #[test]
+    fn canonical_cbor_roundtrip() {
+        let mut pref = golden_ref();
+        pref.meta.insert("run".into(), json!({ "z": [1, 2.5, null], "a": "b" }));
+        let enc = pref.to_canonical_cbor().unwrap();
+
+        let dec = QuantumProofRef::from_canonical_cbor(&enc).unwrap();
+        assert_eq!(dec.circuit_digest, pref.circuit_digest);
+        assert_eq!(dec.traps.as_ref().unwrap().ratio, Some(0.75), "ratio filled by validation");
+        assert_eq!(dec.meta, pref.meta);
+        assert_eq!(dec.to_canonical_cbor().unwrap(), enc);
+
+        // Foreign domain tag / non-canonical bytes / invalid references are rejected.
+        let mut foreign = enc.clone();
+        foreign[2] ^= 0x20;
+        assert!(QuantumProofRef::from_canonical_cbor(&foreign).is_err());
+        let mut trailing = enc.clone();
+        trailing.push(0);
+        assert!(QuantumProofRef::from_canonical_cbor(&trailing).is_err());
+        pref.shots = 0;
+        assert!(pref.to_canonical_cbor().is_err());
+    }
+
+    #[test]
+    fn canonical_cbor_golden_vector() {
+        // ["animica:v1:quantum", {"qos": null, "meta": {}, "shots": 8, ...}]: keys ordered
+        // shortest-first, the 0.75 trap ratio as a half float (f9 3a00) and the
+        // derived Wilson confidence as a double (fb 3fd3...).
+        const GOLDEN: &str = concat!(
+            "8272616e696d6963613a76313a7175616e74756da963716f73f6646d657461a06573686f74730865",
+            "7472617073a465726174696ff93a0065746f74616c0466706173736564036a636f6e666964656e63",
+            "65fb3fd33db7486cd6926a636f6d706c6578697479f66c6f7574707574446967657374f66c70726f",
+            "766964657243657274a26865766964656e6365a262706b62616263616c67016870726f7669646572",
+            "657170753a786c7175616e74756d556e697473182a6d636972637569744469676573747842307834",
+            "39383434616461336130616462616535386563613964353439383562306466646362383236376538",
+            "3861373031616439653437396537623135643763336665",
+        );
+        let enc = golden_ref().to_canonical_cbor().unwrap();
+        assert_eq!(hex::encode(&enc), GOLDEN);
+        assert_eq!(enc, golden_ref().to_canonical_cbor().unwrap());
+    }
+
+    #[test]
+    fn wilson_lower_bound_known_values() {
+        let traps = |passed, total| QuantumTraps { passed, total, ratio: None, confidence: None, extra: BTreeMap::new() };
+        // Reference values for z = 1.96 (95%).
+        for (passed, total, expected) in [
+            (95, 100, 0.888_250),
+            (50, 100, 0.403_832),
+            (10, 10, 0.722_467),
+            (1, 1, 0.206_549),
+            (0, 10, 0.0),
+        ] {
+            let lb = traps(passed, total).wilson_lower_bound(WILSON_Z_95);
+            assert!((lb - expected).abs() < 1e-5, "{passed}/{total}: {lb} vs {expected}");
+        }
+        assert_eq!(traps(0, 0).wilson_lower_bound(WILSON_Z_95), 0.0);
+
+        // validate() fills an absent confidence and keeps an explicit one.
+        let mut pref = QuantumProofRef::from_circuit(b"c", 1, cert(), Some(traps(50, 100)), None, None);
+        pref.validate().unwrap();
+        let c = pref.traps.as_ref().unwrap().confidence.unwrap();
+        assert!((c - 0.403_832).abs() < 1e-5);
+        let mut pref = QuantumProofRef::from_circuit(
+            b"c",
+            1,
+            cert(),
+            Some(QuantumTraps { confidence: Some(0.5), ..traps(50, 100) }),
+            None,
+            None,
+        );
+        pref.validate().unwrap();
+        assert_eq!(pref.traps.unwrap().confidence, Some(0.5));
+    }
+
     #[test]
     fn bad_bounds_fail() {
         let circ = b"x";
@@ -0,0 +1,214 @@
+//! Unified JSON-RPC facade over both transports.
+//!
+//! `Client` routes request/response calls over HTTP and subscriptions over
+//! WebSocket, so applications don't have to pick a transport per call:
+//! - `call` / `call_raw` / `batch` always go through the [`HttpClient`].
+//! - `subscribe_topic` / `subscribe_with` go through a [`WsClient`] that is
+//!   connected **lazily** on the first subscription and then shared by all
+//!   clones of the `Client`.
+//!
+//! Both transports share one endpoint config: by default the WS URL is derived
+//! from the HTTP URL (`http` → `ws`, `https` → `wss`, same host/port/path). Use
+//! [`Client::from_parts`] when the node serves WS elsewhere.
+
+use crate::error::{Error, Result};
+use crate::rpc::http::HttpClient;
+use crate::rpc::ws::{Subscription, WsClient, WsClientBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use url::Url;
+
+/// HTTP-for-calls, WS-for-subscriptions JSON-RPC client.
+#[derive(Clone)]
+pub struct Client {
+    http: HttpClient,
+    ws_builder: WsClientBuilder,
+    ws_url: Url,
+    ws: Arc<OnceCell<WsClient>>,
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("http", &self.http)
+            .field("ws_url", &self.ws_url)
+            .field("ws_connected", &self.ws_connected())
+            .finish()
+    }
+}
+
+impl Client {
+    /// Build from one endpoint (`http(s)://` or `ws(s)://`); the other
+    /// transport's URL is derived from it. No connection is made yet.
+    pub fn new(endpoint: &str) -> Result<Self> {
+        let url = endpoint
+            .parse::<Url>()
+            .map_err(|e| Error::Transport(format!("bad endpoint url: {e}")))?;
+        let (http_url, ws_url) = (with_scheme(&url, false)?, with_scheme(&url, true)?);
+        let http = HttpClient::new(http_url.as_str())?;
+        Ok(Self::from_parts(http, WsClientBuilder::new(ws_url)))
+    }
+
+    /// Combine separately configured transports (custom headers, timeouts,
+    /// or a WS endpoint that isn't derivable from the HTTP one).
+    pub fn from_parts(http: HttpClient, ws_builder: WsClientBuilder) -> Self {
+        let ws_url = ws_builder.endpoint().clone();
+        Self { http, ws_builder, ws_url, ws: Arc::new(OnceCell::new()) }
+    }
+
+    /// The HTTP transport used for calls.
+    pub fn http(&self) -> &HttpClient {
+        &self.http
+    }
+
+    /// URL the WS transport connects to.
+    pub fn ws_url(&self) -> &Url {
+        &self.ws_url
+    }
+
+    /// Whether the WS transport has been connected (by a subscription or [`Client::ws`]).
+    pub fn ws_connected(&self) -> bool {
+        self.ws.initialized()
+    }
+
+    /// The WS transport, connecting it on first use. Concurrent first callers
+    /// share a single connection attempt; a failed attempt is retried next time.
+    pub async fn ws(&self) -> Result<&WsClient> {
+        self.ws
+            .get_or_try_init(|| WsClient::connect_with(self.ws_builder.clone()))
+            .await
+    }
+
+    /// Typed JSON-RPC call over HTTP (with the HTTP client's retry policy).
+    pub async fn call<T, P>(&self, method: &str, params: P) -> Result<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        self.http.call(method, params).await
+    }
+
+    /// Untyped JSON-RPC call over HTTP.
+    pub async fn call_raw(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        self.http.call_raw(method, params).await
+    }
+
+    /// JSON-RPC batch over HTTP; see [`HttpClient::batch`].
+    pub async fn batch(&self, calls: Vec<(&str, Option<Value>)>) -> Result<Vec<Result<Value>>> {
+        self.http.batch(calls).await
+    }
+
+    /// Subscribe to a hub topic (e.g. `"newHeads"`) over WS, connecting if needed.
+    pub async fn subscribe_topic(&self, topic: &str) -> Result<Subscription> {
+        self.ws().await?.subscribe_topic(topic).await
+    }
+
+    /// Subscribe with custom subscribe/unsubscribe methods over WS, connecting if needed.
+    pub async fn subscribe_with(
+        &self,
+        subscribe_method: &str,
+        unsubscribe_method: &str,
+        params: Value,
+    ) -> Result<Subscription> {
+        self.ws()
+            .await?
+            .subscribe_with(subscribe_method, unsubscribe_method, params)
+            .await
+    }
+}
+
+/// `url` with its scheme mapped to the WS (`ws`/`wss`) or HTTP (`http`/`https`)
+/// flavour, keeping TLS-ness, host, port and path.
+fn with_scheme(url: &Url, ws: bool) -> Result<Url> {
+    let secure = match url.scheme() {
+        "http" | "ws" => false,
+        "https" | "wss" => true,
+        _ => return Err(Error::InvalidParams("endpoint scheme must be http(s) or ws(s)")),
+    };
+    let scheme = match (ws, secure) {
+        (false, false) => "http",
+        (false, true) => "https",
+        (true, false) => "ws",
+        (true, true) => "wss",
+    };
+    // Swapping between these special schemes always succeeds; rebuild to be safe.
+    let rest = &url.as_str()[url.scheme().len()..];
+    format!("{scheme}{rest}")
+        .parse::<Url>()
+        .map_err(|e| Error::Transport(format!("bad endpoint url: {e}")))
+}
+
+// --------------------------------- tests -------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{serve_http, serve_ws, Response};
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn derives_each_transport_url() {
+        let c = Client::new("https://rpc.example.org:8443/rpc").unwrap();
+        assert_eq!(c.ws_url().as_str(), "wss://rpc.example.org:8443/rpc");
+        let c = Client::new("ws://127.0.0.1:8546/").unwrap();
+        assert_eq!(c.ws_url().as_str(), "ws://127.0.0.1:8546/");
+        assert!(!c.ws_connected());
+        assert!(Client::new("ftp://example.org").is_err());
+    }
+
+    /// Mock WS hub: acknowledges the n-th `subscribe` on a connection with id
+    /// `"sub-<n>"` and then pushes one notification under it.
+    fn ws_hub_route(_conn: usize) -> impl FnMut(&Value) -> Option<Vec<Value>> {
+        let mut subs = 0;
+        move |req| {
+            subs += 1;
+            let sub = format!("sub-{subs}");
+            let mut out = vec![json!({"jsonrpc": "2.0", "id": req["id"], "result": sub})];
+            if req["method"] == "subscribe" {
+                out.push(json!({
+                    "jsonrpc": "2.0",
+                    "method": "subscription",
+                    "params": {"subscription": sub, "result": {"number": 6 + subs}},
+                }));
+            }
+            Some(out)
+        }
+    }
+
+    #[tokio::test]
+    async fn calls_use_http_and_first_subscription_connects_ws() {
+        let http = serve_http(|req| Response::rpc_result(&req.json(), json!("http")));
+        let ws = serve_ws(ws_hub_route).await;
+        // The mock pushes a note right behind each ack; buffer it until the
+        // `Subscription` attaches rather than racing the reader.
+        let client = Client::from_parts(
+            HttpClient::new(&http.url).unwrap(),
+            WsClientBuilder::from_str(&ws.url).unwrap().replay_buffer(4),
+        );
+
+        let v: String = client.call("chain.getHead", json!([])).await.unwrap();
+        assert_eq!(v, "http");
+        assert_eq!(http.hits(), 1);
+        assert!(!client.ws_connected());
+        assert_eq!(ws.hits(), 0, "calls must not open a WS");
+
+        let mut sub = client.subscribe_topic("newHeads").await.unwrap();
+        assert!(client.ws_connected());
+        assert_eq!(sub.id(), "sub-1");
+        let head = tokio::time::timeout(Duration::from_secs(2), sub.next()).await.unwrap().unwrap();
+        assert_eq!(head["number"], 7);
+
+        // A clone reuses the same WS connection; calls still go over HTTP.
+        let other = client.clone();
+        let mut sub2 = other.subscribe_topic("pendingTxs").await.unwrap();
+        assert_eq!(sub2.id(), "sub-2");
+        let next = tokio::time::timeout(Duration::from_secs(2), sub2.next()).await.unwrap().unwrap();
+        assert_eq!(next["number"], 8);
+        assert_eq!(ws.hits(), 1);
+        let _: String = other.call("chain.getChainId", json!([])).await.unwrap();
+        assert_eq!(http.hits(), 2);
+    }
+}
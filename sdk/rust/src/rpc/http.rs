@@ -2,9 +2,16 @@
 //!
 //! Features:
 //! - Async `reqwest` client with sane defaults (timeouts, UA).
-//! - Exponential backoff with jitter for transient failures (5xx/429/timeouts).
+//! - Exponential backoff with jitter for transient failures (5xx/429/timeouts),
+//!   with an optional custom retry predicate and total retry-time budget.
 //! - Typed single-call API and convenient raw/batch helpers.
+//! - Per-call opt-out of retries for non-idempotent methods (`call_no_retry`).
+//! - Per-call timeout override for slow methods (`call_with_timeout`).
+//! - Optional client-side throttling: a cap on in-flight requests
+//!   (`max_in_flight`) and a token-bucket `rate_limit`; saturated calls wait.
 //! - Optional bearer auth & custom headers.
+//! - Optional gzip/deflate response decoding (`compression`, feature `compression`).
+//! - Optional `on_event` hook observing every call and attempt (metrics/logging).
 //!
 //! This client is transport-only. It does not interpret chain semantics.
 
@@ -12,8 +19,33 @@ use crate::error::{Error, Result};
 use reqwest::{header, Client, StatusCode, Url};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Custom retry decision: `(error, attempt)` → retry? `attempt` is the
+/// 0-based index of the attempt that just failed. See
+/// [`HttpClientBuilder::retry_policy`].
+pub type RetryPredicate = Arc<dyn Fn(&Error, u32) -> bool + Send + Sync>;
+
+/// Lifecycle of one [`HttpClient`] call (single or batch), passed to the
+/// [`HttpClientBuilder::on_event`] hook. Batches report `method: "batch"` and
+/// the id of their first request.
+#[derive(Debug, Clone, Copy)]
+pub enum RpcEvent<'a> {
+    /// The call was issued (before any attempt or limiter wait).
+    RequestStart { method: &'a str, id: u64 },
+    /// An HTTP attempt is about to be sent; `attempt` is 0 for the first try.
+    Attempt { method: &'a str, attempt: u32 },
+    /// The call finished after `attempts` HTTP attempts (1 without retries);
+    /// `elapsed` includes backoff.
+    RequestEnd { method: &'a str, id: u64, attempts: u32, elapsed: Duration, outcome: Result<(), &'a Error> },
+}
+
+/// Observer for [`RpcEvent`]s; runs inline, so keep it cheap.
+pub type RpcHook = Arc<dyn Fn(RpcEvent<'_>) + Send + Sync>;
 
 /// JSON-RPC 2.0 request envelope.
 #[derive(Debug, Serialize)]
@@ -45,7 +77,7 @@ struct RpcErrorObj {
 }
 
 /// Builder for [`HttpClient`].
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct HttpClientBuilder {
     endpoint: Url,
     timeout: Duration,
@@ -54,6 +86,25 @@ pub struct HttpClientBuilder {
     retry_base: Duration,
     default_headers: header::HeaderMap,
     user_agent: Option<String>,
+    max_in_flight: Option<usize>,
+    rate_limit: Option<u32>,
+    retry_policy: Option<RetryPredicate>,
+    retry_total_budget: Option<Duration>,
+    on_event: Option<RpcHook>,
+    compression: bool,
+}
+
+impl std::fmt::Debug for HttpClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClientBuilder")
+            .field("endpoint", &self.endpoint)
+            .field("timeout", &self.timeout)
+            .field("max_retries", &self.max_retries)
+            .field("custom_retry_policy", &self.retry_policy.is_some())
+            .field("retry_total_budget", &self.retry_total_budget)
+            .field("compression", &self.compression)
+            .finish_non_exhaustive()
+    }
 }
 
 impl HttpClientBuilder {
@@ -66,6 +117,12 @@ impl HttpClientBuilder {
             retry_base: Duration::from_millis(250),
             default_headers: header::HeaderMap::new(),
             user_agent: None,
+            max_in_flight: None,
+            rate_limit: None,
+            retry_policy: None,
+            retry_total_budget: None,
+            on_event: None,
+            compression: false,
         }
     }
 
@@ -110,6 +167,51 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Replace the built-in retry heuristic (transport failures, 408/425/429/5xx,
+    /// and server-busy RPC codes -32000..=-32002, which remains the default).
+    ///
+    /// The predicate is consulted after each failed attempt; retries still stop
+    /// at `max_retries` and at `retry_total_budget`, whichever comes first.
+    pub fn retry_policy(mut self, f: RetryPredicate) -> Self {
+        self.retry_policy = Some(f);
+        self
+    }
+
+    /// Cap the total time a call may spend across attempts and backoff; no
+    /// retry is started whose backoff would overrun it. Unlimited by default.
+    pub fn retry_total_budget(mut self, budget: Duration) -> Self {
+        self.retry_total_budget = Some(budget);
+        self
+    }
+
+    /// Call `hook` with an [`RpcEvent`] at the start and end of every call and
+    /// before each attempt. Without a hook no events are built.
+    pub fn on_event(mut self, hook: RpcHook) -> Self {
+        self.on_event = Some(hook);
+        self
+    }
+
+    /// Advertise `Accept-Encoding: gzip, deflate` and transparently decode
+    /// compressed responses. Off by default so bodies are read byte-for-byte.
+    /// Enabling it needs the `compression` cargo feature; otherwise `build`
+    /// fails with `Error::FeatureUnavailable`.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Allow at most `n` requests on the wire at once (shared by clones).
+    pub fn max_in_flight(mut self, n: usize) -> Self {
+        self.max_in_flight = Some(n.max(1));
+        self
+    }
+
+    /// Start at most `per_sec` requests per second, with bursts up to `per_sec`.
+    pub fn rate_limit(mut self, per_sec: u32) -> Self {
+        self.rate_limit = Some(per_sec.max(1));
+        self
+    }
+
     pub fn build(self) -> Result<HttpClient> {
         let mut headers = self.default_headers.clone();
         headers.entry(header::CONTENT_TYPE).or_insert(header::HeaderValue::from_static("application/json"));
@@ -128,16 +230,32 @@ impl HttpClientBuilder {
             .connect_timeout(self.connect_timeout)
             .timeout(self.timeout)
             .pool_idle_timeout(Duration::from_secs(30))
-            .tcp_nodelay(true)
+            .tcp_nodelay(true);
+        // reqwest turns decoding on whenever its gzip/deflate features are
+        // compiled in, so set it explicitly either way.
+        #[cfg(feature = "compression")]
+        let client = client.gzip(self.compression).deflate(self.compression);
+        #[cfg(not(feature = "compression"))]
+        if self.compression {
+            return Err(Error::FeatureUnavailable);
+        }
+        let client = client
             .build()
             .map_err(|e| Error::Transport(format!("reqwest build: {e}")))?;
 
         Ok(HttpClient {
             endpoint: self.endpoint,
             client,
+            timeout: self.timeout,
             max_retries: self.max_retries,
             retry_base: self.retry_base,
             id: AtomicU64::new(1),
+            permits: self.max_in_flight.map(|n| Arc::new(Semaphore::new(n))),
+            bucket: self.rate_limit.map(|r| Arc::new(TokenBucket::new(r))),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            retry_policy: self.retry_policy,
+            retry_total_budget: self.retry_total_budget,
+            on_event: self.on_event,
         })
     }
 }
@@ -147,9 +265,21 @@ impl HttpClientBuilder {
 pub struct HttpClient {
     endpoint: Url,
     client: Client,
+    /// Default per-request timeout (builder `timeout`).
+    timeout: Duration,
     max_retries: u32,
     retry_base: Duration,
     id: AtomicU64,
+    /// `max_in_flight` limiter.
+    permits: Option<Arc<Semaphore>>,
+    /// `rate_limit` limiter.
+    bucket: Option<Arc<TokenBucket>>,
+    /// Requests currently on the wire (see `in_flight`).
+    in_flight: Arc<AtomicUsize>,
+    /// Overrides `should_retry` when set.
+    retry_policy: Option<RetryPredicate>,
+    retry_total_budget: Option<Duration>,
+    on_event: Option<RpcHook>,
 }
 
 impl std::fmt::Debug for HttpClient {
@@ -172,18 +302,59 @@ impl HttpClient {
         HttpClientBuilder::from_str(endpoint)
     }
 
+    /// Number of requests currently being sent or awaiting their response.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
     /// Perform a typed JSON-RPC call with automatic retries on transient errors.
     pub async fn call<T, P>(&self, method: &str, params: P) -> Result<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        self.call_with_timeout(method, params, self.timeout).await
+    }
+
+    /// Like [`call`](Self::call), but each attempt (including retries) may take
+    /// up to `timeout` instead of the builder's default. Useful for slow
+    /// methods such as block fetches from a cold archive node.
+    pub async fn call_with_timeout<T, P>(&self, method: &str, params: P, timeout: Duration) -> Result<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        let params_value = Some(serde_json::to_value(params).map_err(|e| Error::Serde(format!("params: {e}")))?);
+        self.call_value_with_retries::<T>(method, params_value, self.max_retries, timeout).await
+    }
+
+    /// Perform a typed JSON-RPC call with exactly one attempt, regardless of
+    /// `max_retries`. Use this for non-idempotent methods (e.g. submitting a
+    /// transaction) where a retried request could be applied twice.
+    pub async fn call_no_retry<T, P>(&self, method: &str, params: P) -> Result<T>
     where
         T: DeserializeOwned,
         P: Serialize,
     {
         let params_value = Some(serde_json::to_value(params).map_err(|e| Error::Serde(format!("params: {e}")))?);
-        self.call_value::<T>(method, params_value).await
+        self.call_value_with_retries::<T>(method, params_value, 0, self.timeout).await
     }
 
     /// Same as [`call`] but takes pre-built `serde_json::Value` for params (or `None`).
     pub async fn call_value<T>(&self, method: &str, params: Option<Value>) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.call_value_with_retries::<T>(method, params, self.max_retries, self.timeout).await
+    }
+
+    async fn call_value_with_retries<T>(
+        &self,
+        method: &str,
+        params: Option<Value>,
+        max_retries: u32,
+        timeout: Duration,
+    ) -> Result<T>
     where
         T: DeserializeOwned,
     {
@@ -195,21 +366,7 @@ impl HttpClient {
             params,
         };
         let body = serde_json::to_vec(&req).map_err(|e| Error::Serde(format!("encode request: {e}")))?;
-        // Retry loop
-        let mut last_err: Option<Error> = None;
-        for attempt in 0..=self.max_retries {
-            match self.try_send::<T>(&body).await {
-                Ok(v) => return Ok(v),
-                Err(e) => {
-                    if !self.should_retry(&e) || attempt == self.max_retries {
-                        return Err(e);
-                    }
-                    last_err = Some(e);
-                    self.sleep_backoff(attempt).await;
-                }
-            }
-        }
-        Err(last_err.unwrap_or_else(|| Error::Transport("unreachable retry loop".into())))
+        self.with_retries(method, id, max_retries, || self.try_send::<T>(&body, timeout)).await
     }
 
     /// Perform a **raw** call returning the untyped `serde_json::Value` result.
@@ -218,13 +375,17 @@ impl HttpClient {
     }
 
     /// Execute a JSON-RPC batch. Each item is `(method, params)`.
-    /// Returns a vector of results **ordered by request id**, not input order (JSON-RPC spec).
+    ///
+    /// Results come back in **input order**, matched to requests by id whatever
+    /// order the server replies in. A call the server didn't answer gets an
+    /// `Error::Rpc(-32603, ..)` in its slot.
     pub async fn batch(&self, calls: Vec<(&str, Option<Value>)>) -> Result<Vec<Result<Value>>> {
         if calls.is_empty() {
             return Ok(vec![]);
         }
         // Build batch with strictly increasing ids.
-        let mut next = self.next_id();
+        let first_id = self.next_id();
+        let mut next = first_id;
         let reqs: Vec<RpcRequest<'_>> = calls
             .iter()
             .map(|(m, p)| {
@@ -239,45 +400,123 @@ impl HttpClient {
             })
             .collect();
 
+        let ids: Vec<u64> = reqs.iter().map(|r| r.id).collect();
         let body = serde_json::to_vec(&reqs).map_err(|e| Error::Serde(format!("encode batch: {e}")))?;
+        self.with_retries("batch", first_id, self.max_retries, || self.try_send_batch(&body, &ids)).await
+    }
 
-        // Retry loop
-        let mut last_err: Option<Error> = None;
-        for attempt in 0..=self.max_retries {
-            match self.try_send_batch(&body).await {
-                Ok(v) => return Ok(v),
-                Err(e) => {
-                    if !self.should_retry(&e) || attempt == self.max_retries {
-                        return Err(e);
-                    }
-                    last_err = Some(e);
-                    self.sleep_backoff(attempt).await;
+    // --------------------------- internals ----------------------------------
+
+    /// Run `attempt_fn` until it succeeds or the first of `max_retries`, the
+    /// retry predicate, or the total budget says stop; returns the last error.
+    /// Reports the call's [`RpcEvent`]s.
+    async fn with_retries<T, F, Fut>(&self, method: &str, id: u64, max_retries: u32, attempt_fn: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let start = Instant::now();
+        self.emit(|| RpcEvent::RequestStart { method, id });
+        let (res, attempts) = self.retry_loop(method, max_retries, start, attempt_fn).await;
+        self.emit(|| RpcEvent::RequestEnd {
+            method,
+            id,
+            attempts,
+            elapsed: start.elapsed(),
+            outcome: res.as_ref().map(|_| ()),
+        });
+        res
+    }
+
+    /// The retry loop behind [`Self::with_retries`]; also returns how many
+    /// attempts were made.
+    async fn retry_loop<T, F, Fut>(
+        &self,
+        method: &str,
+        max_retries: u32,
+        start: Instant,
+        mut attempt_fn: F,
+    ) -> (Result<T>, u32)
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            self.emit(|| RpcEvent::Attempt { method, attempt });
+            let e = match attempt_fn().await {
+                Ok(v) => return (Ok(v), attempt + 1),
+                Err(e) => e,
+            };
+            let retry = match &self.retry_policy {
+                Some(policy) => policy(&e, attempt),
+                None => self.should_retry(&e),
+            };
+            if !retry || attempt >= max_retries {
+                return (Err(e), attempt + 1);
+            }
+            let delay = self.backoff_delay(attempt);
+            if let Some(budget) = self.retry_total_budget {
+                if start.elapsed() + delay > budget {
+                    return (Err(e), attempt + 1);
                 }
             }
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
-        Err(last_err.unwrap_or_else(|| Error::Transport("unreachable retry loop".into())))
     }
 
-    // --------------------------- internals ----------------------------------
+    /// Build and deliver an event only when a hook is installed.
+    fn emit<'a>(&self, event: impl FnOnce() -> RpcEvent<'a>) {
+        if let Some(hook) = &self.on_event {
+            hook(event());
+        }
+    }
 
     fn next_id(&self) -> u64 {
         self.id.fetch_add(1, Ordering::Relaxed)
     }
 
-    async fn try_send<T>(&self, body: &[u8]) -> Result<T>
-    where
-        T: DeserializeOwned,
-    {
-        let resp = self
-            .client
-            .post(self.endpoint.clone())
-            .body(body.to_vec())
-            .send()
-            .await
-            .map_err(|e| Error::Transport(format!("send: {e}")))?;
+    /// POST `body` once, honouring the limiters: waits for a rate token and an
+    /// in-flight permit, and holds the permit until the response body is read.
+    /// With `compression` on, reqwest has already decoded the returned bytes.
+    async fn post(&self, body: &[u8], timeout: Option<Duration>, what: &str) -> Result<(StatusCode, bytes::Bytes)> {
+        if let Some(bucket) = &self.bucket {
+            bucket.acquire().await;
+        }
+        let _permit = match &self.permits {
+            Some(sem) => Some(
+                sem.acquire()
+                    .await
+                    .map_err(|_| Error::Transport("request limiter closed".into()))?,
+            ),
+            None => None,
+        };
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        let _guard = InFlightGuard(&self.in_flight);
 
+        let mut req = self.client.post(self.endpoint.clone()).body(body.to_vec());
+        if let Some(t) = timeout {
+            req = req.timeout(t);
+        }
+        let resp = req.send().await.map_err(|e| {
+            // reqwest's Display for timeouts doesn't always say so; keep it retryable.
+            if e.is_timeout() {
+                Error::Transport(format!("{what}: timed out: {e}"))
+            } else {
+                Error::Transport(format!("{what}: {e}"))
+            }
+        })?;
         let status = resp.status();
         let bytes = resp.bytes().await.map_err(|e| Error::Transport(format!("read body: {e}")))?;
+        Ok((status, bytes))
+    }
+
+    async fn try_send<T>(&self, body: &[u8], timeout: Duration) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let (status, bytes) = self.post(body, Some(timeout), "send").await?;
         if !status.is_success() {
             return Err(http_status_error(status, &bytes));
         }
@@ -297,17 +536,8 @@ impl HttpClient {
             .ok_or_else(|| Error::Rpc(-32603, "missing result and error".into()))
     }
 
-    async fn try_send_batch(&self, body: &[u8]) -> Result<Vec<Result<Value>>> {
-        let resp = self
-            .client
-            .post(self.endpoint.clone())
-            .body(body.to_vec())
-            .send()
-            .await
-            .map_err(|e| Error::Transport(format!("send batch: {e}")))?;
-
-        let status = resp.status();
-        let bytes = resp.bytes().await.map_err(|e| Error::Transport(format!("read body: {e}")))?;
+    async fn try_send_batch(&self, body: &[u8], ids: &[u64]) -> Result<Vec<Result<Value>>> {
+        let (status, bytes) = self.post(body, None, "send batch").await?;
         if !status.is_success() {
             return Err(http_status_error(status, &bytes));
         }
@@ -315,34 +545,22 @@ impl HttpClient {
         let parsed: Vec<RpcResponse<Value>> = serde_json::from_slice(&bytes)
             .map_err(|e| Error::Serde(format!("decode batch response: {e}; body={}", truncate_body(&bytes))))?;
 
-        // Map to ordered results by id ascending (as many servers already do).
-        let mut items: Vec<(u64, Result<Value>)> = Vec::with_capacity(parsed.len());
+        // Key responses by the id they answer (numeric or numeric string); ids we
+        // never sent and repeats of an id are ignored.
+        let mut by_id: HashMap<u64, RpcResponse<Value>> = HashMap::with_capacity(parsed.len());
         for r in parsed {
-            // best-effort id → u64
-            let id_num = r
-                .id
-                .as_u64()
-                .or_else(|| r.id.as_str().and_then(|s| s.parse::<u64>().ok()))
-                .unwrap_or(0);
-
-            let res = if let Some(err) = r.error {
-                Err(Error::Rpc(
-                    err.code,
-                    if let Some(data) = err.data {
-                        format!("{} | data={}", err.message, data)
-                    } else {
-                        err.message
-                    },
-                ))
-            } else if let Some(val) = r.result {
-                Ok(val)
-            } else {
-                Err(Error::Rpc(-32603, "missing result and error".into()))
-            };
-            items.push((id_num, res));
+            let id = r.id.as_u64().or_else(|| r.id.as_str().and_then(|s| s.parse::<u64>().ok()));
+            if let Some(id) = id.filter(|id| ids.contains(id)) {
+                by_id.entry(id).or_insert(r);
+            }
         }
-        items.sort_by_key(|(id, _)| *id);
-        Ok(items.into_iter().map(|(_, r)| r).collect())
+        Ok(ids
+            .iter()
+            .map(|id| match by_id.remove(id) {
+                Some(r) => rpc_result(r),
+                None => Err(Error::Rpc(-32603, format!("no response for batch request id {id}"))),
+            })
+            .collect())
     }
 
     fn should_retry(&self, err: &Error) -> bool {
@@ -366,19 +584,77 @@ impl HttpClient {
         }
     }
 
-    async fn sleep_backoff(&self, attempt: u32) {
+    fn backoff_delay(&self, attempt: u32) -> Duration {
         // attempt = 0 → base, 1 → 2x, etc., capped to 3s
         let base = self.retry_base.as_millis() as u64;
         let pow = 1u64.saturating_shl(attempt.min(6)); // cap growth
         let max_ms = (base.saturating_mul(pow)).min(3_000);
         let jitter = fastrand::u64(0..=max_ms / 2);
-        let dur = Duration::from_millis(max_ms / 2 + jitter);
-        tokio::time::sleep(dur).await
+        Duration::from_millis(max_ms / 2 + jitter)
     }
 }
 
 // --------------------------- helpers -----------------------------------------
 
+/// Decrements the in-flight counter when a request finishes (or is dropped).
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Token bucket refilled continuously at `rate` tokens/s, holding at most `rate`.
+struct TokenBucket {
+    rate: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(per_sec: u32) -> Self {
+        let rate = f64::from(per_sec);
+        Self { rate, state: Mutex::new((rate, Instant::now())) }
+    }
+
+    /// Take one token, sleeping until one is available.
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                let (tokens, last) = &mut *state;
+                let now = Instant::now();
+                *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * self.rate).min(self.rate);
+                *last = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - *tokens) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// `result`, or the JSON-RPC `error` as `Error::Rpc`.
+fn rpc_result(r: RpcResponse<Value>) -> Result<Value> {
+    if let Some(err) = r.error {
+        Err(Error::Rpc(
+            err.code,
+            if let Some(data) = err.data {
+                format!("{} | data={}", err.message, data)
+            } else {
+                err.message
+            },
+        ))
+    } else if let Some(val) = r.result {
+        Ok(val)
+    } else {
+        Err(Error::Rpc(-32603, "missing result and error".into()))
+    }
+}
+
 fn truncate_body(bytes: &[u8]) -> String {
     const LIM: usize = 512;
     let s = String::from_utf8_lossy(bytes);
@@ -404,6 +680,7 @@ fn contains_any(haystack: &str, needles: &[&str]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{serve_http, Response};
 
     #[test]
     fn builder_defaults() {
@@ -427,4 +704,228 @@ mod tests {
         assert!(c.should_retry(&Error::Http(StatusCode::INTERNAL_SERVER_ERROR, "oops".into())));
         assert!(!c.should_retry(&Error::Rpc(-32601, "method not found".into())));
     }
+
+    #[tokio::test]
+    async fn call_no_retry_makes_single_attempt() {
+        let mock = serve_http(|_| Response::status(503));
+        let c = HttpClient::builder(&mock.url)
+            .unwrap()
+            .max_retries(3)
+            .retry_base(Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        let err = c.call_no_retry::<Value, _>("tx.sendRawTransaction", json!(["0x00"])).await.unwrap_err();
+        assert!(matches!(err, Error::Http(StatusCode::SERVICE_UNAVAILABLE, _)));
+        assert_eq!(mock.hits(), 1);
+
+        // The retrying path still retries the same transient failure.
+        let _ = c.call::<Value, _>("chain.getHead", json!([])).await.unwrap_err();
+        assert_eq!(mock.hits(), 1 + 4);
+    }
+
+    #[tokio::test]
+    async fn call_with_timeout_applies_to_every_attempt() {
+        // Answers each request after 300ms.
+        let mock = serve_http(|req| {
+            std::thread::sleep(Duration::from_millis(300));
+            Response::rpc_result(&req.json(), json!("slow"))
+        });
+        let c = HttpClient::builder(&mock.url)
+            .unwrap()
+            .timeout(Duration::from_millis(50))
+            .max_retries(2)
+            .retry_base(Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        // Default timeout: every attempt times out, and each one is retried.
+        let err = c.call::<Value, _>("chain.getBlockByHeight", json!([1])).await.unwrap_err();
+        assert!(matches!(err, Error::Transport(ref m) if m.contains("timed out")), "{err:?}");
+        assert_eq!(mock.hits(), 3);
+
+        // A longer per-call budget lets the same slow method succeed.
+        let v: String = c
+            .call_with_timeout("chain.getBlockByHeight", json!([1]), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(v, "slow");
+    }
+
+    /// Answers after `delay`, tracking the peak number of concurrent requests.
+    fn spawn_slow_server(delay: Duration) -> (String, Arc<AtomicUsize>) {
+        let (live, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let max = peak.clone();
+        let mock = serve_http(move |req| {
+            let now = live.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(delay);
+            live.fetch_sub(1, Ordering::SeqCst);
+            Response::rpc_result(&req.json(), json!(1))
+        });
+        (mock.url, peak)
+    }
+
+    #[tokio::test]
+    async fn max_in_flight_bounds_concurrent_requests() {
+        let (url, peak) = spawn_slow_server(Duration::from_millis(100));
+        let c = Arc::new(HttpClient::builder(&url).unwrap().max_in_flight(2).build().unwrap());
+        assert_eq!(c.in_flight(), 0);
+
+        let calls: Vec<_> = (0..6)
+            .map(|_| {
+                let c = c.clone();
+                tokio::spawn(async move { c.call::<u64, _>("chain.getHead", json!([])).await })
+            })
+            .collect();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(c.in_flight(), 2, "extra calls wait for a permit");
+        for call in calls {
+            assert_eq!(call.await.unwrap().unwrap(), 1);
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(c.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_spaces_out_requests() {
+        let (url, _peak) = spawn_slow_server(Duration::ZERO);
+        let c = HttpClient::builder(&url).unwrap().rate_limit(10).build().unwrap();
+        let start = Instant::now();
+        // A burst of 10 passes at once; the next 5 need ~0.5s of refill.
+        for _ in 0..15 {
+            c.call::<u64, _>("chain.getHead", json!([])).await.unwrap();
+        }
+        let took = start.elapsed();
+        assert!(took >= Duration::from_millis(400), "{took:?}");
+        assert!(took < Duration::from_secs(3), "{took:?}");
+    }
+
+    #[tokio::test]
+    async fn retry_policy_and_total_budget_cut_retries_short() {
+        let mock = serve_http(|_| Response::status(503));
+        let url = &mock.url;
+
+        // Custom predicate: never retry, even though 503 is retryable by default.
+        let c = HttpClient::builder(url)
+            .unwrap()
+            .max_retries(5)
+            .retry_base(Duration::from_millis(1))
+            .retry_policy(Arc::new(|_, _| false))
+            .build()
+            .unwrap();
+        let _ = c.call::<Value, _>("chain.getHead", json!([])).await.unwrap_err();
+        assert_eq!(mock.hits(), 1);
+
+        // Predicate sees the attempt index: allow exactly two retries.
+        let c = HttpClient::builder(url)
+            .unwrap()
+            .max_retries(5)
+            .retry_base(Duration::from_millis(1))
+            .retry_policy(Arc::new(|_, attempt| attempt < 2))
+            .build()
+            .unwrap();
+        let _ = c.call::<Value, _>("chain.getHead", json!([])).await.unwrap_err();
+        assert_eq!(mock.hits(), 1 + 3);
+
+        // Budget: backoff of >=100ms per retry allows no retry within 50ms.
+        let c = HttpClient::builder(url)
+            .unwrap()
+            .max_retries(10)
+            .retry_base(Duration::from_millis(200))
+            .retry_total_budget(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let start = Instant::now();
+        let _ = c.batch(vec![("chain.getHead", None)]).await.unwrap_err();
+        assert_eq!(mock.hits(), 4 + 1);
+        assert!(start.elapsed() < Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn on_event_reports_start_attempts_and_end() {
+        let mock = serve_http(|_| Response::status(503));
+        let log = Arc::new(Mutex::new(Vec::<String>::new()));
+        let sink = log.clone();
+        let c = HttpClient::builder(&mock.url)
+            .unwrap()
+            .max_retries(1)
+            .retry_base(Duration::from_millis(1))
+            .on_event(Arc::new(move |ev| {
+                let line = match ev {
+                    RpcEvent::RequestStart { method, .. } => format!("start {method}"),
+                    RpcEvent::Attempt { method, attempt } => format!("attempt {method} {attempt}"),
+                    RpcEvent::RequestEnd { method, attempts, outcome, .. } => {
+                        format!("end {method} {attempts} {}", if outcome.is_ok() { "ok" } else { "err" })
+                    }
+                };
+                sink.lock().unwrap().push(line);
+            }))
+            .build()
+            .unwrap();
+
+        let _ = c.call::<Value, _>("chain.getHead", json!([])).await.unwrap_err();
+        let _ = c.batch(vec![("chain.getHead", None)]).await.unwrap_err();
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "start chain.getHead",
+                "attempt chain.getHead 0",
+                "attempt chain.getHead 1",
+                "end chain.getHead 2 err",
+                "start batch",
+                "attempt batch 0",
+                "attempt batch 1",
+                "end batch 2 err",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_results_follow_input_order_with_gaps_filled() {
+        // Replies in reverse order, drops the middle call, and fails the last.
+        let mock = serve_http(|req| {
+            let calls = req.json();
+            let replies: Vec<Value> = [&calls[2], &calls[0]]
+                .iter()
+                .map(|c| match c["method"].as_str() {
+                    Some("bad") => json!({"jsonrpc": "2.0", "id": c["id"], "error": {"code": -32601, "message": "nope"}}),
+                    _ => json!({"jsonrpc": "2.0", "id": c["id"], "result": c["method"]}),
+                })
+                .collect();
+            Response::json(&Value::from(replies))
+        });
+        let c = HttpClient::new(&mock.url).unwrap();
+        let out = c
+            .batch(vec![("first", None), ("second", None), ("bad", None)])
+            .await
+            .unwrap();
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0].as_ref().unwrap(), "first");
+        assert!(matches!(&out[1], Err(Error::Rpc(-32603, m)) if m.contains("no response")), "{:?}", out[1]);
+        assert!(matches!(&out[2], Err(Error::Rpc(-32601, _))), "{:?}", out[2]);
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn compression_decodes_gzip_envelopes() {
+        use std::io::Write;
+
+        let mock = serve_http(|req| {
+            let asked = req.header("accept-encoding").is_some_and(|v| v.contains("gzip"));
+            let json = format!(r#"{{"jsonrpc":"2.0","id":1,"result":{asked}}}"#);
+            let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            gz.write_all(json.as_bytes()).unwrap();
+            Response::body(200, gz.finish().unwrap()).header("Content-Encoding", "gzip")
+        });
+        let url = mock.url;
+
+        let c = HttpClient::builder(&url).unwrap().compression(true).build().unwrap();
+        let asked: bool = c.call("chain.getHead", json!([])).await.unwrap();
+        assert!(asked, "client advertises gzip");
+
+        // Disabled: the gzip bytes reach the JSON decoder untouched.
+        let raw = HttpClient::builder(&url).unwrap().max_retries(0).build().unwrap();
+        assert!(matches!(raw.call::<bool, _>("chain.getHead", json!([])).await, Err(Error::Serde(_))));
+    }
 }
@@ -3,23 +3,37 @@
 //! Goals:
 //! - Async, lightweight WS transport for JSON-RPC 2.0.
 //! - Typed `call` and generic `subscribe` helpers (Ethereum-like subscription frames).
+//!   `Subscription` is a `futures::Stream` of JSON values; `subscribe_typed` decodes each frame.
 //! - Safe concurrency: pending requests are matched by `id`; subscriptions by `subscription` id.
 //! - Optional topic subscriptions via `subscribe_topic("newHeads")` using `subscribe`/`unsubscribe`.
+//! - Opt-in replay buffer (`WsClientBuilder::replay_buffer`): notifications that arrive
+//!   between a subscribe ack and its `Subscription` being attached are kept (last N)
+//!   and handed to `next()` first.
+//! - Keepalive pings; a socket whose pongs stop arriving (`pong_timeout`) is treated as dead.
+//! - Opt-in reconnect (`WsClientBuilder::auto_reconnect`): a dropped socket is redialled
+//!   with backoff and every live subscription is re-issued onto its existing `Subscription`.
+//!   Redialling stops after `reconnect_attempts` failures or once `close()` is called.
 //!
 //! This module does **not** implement chain semantics; it only handles transport and routing.
 
 use crate::error::{Error, Result};
-use futures_util::{stream::SplitSink, SinkExt, StreamExt};
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, Stream, StreamExt,
+};
 use http::{HeaderMap, HeaderName, HeaderValue, Request};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
+    marker::PhantomData,
+    pin::Pin,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
-    time::Duration,
+    task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::{
     net::TcpStream,
@@ -36,14 +50,50 @@ use url::Url;
 
 type Ws = WebSocketStream<MaybeTlsStream<TcpStream>>;
 type Writer = SplitSink<Ws, Message>;
+type Reader = SplitStream<Ws>;
+
+/// Callback fired once a dropped connection has been re-established and its
+/// subscriptions re-issued; use it to refetch state missed during the gap.
+pub type ReconnectHook = Arc<dyn Fn() + Send + Sync>;
+
+/// Delay before the first reconnect attempt; doubles per failure up to the max.
+const RECONNECT_BACKOFF_START: Duration = Duration::from_millis(250);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(10);
+/// Most subscription ids holding replay frames at once; the oldest is evicted.
+const REPLAY_IDS_MAX: usize = 64;
+/// Failed redials before giving up and closing every subscription.
+const RECONNECT_ATTEMPTS_DEFAULT: u32 = 20;
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct WsClientBuilder {
     endpoint: Url,
     headers: HeaderMap,
     connect_timeout: Duration,
+    request_timeout: Duration,
     ping_interval: Option<Duration>,
+    pong_timeout: Option<Duration>,
     max_message_size: Option<usize>,
+    replay_buffer: usize,
+    auto_reconnect: bool,
+    reconnect_attempts: Option<u32>,
+    on_reconnected: Option<ReconnectHook>,
+}
+
+impl std::fmt::Debug for WsClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsClientBuilder")
+            .field("endpoint", &self.endpoint)
+            .field("headers", &self.headers)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("request_timeout", &self.request_timeout)
+            .field("ping_interval", &self.ping_interval)
+            .field("pong_timeout", &self.pong_timeout)
+            .field("max_message_size", &self.max_message_size)
+            .field("replay_buffer", &self.replay_buffer)
+            .field("auto_reconnect", &self.auto_reconnect)
+            .field("reconnect_attempts", &self.reconnect_attempts)
+            .finish_non_exhaustive()
+    }
 }
 
 impl WsClientBuilder {
@@ -52,8 +102,14 @@ impl WsClientBuilder {
             endpoint,
             headers: HeaderMap::new(),
             connect_timeout: Duration::from_secs(15),
+            request_timeout: Duration::from_secs(30),
             ping_interval: Some(Duration::from_secs(20)),
+            pong_timeout: None,
             max_message_size: None,
+            replay_buffer: 0,
+            auto_reconnect: false,
+            reconnect_attempts: Some(RECONNECT_ATTEMPTS_DEFAULT),
+            on_reconnected: None,
         }
     }
 
@@ -64,6 +120,11 @@ impl WsClientBuilder {
         Ok(Self::new(url))
     }
 
+    /// The endpoint this builder connects to.
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
     pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
         self.headers.insert(name, value);
         self
@@ -81,16 +142,67 @@ impl WsClientBuilder {
         self
     }
 
+    /// How long a call waits for its reply before failing with
+    /// `Error::Transport("ws call timeout")` (default 30s).
+    pub fn request_timeout(mut self, d: Duration) -> Self {
+        self.request_timeout = d;
+        self
+    }
+
     pub fn ping_interval(mut self, d: Option<Duration>) -> Self {
         self.ping_interval = d;
         self
     }
 
+    /// Declare the socket dead when a ping goes unanswered for `d` (default:
+    /// twice the ping interval). Pending calls then fail and the reader stops (or
+    /// reconnects, see [`Self::auto_reconnect`]). Ignored when pings are off.
+    pub fn pong_timeout(mut self, d: Duration) -> Self {
+        self.pong_timeout = Some(d);
+        self
+    }
+
+    /// Effective pong deadline, if keepalive pings are enabled.
+    fn pong_deadline(&self) -> Option<Duration> {
+        self.ping_interval.map(|every| self.pong_timeout.unwrap_or(every * 2))
+    }
+
     pub fn max_message_size(mut self, bytes: usize) -> Self {
         self.max_message_size = Some(bytes);
         self
     }
 
+    /// Keep up to `n` notifications per subscription id that arrive after the
+    /// subscribe call is acked but before its `Subscription` is attached, and
+    /// replay them from `next()` ahead of live frames. Frames for ids nobody
+    /// subscribed to are dropped. `0` (default) disables the buffer.
+    pub fn replay_buffer(mut self, n: usize) -> Self {
+        self.replay_buffer = n;
+        self
+    }
+
+    /// When the socket drops, redial with exponential backoff and re-issue each
+    /// live subscription's original subscribe call, feeding the existing
+    /// `Subscription`s. Calls in flight at the drop fail with a retryable
+    /// `Error::Transport`. Off by default: a drop closes every subscription.
+    pub fn auto_reconnect(mut self, enabled: bool) -> Self {
+        self.auto_reconnect = enabled;
+        self
+    }
+
+    /// Give up reconnecting after `n` consecutive failed dials (default 20;
+    /// `None` retries until `close()`). Giving up closes every subscription.
+    pub fn reconnect_attempts(mut self, n: Option<u32>) -> Self {
+        self.reconnect_attempts = n;
+        self
+    }
+
+    /// Invoke `hook` after each successful reconnect (see [`Self::auto_reconnect`]).
+    pub fn on_reconnected(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_reconnected = Some(Arc::new(hook));
+        self
+    }
+
     pub async fn build(self) -> Result<WsClient> {
         WsClient::connect_with(self).await
     }
@@ -103,12 +215,45 @@ pub struct WsClient {
 
 struct Inner {
     url: Url,
+    shared: Arc<Shared>,
+    reader_task: JoinHandle<()>,
+    ping_task: Option<JoinHandle<()>>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // The tasks only hold `Shared`; stop them (and any reconnect loop) with the last handle.
+        self.reader_task.abort();
+        if let Some(t) = &self.ping_task {
+            t.abort();
+        }
+    }
+}
+
+/// Connection state shared by the client handles and the background tasks.
+struct Shared {
+    /// Replaced in place on reconnect.
     writer: Mutex<Writer>,
-    pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
-    subs: Mutex<HashMap<String, mpsc::Sender<Value>>>,
+    routes: ReaderCtx,
+    /// Live subscriptions keyed by the id their `Subscription` was handed, so
+    /// they can be re-issued after a reconnect.
+    active: Mutex<HashMap<String, ActiveSub>>,
     next_id: AtomicU64,
-    _reader_task: JoinHandle<()>,
-    _ping_task: Option<JoinHandle<()>>,
+    request_timeout: Duration,
+    /// Set by `close()` (or when reconnecting gives up); stops the reader's
+    /// redial loop and the ping task.
+    closed: AtomicBool,
+    /// When the oldest unanswered ping went out; `None` while no pong is owed.
+    ping_sent_at: std::sync::Mutex<Option<Instant>>,
+    /// Wakes the reader when a ping starts a pong deadline.
+    ping_sent: tokio::sync::Notify,
+}
+
+struct ActiveSub {
+    subscribe_method: String,
+    params: Value,
+    /// Id the node currently sends notifications under (changes on reconnect).
+    server_id: String,
 }
 
 impl std::fmt::Debug for WsClient {
@@ -127,83 +272,51 @@ impl WsClient {
 
     /// Use the builder for custom headers/timeouts/keepalive.
     pub async fn connect_with(builder: WsClientBuilder) -> Result<Self> {
-        // Prepare request with headers.
-        let mut req: Request<()> = builder
-            .endpoint
-            .clone()
-            .into_client_request()
-            .map_err(|e| Error::Transport(format!("ws request: {e}")))?;
-        *req.headers_mut() = builder.headers.clone();
-
-        // Apply a connect timeout.
-        let connect_fut = connect_async(req);
-        let (ws, _resp) = time::timeout(builder.connect_timeout, connect_fut)
-            .await
-            .map_err(|_| Error::Transport("ws connect timeout".into()))?
-            .map_err(|e| Error::Transport(format!("ws connect: {e}")))?;
-
-        let (writer, mut reader) = ws.split();
-
-        let url = builder.endpoint;
-        let writer = Mutex::new(writer);
-        let pending = Mutex::new(HashMap::<u64, oneshot::Sender<Value>>::new());
-        let subs = Mutex::new(HashMap::<String, mpsc::Sender<Value>>::new());
+        let (writer, reader) = dial(&builder).await?.split();
 
-        // Reader task
-        let inner_for_reader = ReaderCtx {
-            pending: pending.clone(),
-            subs: subs.clone(),
-        };
-        let reader_task = tokio::spawn(async move {
-            while let Some(msg) = reader.next().await {
-                match msg {
-                    Ok(Message::Text(t)) => handle_incoming(&inner_for_reader, t.as_bytes()).await,
-                    Ok(Message::Binary(b)) => handle_incoming(&inner_for_reader, &b).await,
-                    Ok(Message::Close(_)) => break,
-                    Ok(Message::Ping(_)) => {
-                        // tungstenite auto replies with Pong; nothing to do.
-                    }
-                    Ok(Message::Pong(_)) => {}
-                    Err(e) => {
-                        // Cannot surface easily; drop all pending with error.
-                        drain_all_with_error(&inner_for_reader, &format!("ws read: {e}")).await;
-                        break;
-                    }
-                    _ => {}
-                }
-            }
-            // drain on EOF
-            drain_all_with_error(&inner_for_reader, "ws closed").await;
+        let shared = Arc::new(Shared {
+            writer: Mutex::new(writer),
+            routes: ReaderCtx::new(builder.replay_buffer),
+            active: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            request_timeout: builder.request_timeout,
+            closed: AtomicBool::new(false),
+            ping_sent_at: std::sync::Mutex::new(None),
+            ping_sent: tokio::sync::Notify::new(),
         });
 
         // Optional ping task
-        let ping_task = if let Some(every) = builder.ping_interval {
-            let writer_for_ping = writer.clone();
-            Some(tokio::spawn(async move {
+        let ping_task = builder.ping_interval.map(|every| {
+            let shared = shared.clone();
+            let keep_going = builder.auto_reconnect;
+            tokio::spawn(async move {
                 loop {
                     time::sleep(every).await;
-                    let mut w = writer_for_ping.lock().await;
-                    if let Err(e) = w.send(Message::Ping(Vec::new())).await {
-                        // Connection likely closed; exit ping loop.
-                        eprintln!("[ws] ping failed: {e}");
+                    if shared.closed.load(Ordering::Relaxed) {
                         break;
                     }
+                    let mut w = shared.writer.lock().await;
+                    match w.send(Message::Ping(Vec::new())).await {
+                        Ok(()) => {
+                            shared.ping_sent_at.lock().unwrap().get_or_insert_with(Instant::now);
+                            shared.ping_sent.notify_one();
+                        }
+                        Err(e) => {
+                            // Connection likely closed; exit unless the reader will redial.
+                            tracing::debug!("ws ping failed: {e}");
+                            if !keep_going {
+                                break;
+                            }
+                        }
+                    }
                 }
-            }))
-        } else {
-            None
-        };
-
-        let inner = Arc::new(Inner {
-            url,
-            writer,
-            pending,
-            subs,
-            next_id: AtomicU64::new(1),
-            _reader_task: reader_task,
-            _ping_task: ping_task,
+            })
         });
 
+        let url = builder.endpoint.clone();
+        let reader_task = tokio::spawn(run_reader(shared.clone(), reader, builder));
+
+        let inner = Arc::new(Inner { url, shared, reader_task, ping_task });
         Ok(Self { inner })
     }
 
@@ -218,10 +331,98 @@ impl WsClient {
         T: DeserializeOwned,
         P: Serialize,
     {
-        let id = self.next_id();
-        let (tx, rx) = oneshot::channel();
-        self.inner.pending.lock().await.insert(id, tx);
+        self.inner.shared.call(method, params).await
+    }
 
+    /// Subscribe via a custom JSON-RPC **subscribe** method and return a stream of items.
+    ///
+    /// - `subscribe_method`: e.g. `"eth_subscribe"` or `"subscribe"`
+    /// - `unsubscribe_method`: e.g. `"eth_unsubscribe"` or `"unsubscribe"`
+    /// - `params`: usually an array (e.g., `["newHeads"]`)
+    pub async fn subscribe_with(
+        &self,
+        subscribe_method: &str,
+        unsubscribe_method: &str,
+        params: Value,
+    ) -> Result<Subscription> {
+        let sub_id: String = self.inner.shared.request(subscribe_method, params.clone(), OnAck::Subscribe).await?;
+        let routes = &self.inner.shared.routes;
+        let stream = attach(&routes.subs, &routes.replay, &sub_id).await;
+        let entry = ActiveSub {
+            subscribe_method: subscribe_method.to_string(),
+            params,
+            server_id: sub_id.clone(),
+        };
+        self.inner.shared.active.lock().await.insert(sub_id.clone(), entry);
+        Ok(Subscription {
+            client: self.clone(),
+            id: sub_id,
+            unsubscribe_method: unsubscribe_method.to_string(),
+            stream,
+        })
+    }
+
+    /// Like [`Self::subscribe_with`], but decode each notification into `T`.
+    /// A frame that fails to decode yields an `Err` item; the stream goes on.
+    pub async fn subscribe_typed<T: DeserializeOwned>(
+        &self,
+        subscribe_method: &str,
+        unsubscribe_method: &str,
+        params: Value,
+    ) -> Result<TypedSubscription<T>> {
+        let inner = self.subscribe_with(subscribe_method, unsubscribe_method, params).await?;
+        Ok(TypedSubscription { inner, _item: PhantomData })
+    }
+
+    /// Convenience: subscribe to a **topic** using `"subscribe"` / `"unsubscribe"` RPC methods,
+    /// passing `["<topic>"]` as params. Matches the Animica node WS hub.
+    pub async fn subscribe_topic(&self, topic: &str) -> Result<Subscription> {
+        self.subscribe_with("subscribe", "unsubscribe", json!([topic]))
+            .await
+    }
+
+    /// Gracefully close the socket.
+    pub async fn close(&self) -> Result<()> {
+        self.inner.shared.closed.store(true, Ordering::Relaxed);
+        let mut w = self.inner.shared.writer.lock().await;
+        w.send(Message::Close(None))
+            .await
+            .map_err(|e| Error::Transport(format!("ws close: {e}")))
+    }
+
+    /// Unsubscribe the subscription handed out as `id`, under whatever id the
+    /// node knows it by after any reconnects. Its route and any replay frames
+    /// go first, so late notifications are dropped rather than buffered.
+    async fn unsubscribe_id(&self, method: &str, id: &str) -> Result<bool> {
+        let shared = &self.inner.shared;
+        let server_id = shared.active.lock().await.remove(id).map_or_else(|| id.to_string(), |a| a.server_id);
+        {
+            let mut subs = shared.routes.subs.lock().await;
+            subs.remove(&server_id);
+            shared.routes.replay.lock().await.remove(&server_id);
+        }
+        self.call(method, json!([server_id])).await
+    }
+}
+
+impl Shared {
+    /// Send one request on the current socket and await its routed reply.
+    async fn call<T, P>(&self, method: &str, params: P) -> Result<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        self.request(method, params, OnAck::Reply).await
+    }
+
+    /// [`Self::call`], telling the reader what else to do with the reply (see
+    /// [`OnAck`]).
+    async fn request<T, P>(&self, method: &str, params: P, ack: OnAck) -> Result<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
         let env = json!({
             "jsonrpc": "2.0",
             "id": id,
@@ -229,17 +430,36 @@ impl WsClient {
             "params": serde_json::to_value(params).map_err(|e| Error::Serde(format!("params: {e}")))?
         });
 
+        let (tx, rx) = oneshot::channel();
+        self.routes.pending.lock().await.insert(id, tx);
+        match ack {
+            OnAck::Reply => {}
+            OnAck::Subscribe if self.routes.replay_cap == 0 => {}
+            OnAck::Subscribe => {
+                self.routes.subscribing.lock().await.insert(id);
+            }
+            OnAck::Resubscribe(ch) => {
+                self.routes.resubscribing.lock().await.insert(id, ch);
+            }
+        }
+
         let msg = Message::Text(env.to_string());
         {
-            let mut w = self.inner.writer.lock().await;
+            let mut w = self.writer.lock().await;
             w.send(msg)
                 .await
                 .map_err(|e| Error::Transport(format!("ws send: {e}")))?;
         }
 
-        let val = rx
-            .await
-            .map_err(|_| Error::Transport("ws call canceled".into()))?;
+        let val = match time::timeout(self.request_timeout, rx).await {
+            Ok(reply) => reply.map_err(|_| Error::Transport("ws call canceled".into()))??,
+            Err(_) => {
+                self.routes.pending.lock().await.remove(&id);
+                self.routes.subscribing.lock().await.remove(&id);
+                self.routes.resubscribing.lock().await.remove(&id);
+                return Err(Error::Transport("ws call timeout".into()));
+            }
+        };
         // Handle possible error envelope: {"jsonrpc":"2.0","id":X,"error":{...}}
         if let Some(err) = val.get("error") {
             let code = err.get("code").and_then(|c| c.as_i64()).unwrap_or(-32603);
@@ -264,47 +484,6 @@ impl WsClient {
         Ok(serde_json::from_value::<T>(result)
             .map_err(|e| Error::Serde(format!("decode result: {e}")))?)
     }
-
-    /// Subscribe via a custom JSON-RPC **subscribe** method and return a stream of items.
-    ///
-    /// - `subscribe_method`: e.g. `"eth_subscribe"` or `"subscribe"`
-    /// - `unsubscribe_method`: e.g. `"eth_unsubscribe"` or `"unsubscribe"`
-    /// - `params`: usually an array (e.g., `["newHeads"]`)
-    pub async fn subscribe_with(
-        &self,
-        subscribe_method: &str,
-        unsubscribe_method: &str,
-        params: Value,
-    ) -> Result<Subscription> {
-        let sub_id: String = self.call(subscribe_method, params).await?;
-        let (tx, rx) = mpsc::channel::<Value>(64);
-        self.inner.subs.lock().await.insert(sub_id.clone(), tx);
-        Ok(Subscription {
-            client: self.clone(),
-            id: sub_id,
-            unsubscribe_method: unsubscribe_method.to_string(),
-            rx,
-        })
-    }
-
-    /// Convenience: subscribe to a **topic** using `"subscribe"` / `"unsubscribe"` RPC methods,
-    /// passing `["<topic>"]` as params. Matches the Animica node WS hub.
-    pub async fn subscribe_topic(&self, topic: &str) -> Result<Subscription> {
-        self.subscribe_with("subscribe", "unsubscribe", json!([topic]))
-            .await
-    }
-
-    /// Gracefully close the socket.
-    pub async fn close(&self) -> Result<()> {
-        let mut w = self.inner.writer.lock().await;
-        w.send(Message::Close(None))
-            .await
-            .map_err(|e| Error::Transport(format!("ws close: {e}")))
-    }
-
-    fn next_id(&self) -> u64 {
-        self.inner.next_id.fetch_add(1, Ordering::Relaxed)
-    }
 }
 
 /// A live subscription producing a stream of JSON values.
@@ -313,18 +492,20 @@ pub struct Subscription {
     client: WsClient,
     id: String,
     unsubscribe_method: String,
-    rx: mpsc::Receiver<Value>,
+    stream: SubStream,
 }
 
 impl Subscription {
-    /// Receive the next item (awaits). Returns `None` when the subscription is closed.
+    /// Receive the next item (awaits). Replayed frames (see
+    /// [`WsClientBuilder::replay_buffer`]) come first. Returns `None` when the
+    /// subscription is closed.
     pub async fn next(&mut self) -> Option<Value> {
-        self.rx.recv().await
+        self.stream.next().await
     }
 
     /// Try to receive immediately without waiting.
     pub fn try_next(&mut self) -> Option<Value> {
-        self.rx.try_recv().ok()
+        self.stream.try_next()
     }
 
     /// Unsubscribe explicitly (optional; also happens on drop).
@@ -332,16 +513,25 @@ impl Subscription {
         let id = self.id.clone();
         let method = self.unsubscribe_method.clone();
         // Best-effort RPC call; ignore result errors.
-        let _ = self.client.call::<bool, _>(&method, json!([id])).await;
+        let _ = self.client.unsubscribe_id(&method, &id).await;
         Ok(())
     }
 
-    /// Access the subscription id.
+    /// Access the subscription id (as first assigned; after an auto-reconnect
+    /// the node may route it under a fresh id internally).
     pub fn id(&self) -> &str {
         &self.id
     }
 }
 
+impl Stream for Subscription {
+    type Item = Value;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Value>> {
+        self.stream.poll_next(cx)
+    }
+}
+
 impl Drop for Subscription {
     fn drop(&mut self) {
         // Fire-and-forget: attempt to send an unsubscribe; no await in Drop.
@@ -349,17 +539,301 @@ impl Drop for Subscription {
         let id = self.id.clone();
         let method = self.unsubscribe_method.clone();
         tokio::spawn(async move {
-            let _ = client.call::<bool, _>(&method, json!([id])).await;
+            let _ = client.unsubscribe_id(&method, &id).await;
+        });
+    }
+}
+
+/// A [`Subscription`] whose frames are decoded into `T`; see
+/// [`WsClient::subscribe_typed`].
+pub struct TypedSubscription<T> {
+    inner: Subscription,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> TypedSubscription<T> {
+    /// Receive and decode the next item; `None` when the subscription is closed.
+    pub async fn next(&mut self) -> Option<Result<T>> {
+        self.inner.next().await.map(decode_item)
+    }
+
+    /// Unsubscribe explicitly (optional; also happens on drop).
+    pub async fn unsubscribe(self) -> Result<()> {
+        self.inner.unsubscribe().await
+    }
+
+    /// Access the subscription id.
+    pub fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    /// The untyped subscription underneath.
+    pub fn into_inner(self) -> Subscription {
+        self.inner
+    }
+}
+
+impl<T: DeserializeOwned> Stream for TypedSubscription<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        Pin::new(&mut self.inner).poll_next(cx).map(|v| v.map(decode_item))
+    }
+}
+
+fn decode_item<T: DeserializeOwned>(v: Value) -> Result<T> {
+    serde_json::from_value(v).map_err(|e| Error::Serde(format!("decode notification: {e}")))
+}
+
+/// Replayed frames followed by the live channel.
+struct SubStream {
+    replay: VecDeque<Value>,
+    rx: mpsc::Receiver<Value>,
+}
+
+impl SubStream {
+    async fn next(&mut self) -> Option<Value> {
+        match self.replay.pop_front() {
+            Some(v) => Some(v),
+            None => self.rx.recv().await,
+        }
+    }
+
+    fn try_next(&mut self) -> Option<Value> {
+        self.replay.pop_front().or_else(|| self.rx.try_recv().ok())
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Value>> {
+        match self.replay.pop_front() {
+            Some(v) => Poll::Ready(Some(v)),
+            None => self.rx.poll_recv(cx),
+        }
+    }
+}
+
+/// Register a live channel for `sub_id`, claiming any frames buffered for it.
+///
+/// Both maps are held together so no frame lands in the ring after it has
+/// been claimed (the reader only buffers ids without a channel).
+async fn attach(
+    subs: &Mutex<HashMap<String, mpsc::Sender<Value>>>,
+    replay: &Mutex<HashMap<String, ReplayRing>>,
+    sub_id: &str,
+) -> SubStream {
+    let (tx, rx) = mpsc::channel::<Value>(64);
+    let mut subs = subs.lock().await;
+    let buffered = replay.lock().await.remove(sub_id).map(|r| r.frames).unwrap_or_default();
+    subs.insert(sub_id.to_string(), tx);
+    SubStream { replay: buffered, rx }
+}
+
+// ------------------------- Connection lifecycle --------------------------------
+
+/// Open the socket described by `builder` (headers + connect timeout).
+async fn dial(builder: &WsClientBuilder) -> Result<Ws> {
+    // Prepare request with headers (added to, not replacing, the handshake ones).
+    let mut req: Request<()> = builder
+        .endpoint
+        .as_str()
+        .into_client_request()
+        .map_err(|e| Error::Transport(format!("ws request: {e}")))?;
+    req.headers_mut().extend(builder.headers.clone());
+
+    // Apply a connect timeout.
+    let connect_fut = connect_async(req);
+    let (ws, _resp) = time::timeout(builder.connect_timeout, connect_fut)
+        .await
+        .map_err(|_| Error::Transport("ws connect timeout".into()))?
+        .map_err(|e| Error::Transport(format!("ws connect: {e}")))?;
+    Ok(ws)
+}
+
+/// Route frames until the socket ends. Pending calls are then failed; with
+/// `auto_reconnect` the socket is redialled and subscriptions re-issued,
+/// otherwise every subscription is closed.
+async fn run_reader(shared: Arc<Shared>, mut reader: Reader, builder: WsClientBuilder) {
+    loop {
+        let reason = read_frames(&shared, &mut reader, builder.pong_deadline()).await;
+        fail_pending(&shared.routes, &reason).await;
+        if !builder.auto_reconnect || shared.closed.load(Ordering::Relaxed) {
+            close_subscriptions(&shared.routes).await;
+            return;
+        }
+
+        let Some(ws) = redial(&builder, &shared.closed).await else {
+            shared.closed.store(true, Ordering::Relaxed);
+            close_subscriptions(&shared.routes).await;
+            return;
+        };
+        let (writer, next) = ws.split();
+        *shared.writer.lock().await = writer;
+        *shared.ping_sent_at.lock().unwrap() = None;
+        reader = next;
+        // Resubscribing needs replies, which only this task can route; run it alongside.
+        tokio::spawn(resubscribe(shared.clone(), builder.on_reconnected.clone()));
+    }
+}
+
+/// Dispatch incoming frames; returns why the stream ended. With a
+/// `pong_timeout`, a socket that leaves a ping unanswered that long counts as
+/// ended; the clock only runs while a pong is owed.
+async fn read_frames(shared: &Shared, reader: &mut Reader, pong_timeout: Option<Duration>) -> String {
+    let ctx = &shared.routes;
+    loop {
+        let sent_at = *shared.ping_sent_at.lock().unwrap();
+        let deadline = pong_timeout.zip(sent_at).map(|(t, sent)| sent + t);
+        let msg = tokio::select! {
+            msg = reader.next() => msg,
+            _ = shared.ping_sent.notified() => continue,
+            _ = sleep_until(deadline) => return "ws pong timeout".into(),
+        };
+        match msg {
+            Some(Ok(Message::Text(t))) => handle_incoming(ctx, t.as_bytes()).await,
+            Some(Ok(Message::Binary(b))) => handle_incoming(ctx, &b).await,
+            Some(Ok(Message::Close(_))) | None => return "ws closed".into(),
+            Some(Ok(Message::Ping(_))) => {
+                // tungstenite auto replies with Pong; nothing to do.
+            }
+            Some(Ok(Message::Pong(_))) => *shared.ping_sent_at.lock().unwrap() = None,
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return format!("ws read: {e}"),
+        }
+    }
+}
+
+/// Sleep until `deadline`, or forever without one.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(at) => time::sleep_until(at.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Dial until it succeeds, backing off exponentially between attempts.
+/// `None` once `closed` is set or `reconnect_attempts` dials have failed.
+async fn redial(builder: &WsClientBuilder, closed: &AtomicBool) -> Option<Ws> {
+    let mut delay = RECONNECT_BACKOFF_START;
+    let mut failures = 0u32;
+    loop {
+        time::sleep(delay).await;
+        if closed.load(Ordering::Relaxed) {
+            return None;
+        }
+        match dial(builder).await {
+            Ok(ws) => return Some(ws),
+            Err(e) => {
+                failures += 1;
+                tracing::warn!(endpoint = %builder.endpoint, failures, "ws reconnect failed: {e}");
+                if builder.reconnect_attempts.is_some_and(|max| failures >= max) {
+                    return None;
+                }
+                delay = (delay * 2).min(RECONNECT_BACKOFF_MAX);
+            }
+        }
+    }
+}
+
+/// Re-issue every active subscription on the fresh socket. The reader files
+/// each channel under its new server id as the ack arrives, so notifications
+/// sent right behind the ack are delivered. Subscriptions the node now refuses
+/// are closed.
+async fn resubscribe(shared: Arc<Shared>, hook: Option<ReconnectHook>) {
+    // Unroute every channel first: the new socket may hand out ids the old one used.
+    let mut parked = Vec::new();
+    {
+        let mut active = shared.active.lock().await;
+        let mut routes = shared.routes.subs.lock().await;
+        active.retain(|id, a| match routes.remove(&a.server_id) {
+            Some(tx) => {
+                parked.push((id.clone(), a.subscribe_method.clone(), a.params.clone(), tx));
+                true
+            }
+            None => false,
         });
     }
+    for (id, method, params, tx) in parked {
+        let fresh = shared.request::<String, _>(&method, params, OnAck::Resubscribe(tx)).await;
+        let mut active = shared.active.lock().await;
+        match (fresh, active.get_mut(&id)) {
+            (Ok(server_id), Some(entry)) => entry.server_id = server_id,
+            // Unsubscribed meanwhile: drop the route the reader just filed.
+            (Ok(server_id), None) => {
+                shared.routes.subs.lock().await.remove(&server_id);
+            }
+            // The reader dropped the channel, which ends the consumer's stream.
+            (Err(_), _) => {
+                active.remove(&id);
+            }
+        }
+    }
+    if let Some(hook) = hook {
+        hook();
+    }
 }
 
 // --------------------------- Reader routing ----------------------------------
 
-#[derive(Clone)]
+/// What the reader does with a call's reply besides resolving the caller.
+enum OnAck {
+    /// Nothing.
+    Reply,
+    /// A new subscription: open a replay ring for the acked id (when replay is
+    /// enabled) that `attach` then claims.
+    Subscribe,
+    /// A subscription re-issued after a reconnect: file this channel under the
+    /// acked id.
+    Resubscribe(mpsc::Sender<Value>),
+}
+
+/// Notifications held for an acked subscription id until it is attached.
+struct ReplayRing {
+    /// Opening order, for evicting the oldest ring.
+    seq: u64,
+    frames: VecDeque<Value>,
+}
+
 struct ReaderCtx {
-    pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
+    pending: Mutex<HashMap<u64, oneshot::Sender<Result<Value>>>>,
     subs: Mutex<HashMap<String, mpsc::Sender<Value>>>,
+    /// Last `replay_cap` notifications per acked, not-yet-attached
+    /// subscription id (at most [`REPLAY_IDS_MAX`] ids).
+    replay: Mutex<HashMap<String, ReplayRing>>,
+    replay_cap: usize,
+    replay_seq: AtomicU64,
+    /// Request ids of subscribe calls whose ack opens a replay ring.
+    subscribing: Mutex<HashSet<u64>>,
+    /// Channels of subscriptions being re-issued after a reconnect, keyed by
+    /// the subscribe call's request id. The reader files each under the id its
+    /// ack carries before routing the next frame.
+    resubscribing: Mutex<HashMap<u64, mpsc::Sender<Value>>>,
+}
+
+impl ReaderCtx {
+    fn new(replay_cap: usize) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            subs: Mutex::new(HashMap::new()),
+            replay: Mutex::new(HashMap::new()),
+            replay_cap,
+            replay_seq: AtomicU64::new(0),
+            subscribing: Mutex::new(HashSet::new()),
+            resubscribing: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start buffering notifications for `sub_id`, evicting the oldest ring
+    /// when [`REPLAY_IDS_MAX`] ids are already held (e.g. subscribers that
+    /// were cancelled before attaching).
+    async fn open_replay(&self, sub_id: &str) {
+        let mut replay = self.replay.lock().await;
+        if replay.len() >= REPLAY_IDS_MAX && !replay.contains_key(sub_id) {
+            if let Some(oldest) = replay.iter().min_by_key(|(_, r)| r.seq).map(|(id, _)| id.clone()) {
+                replay.remove(&oldest);
+            }
+        }
+        let seq = self.replay_seq.fetch_add(1, Ordering::Relaxed);
+        replay.insert(sub_id.to_string(), ReplayRing { seq, frames: VecDeque::new() });
+    }
 }
 
 async fn handle_incoming(ctx: &ReaderCtx, bytes: &[u8]) {
@@ -368,16 +842,33 @@ async fn handle_incoming(ctx: &ReaderCtx, bytes: &[u8]) {
         Err(_) => return, // ignore non-JSON frames
     };
 
-    // If it's a response with "id", route to pending.
-    if v.get("id").is_some() && (v.get("result").is_some() || v.get("error").is_some()) {
-        let id_num = v
-            .get("id")
-            .and_then(|id| id.as_u64().or_else(|| id.as_str().and_then(|s| s.parse::<u64>().ok())))
-            .unwrap_or(0);
-        if let Some(tx) = ctx.pending.lock().await.remove(&id_num) {
-            let _ = tx.send(v);
+    // Any frame carrying the id of a pending call resolves that caller, even if
+    // it isn't a well-formed response; otherwise the caller would hang until
+    // its timeout.
+    let id_num = v
+        .get("id")
+        .and_then(|id| id.as_u64().or_else(|| id.as_str().and_then(|s| s.parse::<u64>().ok())));
+    if let Some(id_num) = id_num {
+        let caller = ctx.pending.lock().await.remove(&id_num);
+        if let Some(tx) = caller {
+            let v = normalize_response(v);
+            if ctx.subscribing.lock().await.remove(&id_num) {
+                if let Some(sub_id) = v.get("result").and_then(|r| r.as_str()) {
+                    ctx.open_replay(sub_id).await;
+                }
+            }
+            if let Some(ch) = ctx.resubscribing.lock().await.remove(&id_num) {
+                // A refused (or malformed) ack drops the channel.
+                if let Some(server_id) = v.get("result").and_then(|r| r.as_str()) {
+                    ctx.subs.lock().await.insert(server_id.to_string(), ch);
+                }
+            }
+            let _ = tx.send(Ok(v));
+            return;
         }
-        return;
+    }
+    if v.get("id").is_some() && (v.get("result").is_some() || v.get("error").is_some()) {
+        return; // response for an unknown/expired id
     }
 
     // If it's a subscription notification:
@@ -386,9 +877,7 @@ async fn handle_incoming(ctx: &ReaderCtx, bytes: &[u8]) {
         if let Some(params) = v.get("params").and_then(|p| p.as_object()) {
             if let (Some(sub), Some(result)) = (params.get("subscription"), params.get("result")) {
                 if let Some(sub_id) = sub.as_str() {
-                    if let Some(tx) = ctx.subs.lock().await.get(sub_id) {
-                        let _ = tx.send(result.clone()).await;
-                    }
+                    route_notification(ctx, sub_id, result.clone()).await;
                 }
             }
         }
@@ -397,25 +886,53 @@ async fn handle_incoming(ctx: &ReaderCtx, bytes: &[u8]) {
 
     // Animica-simple hub style (optional): {"topic":"newHeads","subscription":"<id>","data":{...}}
     if let (Some(sub_id), Some(data)) = (v.get("subscription").and_then(|s| s.as_str()), v.get("data")) {
-        if let Some(tx) = ctx.subs.lock().await.get(sub_id) {
-            let _ = tx.send(data.clone()).await;
-        }
+        route_notification(ctx, sub_id, data.clone()).await;
         return;
     }
 
     // Unknown frame → ignore.
 }
 
-async fn drain_all_with_error(ctx: &ReaderCtx, msg: &str) {
+/// Deliver a notification to its subscription, or keep it in the id's replay
+/// ring if it was acked but is not attached yet. Anything else is dropped.
+async fn route_notification(ctx: &ReaderCtx, sub_id: &str, item: Value) {
+    let subs = ctx.subs.lock().await;
+    if let Some(tx) = subs.get(sub_id) {
+        let _ = tx.send(item).await;
+        return;
+    }
+    let mut replay = ctx.replay.lock().await;
+    let Some(ring) = replay.get_mut(sub_id) else { return };
+    if ring.frames.len() == ctx.replay_cap {
+        ring.frames.pop_front();
+    }
+    ring.frames.push_back(item);
+}
+
+/// Coerce a frame routed to a pending caller into `{"result": ..}` or
+/// `{"error": {"code", "message", ..}}`, wrapping unexpected shapes as an error.
+fn normalize_response(v: Value) -> Value {
+    match v.get("error") {
+        Some(err) if err.is_object() => v,
+        Some(err) => json!({"error": {"code": -32603, "message": "malformed error frame", "data": err}}),
+        None if v.get("result").is_some() => v,
+        None => json!({"error": {"code": -32603, "message": "malformed response frame", "data": v}}),
+    }
+}
+
+/// Fail every in-flight call with a (retryable) transport error.
+async fn fail_pending(ctx: &ReaderCtx, msg: &str) {
     let mut pending = ctx.pending.lock().await;
     for (_id, tx) in pending.drain() {
-        let _ = tx.send(json!({"error": {"code": -32000, "message": msg}}));
-    }
-    // Close all subscriptions.
-    let mut subs = ctx.subs.lock().await;
-    for (_id, tx) in subs.drain() {
-        let _ = tx.closed().await;
+        let _ = tx.send(Err(Error::Transport(msg.to_string())));
     }
+    ctx.subscribing.lock().await.clear();
+    ctx.resubscribing.lock().await.clear();
+}
+
+/// Close all subscriptions: dropping the senders ends each `Subscription`.
+async fn close_subscriptions(ctx: &ReaderCtx) {
+    ctx.subs.lock().await.clear();
 }
 
 // --------------------------------- tests -------------------------------------
@@ -423,11 +940,352 @@ async fn drain_all_with_error(ctx: &ReaderCtx, msg: &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{serve_ws, serve_ws_once};
 
     #[test]
     fn builder_defaults() {
         let b = WsClientBuilder::from_str("ws://localhost:8546").unwrap();
         assert_eq!(b.connect_timeout, Duration::from_secs(15));
+        assert_eq!(b.request_timeout, Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn malformed_frame_with_pending_id_resolves_caller() {
+        let ctx = ReaderCtx::new(0);
+        let (tx, rx) = oneshot::channel();
+        ctx.pending.lock().await.insert(7, tx);
+
+        // id-only frame: neither `result` nor `error`.
+        handle_incoming(&ctx, br#"{"jsonrpc":"2.0","id":"7"}"#).await;
+
+        let v = time::timeout(Duration::from_secs(1), rx)
+            .await
+            .expect("caller must not hang")
+            .unwrap()
+            .unwrap();
+        assert_eq!(v["error"]["code"], -32603);
+        assert_eq!(v["error"]["message"], "malformed response frame");
+        assert!(ctx.pending.lock().await.is_empty());
+
+        // Non-object error payloads are wrapped too.
+        let wrapped = normalize_response(json!({"id": 8, "error": "boom"}));
+        assert_eq!(wrapped["error"]["data"], "boom");
+    }
+
+    #[tokio::test]
+    async fn replay_buffer_yields_early_frames_before_live_ones() {
+        let ctx = ReaderCtx::new(2);
+        let note = |sub: &str, n: u64| {
+            json!({"jsonrpc":"2.0","method":"subscription","params":{"subscription":sub,"result":{"n":n}}})
+                .to_string()
+        };
+
+        // Frames for an id no subscribe call was acked with are dropped.
+        handle_incoming(&ctx, note("s1", 0).as_bytes()).await;
+        assert!(ctx.replay.lock().await.is_empty());
+
+        // The subscribe ack opens the ring; of three frames before anyone
+        // attaches, only the last two are kept.
+        let (tx, _reply) = oneshot::channel();
+        ctx.pending.lock().await.insert(1, tx);
+        ctx.subscribing.lock().await.insert(1);
+        handle_incoming(&ctx, br#"{"jsonrpc":"2.0","id":1,"result":"s1"}"#).await;
+        for n in 1..=3 {
+            handle_incoming(&ctx, note("s1", n).as_bytes()).await;
+        }
+        let mut stream = attach(&ctx.subs, &ctx.replay, "s1").await;
+        handle_incoming(&ctx, note("s1", 4).as_bytes()).await;
+        handle_incoming(&ctx, br#"{"subscription":"s1","data":{"n":5}}"#).await;
+
+        let mut got = Vec::new();
+        while let Some(v) = stream.try_next() {
+            got.push(v["n"].as_u64().unwrap());
+        }
+        assert_eq!(got, vec![2, 3, 4, 5]);
+        assert!(ctx.replay.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn replay_rings_are_capped_by_id_count() {
+        let ctx = ReaderCtx::new(4);
+        for i in 0..REPLAY_IDS_MAX + 3 {
+            let id = format!("s{i}");
+            ctx.open_replay(&id).await;
+            let frame = json!({"subscription": id, "data": {"n": i}}).to_string();
+            handle_incoming(&ctx, frame.as_bytes()).await;
+        }
+        let replay = ctx.replay.lock().await;
+        assert_eq!(replay.len(), REPLAY_IDS_MAX);
+        assert!(!replay.contains_key("s0") && !replay.contains_key("s2"), "oldest rings evicted");
+        assert_eq!(replay[&format!("s{}", REPLAY_IDS_MAX + 2)].frames.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_drops_the_route_and_stops_buffering() {
+        // Acks subscribe with "sub-1" and pushes a frame right after every reply.
+        let mock = serve_ws(|_| {
+            |req: &Value| {
+                let result = if req["method"] == "subscribe" { json!("sub-1") } else { json!(true) };
+                Some(vec![
+                    json!({"jsonrpc": "2.0", "id": req["id"], "result": result}),
+                    json!({"subscription": "sub-1", "data": {"after": req["method"]}}),
+                ])
+            }
+        })
+        .await;
+        let client = WsClientBuilder::from_str(&mock.url).unwrap().replay_buffer(8).build().await.unwrap();
+        let mut sub = client.subscribe_topic("newHeads").await.unwrap();
+        let first = time::timeout(Duration::from_secs(2), sub.next()).await.unwrap().unwrap();
+        assert_eq!(first["after"], "subscribe");
+
+        sub.unsubscribe().await.unwrap();
+        // The frame pushed after the unsubscribe reply has nowhere to go.
+        time::sleep(Duration::from_millis(50)).await;
+        let routes = &client.inner.shared.routes;
+        assert!(routes.subs.lock().await.is_empty());
+        assert!(routes.replay.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resubscribe_ack_reroutes_before_the_next_frame() {
+        let ctx = ReaderCtx::new(0);
+        let mut calls = Vec::new();
+        let mut streams = Vec::new();
+        for id in [5, 6] {
+            let (tx, rx) = oneshot::channel();
+            let (ch, sub_rx) = mpsc::channel(4);
+            ctx.pending.lock().await.insert(id, tx);
+            ctx.resubscribing.lock().await.insert(id, ch);
+            calls.push(rx);
+            streams.push(sub_rx);
+        }
+
+        // The ack and a notification under the new id arrive back to back,
+        // before the resubscribing task has even seen the reply.
+        handle_incoming(&ctx, br#"{"jsonrpc":"2.0","id":5,"result":"s9"}"#).await;
+        handle_incoming(&ctx, br#"{"subscription":"s9","data":{"n":1}}"#).await;
+        assert_eq!(streams[0].try_recv().unwrap()["n"], 1);
+
+        // A refused resubscribe drops the channel, ending that stream.
+        handle_incoming(&ctx, br#"{"jsonrpc":"2.0","id":6,"error":{"code":-1,"message":"gone"}}"#).await;
+        assert!(streams[1].recv().await.is_none());
+        assert!(ctx.resubscribing.lock().await.is_empty());
+        for rx in calls {
+            assert!(rx.await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn auto_reconnect_replays_subscriptions_and_fails_pending_calls() {
+        use std::sync::atomic::AtomicUsize;
+
+        // Connection N acks `subscribe` with "sub-N"; on the second connection
+        // the ack is followed by a notification. `drop` closes the socket unanswered.
+        let mock = serve_ws(|conn| {
+            move |req: &Value| {
+                if req["method"] == "drop" {
+                    return None;
+                }
+                let sub = format!("sub-{conn}");
+                let mut out = vec![json!({"jsonrpc": "2.0", "id": req["id"], "result": sub})];
+                if conn == 2 && req["method"] == "subscribe" {
+                    out.push(json!({"subscription": sub, "data": {"n": 2}}));
+                }
+                Some(out)
+            }
+        })
+        .await;
+
+        let reconnects = Arc::new(AtomicUsize::new(0));
+        let counter = reconnects.clone();
+        let client = WsClientBuilder::from_str(&mock.url)
+            .unwrap()
+            .ping_interval(None)
+            .auto_reconnect(true)
+            .on_reconnected(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .build()
+            .await
+            .unwrap();
+        let mut sub = client.subscribe_topic("newHeads").await.unwrap();
+
+        let lost = client.call_raw("drop", None).await;
+        assert!(matches!(lost, Err(Error::Transport(_))), "in-flight call fails retryably");
+
+        let item = time::timeout(Duration::from_secs(5), sub.next()).await.unwrap().unwrap();
+        assert_eq!(item["n"], 2);
+        assert_eq!(sub.id(), "sub-1");
+        time::timeout(Duration::from_secs(1), async {
+            while reconnects.load(Ordering::SeqCst) == 0 {
+                time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("reconnected hook fires");
+        let active = client.inner.shared.active.lock().await;
+        assert_eq!(active["sub-1"].server_id, "sub-2");
+    }
+
+    /// Serves one connection (acking every call with "sub-1") until a `drop`
+    /// request, then stops listening so redials are refused.
+    async fn spawn_one_shot_server() -> String {
+        let mock = serve_ws_once(|req: &Value| {
+            (req["method"] != "drop").then(|| vec![json!({"jsonrpc": "2.0", "id": req["id"], "result": "sub-1"})])
+        })
+        .await;
+        mock.url
+    }
+
+    #[tokio::test]
+    async fn reconnect_gives_up_after_attempts_and_close_stops_redial() {
+        // Capped: two refused dials end the subscription and the ping task.
+        let url = spawn_one_shot_server().await;
+        let client = WsClientBuilder::from_str(&url)
+            .unwrap()
+            .ping_interval(Some(Duration::from_millis(50)))
+            .auto_reconnect(true)
+            .reconnect_attempts(Some(2))
+            .build()
+            .await
+            .unwrap();
+        let mut sub = client.subscribe_topic("newHeads").await.unwrap();
+        let _ = client.call_raw("drop", None).await;
+        let ended = time::timeout(Duration::from_secs(3), sub.next()).await.expect("gives up");
+        assert!(ended.is_none());
+        time::timeout(Duration::from_secs(1), async {
+            while !client.inner.ping_task.as_ref().unwrap().is_finished() {
+                time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("ping task exits");
+
+        // Uncapped: only `close()` stops the redial loop.
+        let url = spawn_one_shot_server().await;
+        let client = WsClientBuilder::from_str(&url)
+            .unwrap()
+            .ping_interval(None)
+            .auto_reconnect(true)
+            .reconnect_attempts(None)
+            .build()
+            .await
+            .unwrap();
+        let mut sub = client.subscribe_topic("newHeads").await.unwrap();
+        let _ = client.call_raw("drop", None).await;
+        let _ = client.close().await;
+        let ended = time::timeout(Duration::from_secs(2), sub.next()).await.expect("close stops redial");
+        assert!(ended.is_none());
+        assert!(client.inner.shared.closed.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn pong_timeout_detects_unresponsive_server() {
+        // Completes the handshake, then never reads again, so pings go unanswered.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let _ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let client = WsClientBuilder::from_str(&format!("ws://{addr}"))
+            .unwrap()
+            .ping_interval(Some(Duration::from_millis(50)))
+            .pong_timeout(Duration::from_millis(200))
+            .build()
+            .await
+            .unwrap();
+        let started = Instant::now();
+        let res = time::timeout(Duration::from_secs(2), client.call_raw("chain.getHead", None))
+            .await
+            .expect("dead socket must be detected");
+        let elapsed = started.elapsed();
+        assert!(matches!(res, Err(Error::Transport(ref m)) if m == "ws pong timeout"), "{res:?}");
+        assert!(elapsed >= Duration::from_millis(150) && elapsed < Duration::from_secs(1), "{elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn pong_timeout_shorter_than_ping_interval_keeps_a_live_socket() {
+        // Answers pings (by reading) and every call.
+        let mock = serve_ws(|_| |req: &Value| Some(vec![json!({"jsonrpc": "2.0", "id": req["id"], "result": 1})])).await;
+
+        let client = WsClientBuilder::from_str(&mock.url)
+            .unwrap()
+            .ping_interval(Some(Duration::from_millis(100)))
+            .pong_timeout(Duration::from_millis(30))
+            .build()
+            .await
+            .unwrap();
+        // Several ping rounds, with idle gaps far longer than the pong timeout.
+        time::sleep(Duration::from_millis(450)).await;
+        let res = time::timeout(Duration::from_secs(2), client.call_raw("chain.getHead", None)).await.unwrap();
+        assert_eq!(res.unwrap(), json!(1));
+        assert_eq!(mock.hits(), 1, "no reconnects");
+    }
+
+    #[tokio::test]
+    async fn subscriptions_are_streams_and_typed_decode_errors_dont_end_them() {
+        // Acks any call with "sub-1", then pushes n=1, a bad frame, n=3.
+        let mock = serve_ws(|_| {
+            |req: &Value| {
+                let mut out = vec![json!({"jsonrpc": "2.0", "id": req["id"], "result": "sub-1"})];
+                if req["method"] == "subscribe" {
+                    for data in [json!({"n": 1}), json!("bad"), json!({"n": 3})] {
+                        out.push(json!({"subscription": "sub-1", "data": data}));
+                    }
+                }
+                Some(out)
+            }
+        })
+        .await;
+        let url = mock.url;
+
+        #[derive(serde::Deserialize)]
+        struct Note {
+            n: u64,
+        }
+        // The notes can beat `attach` to the reader; buffer them so none are lost.
+        let connect = || async {
+            WsClientBuilder::from_str(&url).unwrap().replay_buffer(8).build().await.unwrap()
+        };
+        let client = connect().await;
+        let typed = client
+            .subscribe_typed::<Note>("subscribe", "unsubscribe", json!(["newHeads"]))
+            .await
+            .unwrap();
+        let items: Vec<Result<Note>> = time::timeout(Duration::from_secs(2), typed.take(3).collect())
+            .await
+            .unwrap();
+        assert_eq!(items[0].as_ref().unwrap().n, 1);
+        assert!(matches!(items[1], Err(Error::Serde(_))));
+        assert_eq!(items[2].as_ref().unwrap().n, 3);
+
+        let client = connect().await;
+        let sub = client.subscribe_topic("newHeads").await.unwrap();
+        let raw: Vec<Value> = time::timeout(Duration::from_secs(2), sub.map(|v| v["n"].clone()).take(3).collect())
+            .await
+            .unwrap();
+        assert_eq!(raw, vec![json!(1), Value::Null, json!(3)]);
+    }
+
+    #[tokio::test]
+    async fn call_times_out_when_server_never_replies() {
+        // Reads (and so answers pings) but never replies to a request.
+        let mock = serve_ws(|_| |_: &Value| Some(vec![])).await;
+
+        let client = WsClientBuilder::from_str(&mock.url)
+            .unwrap()
+            .request_timeout(Duration::from_millis(100))
+            .build()
+            .await
+            .unwrap();
+        let res = time::timeout(Duration::from_secs(2), client.call_raw("chain.getHead", None))
+            .await
+            .expect("call must not hang");
+        assert!(matches!(res, Err(Error::Transport(ref m)) if m == "ws call timeout"), "{res:?}");
+        assert!(client.inner.shared.routes.pending.lock().await.is_empty());
     }
 
     #[tokio::test]
@@ -0,0 +1,208 @@
+//! Loopback HTTP / WebSocket mock servers shared by the unit tests.
+//!
+//! Every server binds `127.0.0.1:0`, runs in the background for the rest of
+//! the test process, and counts what it served in [`MockServer::hits`]: HTTP
+//! requests for [`serve_http`], accepted connections for [`serve_ws`].
+
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A running mock: its base URL plus the hit counter.
+pub(crate) struct MockServer {
+    pub url: String,
+    hits: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Requests (HTTP) or connections (WS) served so far.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+/// One parsed HTTP request.
+pub(crate) struct Request {
+    pub path: String,
+    headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Header value by case-insensitive name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The body as JSON (`null` if it doesn't parse).
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+}
+
+/// The reply to a [`Request`]; always sent with `Connection: close`.
+pub(crate) struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// `status` with an empty body.
+    pub fn status(status: u16) -> Self {
+        Self { status, headers: Vec::new(), body: Vec::new() }
+    }
+
+    /// `status` with a raw body.
+    pub fn body(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self { body: body.into(), ..Self::status(status) }
+    }
+
+    /// `200` with a JSON body.
+    pub fn json(v: &Value) -> Self {
+        Self::body(200, v.to_string()).header("Content-Type", "application/json")
+    }
+
+    /// `200` with a JSON-RPC `result` for the call `req`.
+    pub fn rpc_result(req: &Value, result: Value) -> Self {
+        Self::json(&serde_json::json!({"jsonrpc": "2.0", "id": req["id"], "result": result}))
+    }
+
+    /// Add a response header.
+    pub fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    fn write_to(&self, stream: &mut impl Write) {
+        let mut head = format!("HTTP/1.1 {} X\r\n", self.status);
+        for (k, v) in &self.headers {
+            head.push_str(&format!("{k}: {v}\r\n"));
+        }
+        head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", self.body.len()));
+        let _ = stream.write_all(head.as_bytes());
+        let _ = stream.write_all(&self.body);
+    }
+}
+
+/// Serve HTTP on a loopback port, answering every request with `route`.
+///
+/// Each connection is handled on its own thread, so a `route` that sleeps
+/// only delays its own reply.
+pub(crate) fn serve_http<F>(route: F) -> MockServer
+where
+    F: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let (counter, route) = (hits.clone(), Arc::new(route));
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let (counter, route) = (counter.clone(), route.clone());
+            std::thread::spawn(move || {
+                let Some(req) = read_request(&mut stream) else { return };
+                counter.fetch_add(1, Ordering::SeqCst);
+                route(&req).write_to(&mut stream);
+            });
+        }
+    });
+    MockServer { url, hits }
+}
+
+fn read_request(stream: &mut impl Read) -> Option<Request> {
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 4096];
+    let body_start = loop {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            return None;
+        }
+        raw.extend_from_slice(&buf[..n]);
+        if let Some(p) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+            break p + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&raw[..body_start]).into_owned();
+    let mut lines = head.lines();
+    let path = lines.next()?.split_whitespace().nth(1)?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect();
+    let mut req = Request { path, headers, body: raw.split_off(body_start) };
+    let want: usize = req.header("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+    while req.body.len() < want {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        req.body.extend_from_slice(&buf[..n]);
+    }
+    Some(req)
+}
+
+/// Serve WebSocket JSON-RPC on a loopback port.
+///
+/// `connect(n)` is called for the `n`-th accepted connection (from 1) and
+/// returns that connection's route. The route sees each text frame parsed as
+/// JSON and returns the frames to send back, or `None` to drop the socket.
+#[cfg(feature = "native")]
+pub(crate) async fn serve_ws<C, R>(connect: C) -> MockServer
+where
+    C: Fn(usize) -> R + Send + Sync + 'static,
+    R: FnMut(&Value) -> Option<Vec<Value>> + Send + 'static,
+{
+    spawn_ws(None, connect).await
+}
+
+/// [`serve_ws`] for a single connection: the listener closes once it is
+/// accepted, so later dials are refused.
+#[cfg(feature = "native")]
+pub(crate) async fn serve_ws_once<R>(route: R) -> MockServer
+where
+    R: FnMut(&Value) -> Option<Vec<Value>> + Send + 'static,
+{
+    let route = std::sync::Mutex::new(Some(route));
+    spawn_ws(Some(1), move |_| route.lock().unwrap().take().unwrap()).await
+}
+
+#[cfg(feature = "native")]
+async fn spawn_ws<C, R>(limit: Option<usize>, connect: C) -> MockServer
+where
+    C: Fn(usize) -> R + Send + Sync + 'static,
+    R: FnMut(&Value) -> Option<Vec<Value>> + Send + 'static,
+{
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::protocol::Message;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    tokio::spawn(async move {
+        while let Ok((tcp, _)) = listener.accept().await {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut route = connect(n);
+            tokio::spawn(async move {
+                let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                while let Some(Ok(msg)) = ws.next().await {
+                    let Message::Text(t) = msg else { continue };
+                    let Some(frames) = route(&serde_json::from_str(&t).unwrap()) else { return };
+                    for frame in frames {
+                        ws.send(Message::Text(frame.to_string())).await.unwrap();
+                    }
+                }
+            });
+            if limit == Some(n) {
+                break;
+            }
+        }
+    });
+    MockServer { url, hits }
+}
@@ -2,11 +2,14 @@
 //!
 //! These models are intentionally conservative and forward-compatible:
 //! - Numeric fields are `u64` where practical.
-//! - Hex/Hash/Bytes surfaces are `String` with `0x`-hex canonicalization left to callers.
+//! - Hex/Hash/Bytes surfaces are `String` with `0x`-hex canonicalization left to callers;
+//!   `*_bytes()` accessors decode them (strict `0x`-hex, exact length for hashes).
 //! - Unknown/extension fields from the node are preserved via `#[serde(flatten)]` where helpful.
 //!
 //! For address manipulation, see `crate::address`.
 
+use crate::error::Error;
+use crate::utils::bytes::hex_decode_strict;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -51,6 +54,15 @@ pub struct Head {
     pub hash: Hash,
     /// Seconds since epoch (node-defined block time).
     pub timestamp: u64,
+    /// Whether this head is final; omitted by node builds without finality tracking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalized: Option<bool>,
+    /// Whether this head is past the node's "safe" (unlikely to reorg) depth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe: Option<bool>,
+    /// Cumulative chain difficulty up to and including this head, if reported.
+    #[serde(default, alias = "totalDifficulty", skip_serializing_if = "Option::is_none")]
+    pub total_difficulty: Option<u64>,
     #[serde(default)]
     #[serde(flatten)]
     pub extra: BTreeMap<String, serde_json::Value>,
@@ -176,11 +188,94 @@ pub struct Block {
     pub extra: BTreeMap<String, serde_json::Value>,
 }
 
+/// Decode a strict `0x`-hex 32-byte hash; `what` names the field in the error.
+fn hash32(s: &str, what: &'static str) -> Result<[u8; 32], Error> {
+    let bytes = hex_decode_strict(s).map_err(|_| Error::InvalidParams(what))?;
+    bytes.try_into().map_err(|_| Error::InvalidParams(what))
+}
+
+/// Decode a strict `0x`-hex byte string; `what` names the field in the error.
+fn hex_bytes(s: &str, what: &'static str) -> Result<Vec<u8>, Error> {
+    hex_decode_strict(s).map_err(|_| Error::InvalidParams(what))
+}
+
 impl Head {
     /// Height convenience alias.
     pub fn height(&self) -> u64 {
         self.number
     }
+
+    /// Finality as reported by the node; `None` when the node doesn't say.
+    pub fn is_finalized(&self) -> Option<bool> {
+        self.finalized
+    }
+
+    /// Decode `hash` into 32 bytes.
+    pub fn hash_bytes(&self) -> Result<[u8; 32], Error> {
+        hash32(&self.hash, "head.hash: expected 0x-hex 32-byte hash")
+    }
+}
+
+impl TxView {
+    /// Decode `hash` into 32 bytes.
+    pub fn hash_bytes(&self) -> Result<[u8; 32], Error> {
+        hash32(&self.hash, "tx.hash: expected 0x-hex 32-byte hash")
+    }
+
+    /// Decode the `data` payload, if present.
+    pub fn data_bytes(&self) -> Result<Option<Vec<u8>>, Error> {
+        self.data.as_deref().map(|d| hex_bytes(d, "tx.data: expected 0x-hex")).transpose()
+    }
+}
+
+impl LogEvent {
+    /// Decode every topic into 32 bytes.
+    pub fn topic_bytes(&self) -> Result<Vec<[u8; 32]>, Error> {
+        self.topics
+            .iter()
+            .map(|t| hash32(t, "log.topics: expected 0x-hex 32-byte topic"))
+            .collect()
+    }
+
+    /// Decode the ABI-encoded `data`.
+    pub fn data_bytes(&self) -> Result<Vec<u8>, Error> {
+        hex_bytes(&self.data, "log.data: expected 0x-hex")
+    }
+}
+
+impl Receipt {
+    /// Decode `tx_hash` into 32 bytes.
+    pub fn tx_hash_bytes(&self) -> Result<[u8; 32], Error> {
+        hash32(&self.tx_hash, "receipt.tx_hash: expected 0x-hex 32-byte hash")
+    }
+
+    /// Decode `block_hash` into 32 bytes, if present.
+    pub fn block_hash_bytes(&self) -> Result<Option<[u8; 32]>, Error> {
+        self.block_hash
+            .as_deref()
+            .map(|h| hash32(h, "receipt.block_hash: expected 0x-hex 32-byte hash"))
+            .transpose()
+    }
+}
+
+impl Header {
+    /// Decode `hash` into 32 bytes.
+    pub fn hash_bytes(&self) -> Result<[u8; 32], Error> {
+        hash32(&self.hash, "header.hash: expected 0x-hex 32-byte hash")
+    }
+
+    /// Decode `parent_hash` into 32 bytes.
+    pub fn parent_hash_bytes(&self) -> Result<[u8; 32], Error> {
+        hash32(&self.parent_hash, "header.parent_hash: expected 0x-hex 32-byte hash")
+    }
+
+    /// Decode `da_root` into 32 bytes, if present.
+    pub fn da_root_bytes(&self) -> Result<Option<[u8; 32]>, Error> {
+        self.da_root
+            .as_deref()
+            .map(|h| hash32(h, "header.da_root: expected 0x-hex 32-byte hash"))
+            .transpose()
+    }
 }
 
 impl Tx {
@@ -199,6 +294,11 @@ impl Tx {
             extra: BTreeMap::new(),
         }
     }
+
+    /// Decode the `data` payload, if present.
+    pub fn data_bytes(&self) -> Result<Option<Vec<u8>>, Error> {
+        self.data.as_deref().map(|d| hex_bytes(d, "tx.data: expected 0x-hex")).transpose()
+    }
 }
 
 #[cfg(test)]
@@ -211,6 +311,9 @@ mod tests {
             number: 10,
             hash: "0x01".into(),
             timestamp: 123,
+            finalized: None,
+            safe: None,
+            total_difficulty: None,
             extra: BTreeMap::new(),
         };
         let j = serde_json::to_string(&h).unwrap();
@@ -218,6 +321,24 @@ mod tests {
         assert_eq!(h2.number, 10);
     }
 
+    #[test]
+    fn head_optional_finality_fields() {
+        let full: Head = serde_json::from_str(
+            r#"{"number":5,"hash":"0x05","timestamp":9,"finalized":true,"safe":true,"totalDifficulty":4096}"#,
+        )
+        .unwrap();
+        assert_eq!(full.is_finalized(), Some(true));
+        assert_eq!(full.safe, Some(true));
+        assert_eq!(full.total_difficulty, Some(4096));
+        assert!(full.extra.is_empty());
+
+        let bare: Head = serde_json::from_str(r#"{"number":6,"hash":"0x06","timestamp":10}"#).unwrap();
+        assert_eq!(bare.is_finalized(), None);
+        assert_eq!((bare.safe, bare.total_difficulty), (None, None));
+        // Absent fields stay absent on re-serialization.
+        assert!(!serde_json::to_string(&bare).unwrap().contains("finalized"));
+    }
+
     #[test]
     fn serde_tx_roundtrip() {
         let tx = Tx {
@@ -251,4 +372,62 @@ mod tests {
         let j = serde_json::to_string(&r).unwrap();
         let _r2: Receipt = serde_json::from_str(&j).unwrap();
     }
+
+    fn receipt_with(tx_hash: &str, block_hash: Option<&str>) -> Receipt {
+        Receipt {
+            tx_hash: tx_hash.into(),
+            status: TxStatus::SUCCESS,
+            gas_used: 0,
+            block_hash: block_hash.map(Into::into),
+            block_number: None,
+            contract_address: None,
+            logs: vec![],
+            extra: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn receipt_hash_bytes_valid() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let r = receipt_with(&hash, Some(&hash.to_uppercase().replacen("0X", "0x", 1)));
+        assert_eq!(r.tx_hash_bytes().unwrap(), [0xab; 32]);
+        assert_eq!(r.block_hash_bytes().unwrap(), Some([0xab; 32]));
+        assert_eq!(receipt_with(&hash, None).block_hash_bytes().unwrap(), None);
+    }
+
+    #[test]
+    fn receipt_hash_bytes_malformed() {
+        let full = "ab".repeat(32);
+        for bad in [
+            full.clone(),                      // missing 0x
+            format!("0x{}", &full[..62]),      // too short
+            format!("0x{full}00"),             // too long
+            format!("0x{}zz", &full[..62]),    // non-hex
+            format!("0x{}", &full[..63]),      // odd length
+        ] {
+            let err = receipt_with(&bad, None).tx_hash_bytes().unwrap_err();
+            assert!(matches!(err, Error::InvalidParams(m) if m.starts_with("receipt.tx_hash")), "{bad}");
+        }
+        let r = receipt_with(&format!("0x{full}"), Some("0xdead"));
+        assert!(r.block_hash_bytes().is_err());
+    }
+
+    #[test]
+    fn log_and_tx_data_bytes() {
+        let log = LogEvent {
+            address: "anim1xyz".into(),
+            topics: vec![format!("0x{}", "11".repeat(32)), "0x1234".into()],
+            data: "0xc0ffee".into(),
+            extra: BTreeMap::new(),
+        };
+        assert_eq!(log.data_bytes().unwrap(), vec![0xc0, 0xff, 0xee]);
+        assert!(log.topic_bytes().is_err());
+
+        let mut tx = Tx::transfer("anim1a".into(), "anim1b".into(), 1, 0, 1, 21_000, 1);
+        assert_eq!(tx.data_bytes().unwrap(), None);
+        tx.data = Some("0x0102".into());
+        assert_eq!(tx.data_bytes().unwrap(), Some(vec![1, 2]));
+        tx.data = Some("0xnope".into());
+        assert!(tx.data_bytes().is_err());
+    }
 }
@@ -0,0 +1,37 @@
+//! Secure randomness for the SDK (keystore salts and nonces, key generation).
+//!
+//! Backed by `rand_core::OsRng` (the platform `getrandom` source, which is
+//! `crypto.getRandomValues` on wasm32), so drawing random bytes does not pull
+//! in `animica_native`. The `deterministic_rng` feature swaps it for a
+//! reproducible SHAKE256 stream in tests; **never** enable it in production.
+
+/// Fill `out` with cryptographically secure random bytes.
+///
+/// Panics if the OS source is unavailable, as `rand::thread_rng` would.
+pub fn random_bytes(out: &mut [u8]) {
+    #[cfg(not(feature = "deterministic_rng"))]
+    {
+        use rand_core::RngCore;
+        rand_core::OsRng.fill_bytes(out);
+    }
+
+    #[cfg(feature = "deterministic_rng")]
+    {
+        use sha3::digest::{ExtendableOutput, Update, XofReader};
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static CALLS: AtomicU64 = AtomicU64::new(0);
+        let n = CALLS.fetch_add(1, Ordering::Relaxed);
+        let mut h = sha3::Shake256::default();
+        h.update(b"animica_sdk deterministic_rng v1");
+        h.update(&n.to_le_bytes());
+        h.finalize_xof().read(out);
+    }
+}
+
+/// Return `N` secure random bytes; see [`random_bytes`].
+#[inline]
+pub fn random_array<const N: usize>() -> [u8; N] {
+    let mut out = [0u8; N];
+    random_bytes(&mut out);
+    out
+}
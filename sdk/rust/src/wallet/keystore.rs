@@ -1,16 +1,21 @@
-//! File-based keystore with password-derived AES-256-GCM encryption.
+//! File-based keystore with password-derived AEAD encryption.
 //!
 //! Design goals:
-//! - Simple, audited primitives (PBKDF2-HMAC-SHA3-256 → 32B key; AES-256-GCM AEAD).
+//! - Simple, audited primitives (PBKDF2-HMAC-SHA3-256 → 32B key by default,
+//!   Argon2id selectable via [`KdfChoice`] with feature `argon2`; AES-256-GCM
+//!   AEAD by default, XChaCha20-Poly1305 selectable via [`Aead`]).
 //! - Self-describing JSON envelope; **no plaintext secrets** on disk.
 //! - Atomic writes (temp file + rename), safe directory permissions hint.
+//! - Envelope metadata is authenticated: tampering with `kdf`/`aead`/`meta`
+//!   makes decryption fail.
 //!
 //! File schema (JSON):
 //! ```jsonc
 //! {
-//!   "version": 1,
+//!   "version": 2,
 //!   "kdf": { "name": "PBKDF2-SHA3-256", "salt": "<b64>", "iterations": 120000 },
-//!   "aead": { "name": "AES-256-GCM", "nonce": "<b64>" },
+//!   // or { "name": "Argon2id", "salt": "<b64>", "iterations": 2, "memory": 19456, "parallelism": 1 }
+//!   "aead": { "name": "AES-256-GCM", "nonce": "<b64>" },   // or "XChaCha20-Poly1305" (24B nonce)
 //!   "meta": { "label": "my-key", "alg_id": 259, "created_at": "2025-09-27T12:34:56Z" },
 //!   "ciphertext": "<b64>"
 //! }
@@ -23,13 +28,37 @@
 //! 6..n:  secret bytes
 //! ```
 //!
+//! Associated data (AAD) for the AEAD is a fixed binary encoding of the header
+//! values: `version`, the `kdf` parameters with the decoded salt, the `aead`
+//! name with the decoded nonce, and `meta`, each length-prefixed in that order
+//! (see `envelope_aad`). It never depends on how the JSON is laid out, and
+//! unknown fields are rejected when parsing, so every header byte that matters
+//! is authenticated. Version 1 files were sealed with empty AAD and are still
+//! readable.
+//!
+//! Bundles (`export_bundle` / `import_bundle`) move several entries between
+//! machines as one file. A bundle is versioned JSON using the same KDF/AEAD:
+//! ```jsonc
+//! {
+//!   "format": "animica-keystore-bundle",
+//!   "version": 1,
+//!   "kdf": { ... }, "aead": { ... },
+//!   "count": 2,
+//!   "ciphertext": "<b64>"   // AEAD over JSON [{ "label", "alg_id", "secret": "<b64>" }, ...]
+//! }
+//! ```
+//! The header (`format`, `version`, `kdf`, `aead`, `count`) is bound as AAD the
+//! same way, under its own domain tag.
+//!
 //! Note: This module ONLY stores opaque secret bytes. Higher-level code decides
 //! whether those bytes are a seed, a private key, etc.
 
 use crate::error::{Error, Result};
+use crate::utils::random::random_bytes;
 use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+use chacha20poly1305::aead::{Aead as _, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
 use pbkdf2::pbkdf2_hmac;
-use rand::RngCore;
 use ring::aead::{self, Aad, BoundKey, LessSafeKey, Nonce, UnboundKey};
 use serde::{Deserialize, Serialize};
 use sha3::Sha3_256;
@@ -40,8 +69,13 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use zeroize::{Zeroize, Zeroizing};
 
 const KDF_NAME: &str = "PBKDF2-SHA3-256";
-const AEAD_NAME: &str = "AES-256-GCM";
-const VERSION: u32 = 1;
+const ARGON2ID_KDF_NAME: &str = "Argon2id";
+const VERSION: u32 = 2;
+/// Legacy envelopes sealed without AAD; accepted by `load` only.
+const VERSION_V1: u32 = 1;
+const BUNDLE_FORMAT: &str = "animica-keystore-bundle";
+const BUNDLE_VERSION: u32 = 1;
+const KDF_ITERATIONS: u32 = 120_000; // ~100-200ms on typical CPUs; tune as needed
 
 /// In-memory keystore handle bound to a directory.
 #[derive(Debug, Clone)]
@@ -57,20 +91,123 @@ pub struct KeystoreEntry {
     pub secret: Vec<u8>,
 }
 
+/// AEAD used to seal a keystore file; recorded in the envelope's `aead.name`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aead {
+    /// AES-256-GCM with a random 96-bit nonce (default).
+    #[default]
+    Aes256Gcm,
+    /// XChaCha20-Poly1305 with a random 192-bit nonce; no practical risk of
+    /// nonce reuse even with many files under one password.
+    XChaCha20Poly1305,
+}
+
+impl Aead {
+    /// Envelope name, e.g. `"AES-256-GCM"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Aead::Aes256Gcm => "AES-256-GCM",
+            Aead::XChaCha20Poly1305 => "XChaCha20-Poly1305",
+        }
+    }
+
+    /// Parse an envelope name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Aead::Aes256Gcm, Aead::XChaCha20Poly1305]
+            .into_iter()
+            .find(|a| a.name() == name)
+    }
+
+    fn nonce_len(self) -> usize {
+        match self {
+            Aead::Aes256Gcm => 12,
+            Aead::XChaCha20Poly1305 => 24,
+        }
+    }
+
+    fn random_nonce(self) -> Vec<u8> {
+        let mut n = vec![0u8; self.nonce_len()];
+        random_bytes(&mut n);
+        n
+    }
+}
+
+/// Password KDF used to seal a keystore file; recorded in the envelope's `kdf`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KdfChoice {
+    /// PBKDF2-HMAC-SHA3-256 at 120k iterations (default; readable everywhere).
+    #[default]
+    Pbkdf2Sha3,
+    /// Argon2id (v1.3), memory-hard. Needs the `argon2` feature to store or load.
+    Argon2id {
+        /// Memory cost in KiB.
+        memory: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+}
+
+impl KdfChoice {
+    /// Argon2id with the OWASP-recommended minimum: 19 MiB, 2 passes, 1 lane.
+    pub const fn argon2id() -> Self {
+        KdfChoice::Argon2id { memory: 19 * 1024, iterations: 2, parallelism: 1 }
+    }
+
+    /// The choice recorded in an envelope (PBKDF2 resets to the current iteration count).
+    fn from_params(kdf: &KdfParams) -> Result<Self> {
+        match (kdf.name.as_str(), kdf.memory, kdf.parallelism) {
+            (KDF_NAME, _, _) => Ok(KdfChoice::Pbkdf2Sha3),
+            (ARGON2ID_KDF_NAME, Some(memory), Some(parallelism)) => {
+                Ok(KdfChoice::Argon2id { memory, iterations: kdf.iterations, parallelism })
+            }
+            _ => Err(Error::Serde("unsupported kdf/aead".into())),
+        }
+    }
+
+    fn params(self, salt: &[u8]) -> KdfParams {
+        let salt = B64.encode(salt);
+        match self {
+            KdfChoice::Pbkdf2Sha3 => KdfParams {
+                name: KDF_NAME.to_string(),
+                salt,
+                iterations: KDF_ITERATIONS,
+                memory: None,
+                parallelism: None,
+            },
+            KdfChoice::Argon2id { memory, iterations, parallelism } => KdfParams {
+                name: ARGON2ID_KDF_NAME.to_string(),
+                salt,
+                iterations,
+                memory: Some(memory),
+                parallelism: Some(parallelism),
+            },
+        }
+    }
+}
+
+/// Envelope `kdf` block. The Argon2id-only fields are omitted for PBKDF2 so
+/// those headers (and hence their AAD) stay byte-identical to older files.
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct KdfParams {
     name: String,
     salt: String,
     iterations: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    memory: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parallelism: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct AeadParams {
     name: String,
     nonce: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct Meta {
     label: String,
     alg_id: u16,
@@ -78,6 +215,7 @@ struct Meta {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FileEnvelope {
     version: u32,
     kdf: KdfParams,
@@ -86,6 +224,26 @@ struct FileEnvelope {
     ciphertext: String,
 }
 
+/// An export bundle; everything but `ciphertext` is authenticated.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BundleEnvelope {
+    format: String,
+    version: u32,
+    kdf: KdfParams,
+    aead: AeadParams,
+    count: u32,
+    ciphertext: String,
+}
+
+/// One entry inside the encrypted bundle payload.
+#[derive(Serialize, Deserialize)]
+struct BundleItem {
+    label: String,
+    alg_id: u16,
+    secret: String, // b64
+}
+
 impl Keystore {
     /// Open (or create) a keystore at `dir`.
     pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
@@ -106,6 +264,7 @@ impl Keystore {
     /// Store a secret under `label`, protecting it with `password`.
     ///
     /// If a file already exists for `label`, set `overwrite = true` to replace it.
+    /// Uses the default AEAD (AES-256-GCM); see [`Keystore::store_with_aead`].
     pub fn store(
         &self,
         label: &str,
@@ -113,6 +272,36 @@ impl Keystore {
         secret: &[u8],
         password: &str,
         overwrite: bool,
+    ) -> Result<()> {
+        self.store_with_aead(label, alg_id, secret, password, overwrite, Aead::default())
+    }
+
+    /// Like [`Keystore::store`], sealing with the chosen `aead`. `load` picks the
+    /// algorithm back up from the envelope.
+    pub fn store_with_aead(
+        &self,
+        label: &str,
+        alg_id: u16,
+        secret: &[u8],
+        password: &str,
+        overwrite: bool,
+        aead_alg: Aead,
+    ) -> Result<()> {
+        self.store_with(label, alg_id, secret, password, overwrite, aead_alg, KdfChoice::default())
+    }
+
+    /// Like [`Keystore::store_with_aead`], also choosing the password `kdf`.
+    /// `load` dispatches on the envelope's `kdf.name`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn store_with(
+        &self,
+        label: &str,
+        alg_id: u16,
+        secret: &[u8],
+        password: &str,
+        overwrite: bool,
+        aead_alg: Aead,
+        kdf_choice: KdfChoice,
     ) -> Result<()> {
         validate_label(label)?;
         let path = self.path_for(label);
@@ -120,66 +309,49 @@ impl Keystore {
             return Err(Error::Io("keystore file exists; set overwrite=true".into()));
         }
 
-        // Derive key
-        let mut salt = [0u8; 16];
-        rand::thread_rng().fill_bytes(&mut salt);
-        let iterations = 120_000u32; // ~100-200ms on typical CPUs; tune as needed
-        let key = derive_key(password, &salt, iterations)?;
-
-        // Build plaintext blob: alg_id (u16 BE) | len (u32 BE) | secret
-        let mut pt = Vec::with_capacity(2 + 4 + secret.len());
-        pt.extend_from_slice(&alg_id.to_be_bytes());
-        pt.extend_from_slice(&(secret.len() as u32).to_be_bytes());
-        pt.extend_from_slice(secret);
-
-        // AEAD encrypt with random 96-bit nonce
-        let mut nonce_bytes = [0u8; 12];
-        rand::thread_rng().fill_bytes(&mut nonce_bytes);
-        let sealed = aead_encrypt(&key, &nonce_bytes, &pt)?;
-
-        // Build envelope
-        let env = FileEnvelope {
-            version: VERSION,
-            kdf: KdfParams {
-                name: KDF_NAME.to_string(),
-                salt: B64.encode(salt),
-                iterations,
-            },
-            aead: AeadParams {
-                name: AEAD_NAME.to_string(),
-                nonce: B64.encode(nonce_bytes),
-            },
-            meta: Meta {
-                label: label.to_string(),
-                alg_id,
-                created_at: rfc3339_now(),
-            },
-            ciphertext: B64.encode(sealed),
+        let meta = Meta {
+            label: label.to_string(),
+            alg_id,
+            created_at: rfc3339_now(),
         };
-
-        // Serialize and write atomically
-        let json = serde_json::to_vec_pretty(&env)
-            .map_err(|e| Error::Serde(format!("keystore serialize: {e}")))?;
-        write_atomic(&path, &json)
+        seal_to_file(&path, meta, secret, password, aead_alg, kdf_choice)
     }
 
     /// Load and decrypt a secret by `label` using `password`.
     pub fn load(&self, label: &str, password: &str) -> Result<KeystoreEntry> {
+        self.unseal(label, password).map(|(_, entry)| entry)
+    }
+
+    /// Re-encrypt `label` under `new_pw`, keeping its AEAD, KDF and metadata.
+    ///
+    /// The secret never leaves this call: it is decrypted with `old_pw` into a
+    /// zeroized buffer and resealed with a fresh salt and nonce. The file is
+    /// replaced atomically, so a wrong `old_pw` or any failure leaves it as it was.
+    pub fn change_password(&self, label: &str, old_pw: &str, new_pw: &str) -> Result<()> {
+        let (env, entry) = self.unseal(label, old_pw)?;
+        let secret = Zeroizing::new(entry.secret);
+        let aead_alg = Aead::from_name(&env.aead.name)
+            .ok_or_else(|| Error::Serde("unsupported kdf/aead".into()))?;
+        let kdf_choice = KdfChoice::from_params(&env.kdf)?;
+        seal_to_file(&self.path_for(label), env.meta, &secret, new_pw, aead_alg, kdf_choice)
+    }
+
+    /// Read and decrypt `label`'s envelope, returning it with the entry.
+    fn unseal(&self, label: &str, password: &str) -> Result<(FileEnvelope, KeystoreEntry)> {
         validate_label(label)?;
         let path = self.path_for(label);
         let data = fs::read(&path).map_err(|e| Error::Io(format!("read keystore: {e}")))?;
         let env: FileEnvelope =
             serde_json::from_slice(&data).map_err(|e| Error::Serde(format!("parse: {e}")))?;
 
-        if env.version != VERSION {
+        if env.version != VERSION && env.version != VERSION_V1 {
             return Err(Error::Serde(format!(
                 "unsupported keystore version: {}",
                 env.version
             )));
         }
-        if env.kdf.name != KDF_NAME || env.aead.name != AEAD_NAME {
-            return Err(Error::Serde("unsupported kdf/aead".into()));
-        }
+        let aead_alg = Aead::from_name(&env.aead.name)
+            .ok_or_else(|| Error::Serde("unsupported kdf/aead".into()))?;
 
         let salt =
             B64.decode(env.kdf.salt.as_bytes())
@@ -191,8 +363,22 @@ impl Keystore {
             B64.decode(env.ciphertext.as_bytes())
                 .map_err(|e| Error::Serde(format!("ciphertext b64: {e}")))?;
 
-        let key = derive_key(password, &salt, env.kdf.iterations)?;
-        let pt = aead_decrypt(&key, &nonce, &ct)?;
+        if nonce.len() != aead_alg.nonce_len() {
+            return Err(Error::Serde(format!(
+                "nonce must be {} bytes for {}",
+                aead_alg.nonce_len(),
+                aead_alg.name()
+            )));
+        }
+
+        let aad = if env.version == VERSION_V1 {
+            Vec::new()
+        } else {
+            envelope_aad(env.version, &env.kdf, &salt, &env.aead, &nonce, &env.meta)
+        };
+
+        let key = derive_envelope_key(&env.kdf, password, &salt)?;
+        let pt = Zeroizing::new(aead_decrypt(aead_alg, &key, &nonce, &aad, &ct)?);
 
         // parse plaintext blob
         if pt.len() < 6 {
@@ -205,11 +391,12 @@ impl Keystore {
         }
         let secret = pt[6..6 + l].to_vec();
 
-        Ok(KeystoreEntry {
-            label: env.meta.label,
+        let entry = KeystoreEntry {
+            label: env.meta.label.clone(),
             alg_id,
             secret,
-        })
+        };
+        Ok((env, entry))
     }
 
     /// Delete a stored label (best-effort).
@@ -239,6 +426,142 @@ impl Keystore {
         Ok(out)
     }
 
+    /// Export the entries for `labels` into one encrypted, portable bundle.
+    ///
+    /// Each entry is decrypted with `password`, and the bundle is sealed under
+    /// a fresh key derived from the same `password`.
+    pub fn export_bundle(&self, labels: &[&str], password: &str) -> Result<Vec<u8>> {
+        if labels.is_empty() {
+            return Err(Error::Serde("export_bundle: no labels given".into()));
+        }
+        let mut items = Vec::with_capacity(labels.len());
+        for label in labels {
+            let mut e = self.load(label, password)?;
+            items.push(BundleItem {
+                label: e.label.clone(),
+                alg_id: e.alg_id,
+                secret: B64.encode(&e.secret),
+            });
+            e.secret.zeroize();
+        }
+        let pt = Zeroizing::new(
+            serde_json::to_vec(&items).map_err(|e| Error::Serde(format!("bundle payload: {e}")))?,
+        );
+        for it in &mut items {
+            it.secret.zeroize();
+        }
+
+        let mut salt = [0u8; 16];
+        random_bytes(&mut salt);
+        let mut nonce_bytes = [0u8; 12];
+        random_bytes(&mut nonce_bytes);
+        let key = derive_key(password, &salt, KDF_ITERATIONS)?;
+
+        let mut env = BundleEnvelope {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            kdf: KdfChoice::Pbkdf2Sha3.params(&salt),
+            aead: AeadParams {
+                name: Aead::Aes256Gcm.name().to_string(),
+                nonce: B64.encode(nonce_bytes),
+            },
+            count: items.len() as u32,
+            ciphertext: String::new(),
+        };
+        let aad = bundle_aad(&env, &salt, &nonce_bytes);
+        let sealed = aead_encrypt(Aead::Aes256Gcm, &key, &nonce_bytes, &aad, &pt)?;
+        env.ciphertext = B64.encode(sealed);
+
+        serde_json::to_vec_pretty(&env).map_err(|e| Error::Serde(format!("bundle serialize: {e}")))
+    }
+
+    /// Import a bundle produced by [`Keystore::export_bundle`], storing every
+    /// entry under `password`. Returns the imported labels.
+    ///
+    /// Nothing is written if any label already exists and `overwrite` is false,
+    /// and an error part-way through leaves the keystore as it was.
+    pub fn import_bundle(&self, bytes: &[u8], password: &str, overwrite: bool) -> Result<Vec<String>> {
+        let env: BundleEnvelope =
+            serde_json::from_slice(bytes).map_err(|e| Error::Serde(format!("bundle parse: {e}")))?;
+        if env.format != BUNDLE_FORMAT || env.version != BUNDLE_VERSION {
+            return Err(Error::Serde(format!(
+                "unsupported bundle: {} v{}",
+                env.format, env.version
+            )));
+        }
+        if env.kdf.name != KDF_NAME || env.aead.name != Aead::Aes256Gcm.name() {
+            return Err(Error::Serde("unsupported kdf/aead".into()));
+        }
+
+        let salt = B64
+            .decode(env.kdf.salt.as_bytes())
+            .map_err(|e| Error::Serde(format!("salt b64: {e}")))?;
+        let nonce: [u8; 12] = B64
+            .decode(env.aead.nonce.as_bytes())
+            .map_err(|e| Error::Serde(format!("nonce b64: {e}")))?
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::Serde("nonce must be 12 bytes".into()))?;
+        let ct = B64
+            .decode(env.ciphertext.as_bytes())
+            .map_err(|e| Error::Serde(format!("ciphertext b64: {e}")))?;
+
+        let aad = bundle_aad(&env, &salt, &nonce);
+        let key = derive_key(password, &salt, env.kdf.iterations)?;
+        let pt = Zeroizing::new(aead_decrypt(Aead::Aes256Gcm, &key, &nonce, &aad, &ct)?);
+
+        let mut items: Vec<BundleItem> =
+            serde_json::from_slice(&pt).map_err(|e| Error::Serde(format!("bundle payload: {e}")))?;
+        if items.len() != env.count as usize {
+            return Err(Error::Serde("bundle entry count mismatch".into()));
+        }
+        for it in &items {
+            validate_label(&it.label)?;
+            if !overwrite && self.path_for(&it.label).exists() {
+                return Err(Error::Io(format!(
+                    "keystore file exists for {}; set overwrite=true",
+                    it.label
+                )));
+            }
+        }
+
+        // Seal every entry to a staging file beside its final path before
+        // touching any keystore file, so a failed import leaves none behind.
+        let mut staged = Vec::with_capacity(items.len());
+        for it in &mut items {
+            let res = self.stage_import(it, password);
+            it.secret.zeroize();
+            match res {
+                Ok(tmp) => staged.push((tmp, self.path_for(&it.label))),
+                Err(e) => {
+                    for (tmp, _) in &staged {
+                        let _ = fs::remove_file(tmp);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        commit_staged(&staged)?;
+        Ok(items.into_iter().map(|it| it.label).collect())
+    }
+
+    /// Seal one bundle entry to `<label>.json.import` and return that path.
+    fn stage_import(&self, it: &BundleItem, password: &str) -> Result<PathBuf> {
+        let secret = Zeroizing::new(
+            B64.decode(it.secret.as_bytes())
+                .map_err(|e| Error::Serde(format!("secret b64: {e}")))?,
+        );
+        let meta = Meta {
+            label: it.label.clone(),
+            alg_id: it.alg_id,
+            created_at: rfc3339_now(),
+        };
+        let json = seal_envelope(meta, &secret, password, Aead::default(), KdfChoice::default())?;
+        let tmp = self.path_for(&it.label).with_extension("json.import");
+        write_synced(&tmp, &json)?;
+        Ok(tmp)
+    }
+
     fn path_for(&self, label: &str) -> PathBuf {
         self.dir.join(format!("{}.json", label))
     }
@@ -246,6 +569,64 @@ impl Keystore {
 
 // ------------------------------ Crypto ---------------------------------------
 
+/// Seal `secret` under `password` with a fresh salt and nonce, and atomically
+/// (re)write the envelope at `path`.
+fn seal_to_file(
+    path: &Path,
+    meta: Meta,
+    secret: &[u8],
+    password: &str,
+    aead_alg: Aead,
+    kdf_choice: KdfChoice,
+) -> Result<()> {
+    let json = seal_envelope(meta, secret, password, aead_alg, kdf_choice)?;
+    write_atomic(path, &json)
+}
+
+/// Seal `secret` under `password` with a fresh salt and nonce into envelope JSON.
+fn seal_envelope(
+    meta: Meta,
+    secret: &[u8],
+    password: &str,
+    aead_alg: Aead,
+    kdf_choice: KdfChoice,
+) -> Result<Vec<u8>> {
+    // Derive key
+    let mut salt = [0u8; 16];
+    random_bytes(&mut salt);
+    let kdf = kdf_choice.params(&salt);
+    let key = derive_envelope_key(&kdf, password, &salt)?;
+
+    // Build plaintext blob: alg_id (u16 BE) | len (u32 BE) | secret
+    let mut pt = Zeroizing::new(Vec::with_capacity(2 + 4 + secret.len()));
+    pt.extend_from_slice(&meta.alg_id.to_be_bytes());
+    pt.extend_from_slice(&(secret.len() as u32).to_be_bytes());
+    pt.extend_from_slice(secret);
+
+    let nonce_bytes = aead_alg.random_nonce();
+
+    // Header fields are fixed before sealing so they can be bound as AAD.
+    let aead = AeadParams {
+        name: aead_alg.name().to_string(),
+        nonce: B64.encode(&nonce_bytes),
+    };
+
+    // AEAD encrypt with a random nonce, authenticating the header.
+    let aad = envelope_aad(VERSION, &kdf, &salt, &aead, &nonce_bytes, &meta);
+    let sealed = aead_encrypt(aead_alg, &key, &nonce_bytes, &aad, &pt)?;
+
+    // Build envelope
+    let env = FileEnvelope {
+        version: VERSION,
+        kdf,
+        aead,
+        meta,
+        ciphertext: B64.encode(sealed),
+    };
+
+    serde_json::to_vec_pretty(&env).map_err(|e| Error::Serde(format!("keystore serialize: {e}")))
+}
+
 fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Result<[u8; 32]> {
     if salt.len() < 8 {
         return Err(Error::Serde("salt too short".into()));
@@ -255,29 +636,142 @@ fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Result<[u8; 32]>
     Ok(*out)
 }
 
-/// Encrypt `pt` with AES-256-GCM using 32-byte `key` and 12-byte `nonce`.
-fn aead_encrypt(key: &[u8; 32], nonce: &[u8; 12], pt: &[u8]) -> Result<Vec<u8>> {
+/// Derive the sealing key described by an envelope `kdf` block.
+fn derive_envelope_key(kdf: &KdfParams, password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    match kdf.name.as_str() {
+        KDF_NAME => derive_key(password, salt, kdf.iterations),
+        ARGON2ID_KDF_NAME => {
+            let (Some(memory), Some(parallelism)) = (kdf.memory, kdf.parallelism) else {
+                return Err(Error::Serde("argon2id kdf needs memory and parallelism".into()));
+            };
+            derive_key_argon2id(password, salt, memory, kdf.iterations, parallelism)
+        }
+        _ => Err(Error::Serde("unsupported kdf/aead".into())),
+    }
+}
+
+#[cfg(feature = "argon2")]
+fn derive_key_argon2id(
+    password: &str,
+    salt: &[u8],
+    memory: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<[u8; 32]> {
+    use argon2::{Algorithm, Argon2, Params, Version};
+
+    if salt.len() < 8 {
+        return Err(Error::Serde("salt too short".into()));
+    }
+    let params = Params::new(memory, iterations, parallelism, Some(32))
+        .map_err(|e| Error::Serde(format!("argon2id params: {e}")))?;
+    let mut out = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, out.as_mut())
+        .map_err(|e| Error::Crypto(format!("argon2id: {e}")))?;
+    Ok(*out)
+}
+
+#[cfg(not(feature = "argon2"))]
+fn derive_key_argon2id(_: &str, _: &[u8], _: u32, _: u32, _: u32) -> Result<[u8; 32]> {
+    Err(Error::FeatureUnavailable)
+}
+
+/// Canonical AAD for a keystore file header. `salt` and `nonce` are the
+/// decoded bytes, so only their values are bound, not their base64 spelling.
+fn envelope_aad(
+    version: u32,
+    kdf: &KdfParams,
+    salt: &[u8],
+    aead: &AeadParams,
+    nonce: &[u8],
+    meta: &Meta,
+) -> Vec<u8> {
+    let mut aad = header_aad(b"animica-keystore", version, kdf, salt, aead, nonce);
+    put_field(&mut aad, meta.label.as_bytes());
+    put_field(&mut aad, &meta.alg_id.to_be_bytes());
+    put_field(&mut aad, meta.created_at.as_bytes());
+    aad
+}
+
+/// Canonical AAD for a bundle header: `format` is the domain tag, then `count`.
+fn bundle_aad(env: &BundleEnvelope, salt: &[u8], nonce: &[u8]) -> Vec<u8> {
+    let mut aad = header_aad(env.format.as_bytes(), env.version, &env.kdf, salt, &env.aead, nonce);
+    put_field(&mut aad, &env.count.to_be_bytes());
+    aad
+}
+
+/// Fields shared by file and bundle headers, each via [`put_field`].
+fn header_aad(
+    domain: &[u8],
+    version: u32,
+    kdf: &KdfParams,
+    salt: &[u8],
+    aead: &AeadParams,
+    nonce: &[u8],
+) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(128);
+    put_field(&mut aad, domain);
+    put_field(&mut aad, &version.to_be_bytes());
+    put_field(&mut aad, kdf.name.as_bytes());
+    put_field(&mut aad, salt);
+    put_field(&mut aad, &kdf.iterations.to_be_bytes());
+    // Absent Argon2id costs encode as empty fields, distinct from any value.
+    for cost in [kdf.memory, kdf.parallelism] {
+        put_field(&mut aad, cost.map(u32::to_be_bytes).as_ref().map_or(&[], |b| &b[..]));
+    }
+    put_field(&mut aad, aead.name.as_bytes());
+    put_field(&mut aad, nonce);
+    aad
+}
+
+/// Append `field` prefixed with its length (u32, BE).
+fn put_field(aad: &mut Vec<u8>, field: &[u8]) {
+    aad.extend_from_slice(&(field.len() as u32).to_be_bytes());
+    aad.extend_from_slice(field);
+}
+
+/// Encrypt `pt` with `alg` using 32-byte `key`, an `alg`-sized `nonce` and `aad`.
+fn aead_encrypt(alg: Aead, key: &[u8; 32], nonce: &[u8], aad: &[u8], pt: &[u8]) -> Result<Vec<u8>> {
+    if nonce.len() != alg.nonce_len() {
+        return Err(Error::Crypto("bad nonce length".into()));
+    }
+    if alg == Aead::XChaCha20Poly1305 {
+        return XChaCha20Poly1305::new(key.into())
+            .encrypt(XNonce::from_slice(nonce), Payload { msg: pt, aad })
+            .map_err(|_| Error::Crypto("aead seal".into()));
+    }
+    let nonce: [u8; 12] = nonce.try_into().map_err(|_| Error::Crypto("bad nonce length".into()))?;
     let unbound =
         UnboundKey::new(&aead::AES_256_GCM, key).map_err(|_| Error::Crypto("bad key".into()))?;
-    let nonce = Nonce::assume_unique_for_key(*nonce);
+    let nonce = Nonce::assume_unique_for_key(nonce);
     let mut sealing_key = LessSafeKey::new(unbound);
     let mut buf = Vec::with_capacity(pt.len() + aead::AES_256_GCM.tag_len());
     buf.extend_from_slice(pt);
     sealing_key
-        .seal_in_place_append_tag(nonce, Aad::empty(), &mut buf)
+        .seal_in_place_append_tag(nonce, Aad::from(aad), &mut buf)
         .map_err(|_| Error::Crypto("aead seal".into()))?;
     Ok(buf)
 }
 
-/// Decrypt `ct` with AES-256-GCM using 32-byte `key` and 12-byte `nonce`.
-fn aead_decrypt(key: &[u8; 32], nonce: &[u8; 12], ct: &[u8]) -> Result<Vec<u8>> {
+/// Decrypt `ct` with `alg` using 32-byte `key`, an `alg`-sized `nonce` and `aad`.
+fn aead_decrypt(alg: Aead, key: &[u8; 32], nonce: &[u8], aad: &[u8], ct: &[u8]) -> Result<Vec<u8>> {
+    if nonce.len() != alg.nonce_len() {
+        return Err(Error::Crypto("bad nonce length".into()));
+    }
+    if alg == Aead::XChaCha20Poly1305 {
+        return XChaCha20Poly1305::new(key.into())
+            .decrypt(XNonce::from_slice(nonce), Payload { msg: ct, aad })
+            .map_err(|_| Error::Crypto("aead open".into()));
+    }
+    let nonce: [u8; 12] = nonce.try_into().map_err(|_| Error::Crypto("bad nonce length".into()))?;
     let unbound =
         UnboundKey::new(&aead::AES_256_GCM, key).map_err(|_| Error::Crypto("bad key".into()))?;
-    let nonce = Nonce::assume_unique_for_key(*nonce);
+    let nonce = Nonce::assume_unique_for_key(nonce);
     let mut opening_key = LessSafeKey::new(unbound);
     let mut buf = ct.to_vec();
     let out = opening_key
-        .open_in_place(nonce, Aad::empty(), &mut buf)
+        .open_in_place(nonce, Aad::from(aad), &mut buf)
         .map_err(|_| Error::Crypto("aead open".into()))?;
     Ok(out.to_vec())
 }
@@ -289,37 +783,102 @@ fn rfc3339_now() -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default();
-    // This is a compact ISO8601-like fallback without date libs: seconds precision UTC.
-    // Example: 2025-09-27T13:37:42Z
-    // Compute date manually would be overkill; using chrono would be preferable in real builds.
-    // For readability, we'll leave a simple placeholder with UNIX seconds.
-    format!("{}Z", unix_to_iso_approx(now.as_secs()))
+    unix_to_rfc3339(now.as_secs())
 }
 
-/// Best-effort ISO8601 formatting from unix seconds (UTC). Month/day calc is simplified
-/// but monotonic; not for display-critical logs. Consider enabling `chrono` if you need exactness.
-fn unix_to_iso_approx(mut secs: u64) -> String {
-    // Very rough conversion: we will not try to calculate month/day; instead emit
-    // "1970-01-01T<HH:MM:SS>" + days offset ignored. To avoid confusion, include full seconds.
-    // Consumers should treat `created_at` as informational only.
-    let h = (secs / 3600) % 24;
-    let m = (secs / 60) % 60;
-    let s = secs % 60;
-    format!("1970-01-01T{:02}:{:02}:{:02}", h, m, s)
+/// Format unix seconds as a UTC RFC3339 timestamp, e.g. `2025-09-27T13:37:42Z`.
+fn unix_to_rfc3339(secs: u64) -> String {
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    let (y, m, d) = civil_from_days(days as i64);
+    format!(
+        "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        (rem / 60) % 60,
+        rem % 60
+    )
+}
+
+/// Days since 1970-01-01 → proleptic Gregorian (year, month, day), using
+/// Howard Hinnant's `civil_from_days` (400-year eras starting in March).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097); // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365; // [0, 399]
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365], from March 1st
+    let mp = (5 * doy + 2) / 153; // [0, 11], March-based month
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
 }
 
 fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
     let tmp = path.with_extension("json.tmp");
-    {
-        let mut f =
-            fs::File::create(&tmp).map_err(|e| Error::Io(format!("create tmp: {e}")))?;
-        f.write_all(data)
-            .map_err(|e| Error::Io(format!("write tmp: {e}")))?;
-        f.sync_all().ok(); // best-effort
-    }
+    write_synced(&tmp, data)?;
     fs::rename(&tmp, path).map_err(|e| Error::Io(format!("rename tmp: {e}")))
 }
 
+/// Create `tmp` holding `data`; a partial file is removed on error.
+fn write_synced(tmp: &Path, data: &[u8]) -> Result<()> {
+    let res = fs::File::create(tmp)
+        .map_err(|e| Error::Io(format!("create tmp: {e}")))
+        .and_then(|mut f| {
+            f.write_all(data)
+                .map_err(|e| Error::Io(format!("write tmp: {e}")))?;
+            f.sync_all().ok(); // best-effort
+            Ok(())
+        });
+    if res.is_err() {
+        let _ = fs::remove_file(tmp);
+    }
+    res
+}
+
+/// Rename each staged `(tmp, dest)` pair into place, all or nothing.
+///
+/// A file being replaced is parked at `<label>.json.bak` until every rename
+/// has gone through. On error the entries already moved are undone (parked
+/// files restored, new ones removed) and the remaining staging files deleted.
+fn commit_staged(staged: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let mut done: Vec<(&Path, Option<PathBuf>)> = Vec::with_capacity(staged.len());
+    let mut failed = None;
+    for (tmp, dest) in staged {
+        let backup = dest.exists().then(|| dest.with_extension("json.bak"));
+        if let Some(bak) = &backup {
+            if let Err(e) = fs::rename(dest, bak) {
+                failed = Some(Error::Io(format!("park {}: {e}", dest.display())));
+                break;
+            }
+        }
+        if let Err(e) = fs::rename(tmp, dest) {
+            if let Some(bak) = &backup {
+                let _ = fs::rename(bak, dest);
+            }
+            failed = Some(Error::Io(format!("rename tmp: {e}")));
+            break;
+        }
+        done.push((dest, backup));
+    }
+
+    let Some(err) = failed else {
+        for bak in done.into_iter().filter_map(|(_, bak)| bak) {
+            let _ = fs::remove_file(bak);
+        }
+        return Ok(());
+    };
+    for (dest, backup) in done.into_iter().rev() {
+        let _ = match backup {
+            Some(bak) => fs::rename(bak, dest),
+            None => fs::remove_file(dest),
+        };
+    }
+    for (tmp, _) in staged {
+        let _ = fs::remove_file(tmp);
+    }
+    Err(err)
+}
+
 fn validate_label(label: &str) -> Result<()> {
     if label.is_empty() || label.len() > 128 {
         return Err(Error::Serde("label length invalid".into()));
@@ -340,7 +899,6 @@ fn validate_label(label: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::Rng;
 
     #[test]
     fn label_validation() {
@@ -349,6 +907,16 @@ mod tests {
         assert!(validate_label("").is_err());
     }
 
+    #[test]
+    fn rfc3339_from_unix_seconds() {
+        assert_eq!(unix_to_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(unix_to_rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(unix_to_rfc3339(1_709_251_199), "2024-02-29T23:59:59Z");
+        assert_eq!(unix_to_rfc3339(1_234_567_890), "2009-02-13T23:31:30Z");
+        assert_eq!(unix_to_rfc3339(1_758_980_262), "2025-09-27T13:37:42Z");
+        assert_eq!(unix_to_rfc3339(4_102_444_800), "2100-01-01T00:00:00Z");
+    }
+
     #[test]
     fn pbkdf2_sha3_len() {
         let key = derive_key("pw", b"0123456789ABCDEF", 1).unwrap();
@@ -360,10 +928,12 @@ mod tests {
         let key = [42u8; 32];
         let nonce = [7u8; 12];
         let msg = b"hello secret";
-        let ct = aead_encrypt(&key, &nonce, msg).unwrap();
+        let ct = aead_encrypt(Aead::Aes256Gcm, &key, &nonce, b"hdr", msg).unwrap();
         assert_ne!(ct, msg);
-        let pt = aead_decrypt(&key, &nonce, &ct).unwrap();
+        let pt = aead_decrypt(Aead::Aes256Gcm, &key, &nonce, b"hdr", &ct).unwrap();
         assert_eq!(pt, msg);
+        // Different AAD must not open.
+        assert!(aead_decrypt(Aead::Aes256Gcm, &key, &nonce, b"hdR", &ct).is_err());
     }
 
     #[test]
@@ -373,7 +943,7 @@ mod tests {
 
         // random secret
         let mut sec = vec![0u8; 48];
-        rand::thread_rng().fill_bytes(&mut sec);
+        random_bytes(&mut sec);
 
         ks.store("mykey", 0x0103, &sec, "strong-password", true)
             .unwrap();
@@ -392,4 +962,243 @@ mod tests {
         ks.delete("mykey").unwrap();
         assert!(ks.list_labels().unwrap().is_empty());
     }
+
+    #[test]
+    fn xchacha_store_load_and_mixed_aead_rejected() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ks = Keystore::open(tmpdir.path()).unwrap();
+        ks.store_with_aead("xc", 0x0103, b"xchacha-seed", "pw", true, Aead::XChaCha20Poly1305)
+            .unwrap();
+
+        let path = ks.path_for("xc");
+        let v: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(v["aead"]["name"], "XChaCha20-Poly1305");
+        assert_eq!(B64.decode(v["aead"]["nonce"].as_str().unwrap()).unwrap().len(), 24);
+
+        let e = ks.load("xc", "pw").unwrap();
+        assert_eq!(e.secret, b"xchacha-seed");
+
+        // Relabel as AES-GCM: the 24-byte nonce is rejected up front.
+        let mut mixed = v.clone();
+        mixed["aead"]["name"] = serde_json::json!("AES-256-GCM");
+        fs::write(&path, serde_json::to_vec_pretty(&mixed).unwrap()).unwrap();
+        assert!(matches!(ks.load("xc", "pw"), Err(Error::Serde(_))));
+
+        // Relabel with a well-formed AES nonce: authentication fails cleanly.
+        mixed["aead"]["nonce"] = serde_json::json!(B64.encode([0u8; 12]));
+        fs::write(&path, serde_json::to_vec_pretty(&mixed).unwrap()).unwrap();
+        assert!(matches!(ks.load("xc", "pw"), Err(Error::Crypto(_))));
+    }
+
+    #[cfg(feature = "argon2")]
+    #[test]
+    fn argon2id_and_pbkdf2_files_roundtrip_side_by_side() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ks = Keystore::open(tmpdir.path()).unwrap();
+        let cheap = KdfChoice::Argon2id { memory: 1024, iterations: 1, parallelism: 1 };
+        ks.store("old", 0x0103, b"pbkdf2-seed", "pw", true).unwrap();
+        ks.store_with("new", 0x0201, b"argon-seed", "pw", true, Aead::XChaCha20Poly1305, cheap)
+            .unwrap();
+
+        let read = |label: &str| -> serde_json::Value {
+            serde_json::from_slice(&fs::read(ks.path_for(label)).unwrap()).unwrap()
+        };
+        let (old, new) = (read("old"), read("new"));
+        assert_eq!(old["kdf"]["name"], KDF_NAME);
+        assert!(old["kdf"].get("memory").is_none(), "PBKDF2 header shape unchanged");
+        assert_eq!(new["kdf"]["name"], "Argon2id");
+        assert_eq!(
+            (&new["kdf"]["memory"], &new["kdf"]["iterations"], &new["kdf"]["parallelism"]),
+            (&serde_json::json!(1024), &serde_json::json!(1), &serde_json::json!(1))
+        );
+
+        assert_eq!(ks.load("old", "pw").unwrap().secret, b"pbkdf2-seed");
+        assert_eq!(ks.load("new", "pw").unwrap().secret, b"argon-seed");
+        assert!(ks.load("new", "wrong").is_err());
+
+        // The Argon2id cost parameters are authenticated like the rest of the header.
+        let mut tampered = new.clone();
+        tampered["kdf"]["memory"] = serde_json::json!(2048);
+        fs::write(ks.path_for("new"), serde_json::to_vec_pretty(&tampered).unwrap()).unwrap();
+        assert!(ks.load("new", "pw").is_err());
+    }
+
+    #[cfg(not(feature = "argon2"))]
+    #[test]
+    fn argon2id_needs_feature() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ks = Keystore::open(tmpdir.path()).unwrap();
+        let res = ks.store_with("a", 0x0103, b"s", "pw", true, Aead::default(), KdfChoice::argon2id());
+        assert!(matches!(res, Err(Error::FeatureUnavailable)));
+    }
+
+    #[test]
+    fn change_password_rekeys_in_place() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ks = Keystore::open(tmpdir.path()).unwrap();
+        ks.store_with_aead("rot", 0x0201, b"rotating-seed", "old-pw", true, Aead::XChaCha20Poly1305)
+            .unwrap();
+        let path = ks.path_for("rot");
+        let raw_before = fs::read(&path).unwrap();
+        let before: serde_json::Value = serde_json::from_slice(&raw_before).unwrap();
+
+        // Wrong old password: error, file untouched.
+        assert!(ks.change_password("rot", "nope", "new-pw").is_err());
+        assert_eq!(fs::read(&path).unwrap(), raw_before);
+
+        ks.change_password("rot", "old-pw", "new-pw").unwrap();
+        assert!(ks.load("rot", "old-pw").is_err());
+        let e = ks.load("rot", "new-pw").unwrap();
+        assert_eq!((e.alg_id, e.secret), (0x0201, b"rotating-seed".to_vec()));
+
+        let after: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(after["aead"]["name"], "XChaCha20-Poly1305");
+        assert_eq!(after["meta"], before["meta"]);
+        assert_ne!(after["kdf"]["salt"], before["kdf"]["salt"]);
+        assert_ne!(after["aead"]["nonce"], before["aead"]["nonce"]);
+    }
+
+    #[test]
+    fn tampered_meta_fails_load() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ks = Keystore::open(tmpdir.path()).unwrap();
+        ks.store("tamper", 0x0103, b"seed-bytes", "pw", true).unwrap();
+
+        // Rewrite meta.alg_id directly in the file.
+        let path = ks.path_for("tamper");
+        let mut v: serde_json::Value =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(v["version"], 2);
+        v["meta"]["alg_id"] = serde_json::json!(0x0201);
+        fs::write(&path, serde_json::to_vec_pretty(&v).unwrap()).unwrap();
+
+        assert!(ks.load("tamper", "pw").is_err());
+    }
+
+    #[test]
+    fn envelope_key_order_is_free_but_unknown_fields_are_rejected() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ks = Keystore::open(tmpdir.path()).unwrap();
+        ks.store("hdr", 0x0103, b"seed-bytes", "pw", true).unwrap();
+        let path = ks.path_for("hdr");
+        let v: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+
+        // Compact JSON with keys sorted differently still opens.
+        fs::write(&path, serde_json::to_vec(&v).unwrap()).unwrap();
+        assert_eq!(ks.load("hdr", "pw").unwrap().secret, b"seed-bytes");
+
+        for (block, key) in [(None, "note"), (Some("kdf"), "pepper"), (Some("meta"), "owner")] {
+            let mut t = v.clone();
+            match block {
+                Some(b) => t[b][key] = serde_json::json!("x"),
+                None => t[key] = serde_json::json!("x"),
+            }
+            fs::write(&path, serde_json::to_vec(&t).unwrap()).unwrap();
+            let err = ks.load("hdr", "pw").unwrap_err();
+            assert!(matches!(err, Error::Serde(ref m) if m.contains(key)), "{err:?}");
+        }
+        fs::write(&path, serde_json::to_vec(&v).unwrap()).unwrap();
+
+        let mut bundle: serde_json::Value =
+            serde_json::from_slice(&ks.export_bundle(&["hdr"], "pw").unwrap()).unwrap();
+        bundle["aead"]["tag"] = serde_json::json!("x");
+        let dst_dir = tempfile::tempdir().unwrap();
+        let dst = Keystore::open(dst_dir.path()).unwrap();
+        assert!(dst.import_bundle(&serde_json::to_vec(&bundle).unwrap(), "pw", false).is_err());
+    }
+
+    #[test]
+    fn bundle_export_import_roundtrip() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let src = Keystore::open(src_dir.path()).unwrap();
+        src.store("alpha", 0x0103, b"alpha-secret", "pw", false).unwrap();
+        src.store("beta", 0x0201, &[9u8; 64], "pw", false).unwrap();
+        src.store("gamma", 0x0103, b"not-exported", "pw", false).unwrap();
+
+        let bundle = src.export_bundle(&["alpha", "beta"], "pw").unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&bundle).unwrap();
+        assert_eq!(v["format"], BUNDLE_FORMAT);
+        assert_eq!(v["version"], 1);
+        assert_eq!(v["count"], 2);
+
+        let dst_dir = tempfile::tempdir().unwrap();
+        let dst = Keystore::open(dst_dir.path()).unwrap();
+        assert!(dst.import_bundle(&bundle, "wrong", false).is_err());
+
+        let labels = dst.import_bundle(&bundle, "pw", false).unwrap();
+        assert_eq!(labels, vec!["alpha".to_string(), "beta".to_string()]);
+        assert_eq!(dst.list_labels().unwrap(), labels);
+        assert_eq!(dst.load("alpha", "pw").unwrap().secret, b"alpha-secret");
+        let beta = dst.load("beta", "pw").unwrap();
+        assert_eq!((beta.alg_id, beta.secret), (0x0201, vec![9u8; 64]));
+
+        // Re-import refuses to clobber unless asked.
+        assert!(dst.import_bundle(&bundle, "pw", false).is_err());
+        dst.import_bundle(&bundle, "pw", true).unwrap();
+    }
+
+    #[test]
+    fn bundle_of_three_and_wrong_password_writes_nothing() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let src = Keystore::open(src_dir.path()).unwrap();
+        let keys: [(&str, u16, &[u8]); 3] =
+            [("k1", 0x0103, b"one"), ("k2", 0x0201, b"two"), ("k3", 0x0103, &[3u8; 48])];
+        for (label, alg, secret) in keys {
+            src.store(label, alg, secret, "pw", false).unwrap();
+        }
+        let bundle = src.export_bundle(&["k1", "k2", "k3"], "pw").unwrap();
+        // The bundle header has its own salt/nonce, not any file's.
+        let v: serde_json::Value = serde_json::from_slice(&bundle).unwrap();
+        let f: serde_json::Value = serde_json::from_slice(&fs::read(src.path_for("k1")).unwrap()).unwrap();
+        assert_ne!(v["kdf"]["salt"], f["kdf"]["salt"]);
+
+        let dst_dir = tempfile::tempdir().unwrap();
+        let dst = Keystore::open(dst_dir.path()).unwrap();
+        assert!(dst.import_bundle(&bundle, "wrong", true).is_err());
+        assert_eq!(fs::read_dir(dst_dir.path()).unwrap().count(), 0, "no files on failure");
+
+        dst.import_bundle(&bundle, "pw", false).unwrap();
+        for (label, alg, secret) in keys {
+            let e = dst.load(label, "pw").unwrap();
+            assert_eq!((e.alg_id, e.secret.as_slice()), (alg, secret));
+        }
+    }
+
+    #[test]
+    fn failed_bundle_import_leaves_keystore_unchanged() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let src = Keystore::open(src_dir.path()).unwrap();
+        for label in ["k1", "k2", "k3"] {
+            src.store(label, 0x0103, label.as_bytes(), "pw", false).unwrap();
+        }
+        let bundle = src.export_bundle(&["k1", "k2", "k3"], "pw").unwrap();
+
+        let dst_dir = tempfile::tempdir().unwrap();
+        let dst = Keystore::open(dst_dir.path()).unwrap();
+        dst.store("k1", 0x0201, b"old-k1", "pw", false).unwrap();
+
+        // A directory squatting on k2's staging path makes its write fail
+        // after k1 has already been sealed.
+        let blocker = dst.path_for("k2").with_extension("json.import");
+        fs::create_dir(&blocker).unwrap();
+        assert!(dst.import_bundle(&bundle, "pw", true).is_err());
+        fs::remove_dir(&blocker).unwrap();
+
+        assert_eq!(dst.list_labels().unwrap(), vec!["k1".to_string()]);
+        assert_eq!(fs::read_dir(dst_dir.path()).unwrap().count(), 1, "no staging files left");
+        assert_eq!(dst.load("k1", "pw").unwrap().secret, b"old-k1");
+
+        // A failed rename rolls back the entries already moved into place.
+        let staged = vec![
+            (src.path_for("k1"), dst.path_for("k1")),
+            (src_dir.path().join("missing.json"), dst.path_for("k9")),
+        ];
+        assert!(commit_staged(&staged).is_err());
+        assert_eq!(dst.list_labels().unwrap(), vec!["k1".to_string()]);
+        assert_eq!(dst.load("k1", "pw").unwrap().secret, b"old-k1");
+        assert_eq!(fs::read_dir(dst_dir.path()).unwrap().count(), 1, "no backup left");
+
+        dst.import_bundle(&bundle, "pw", true).unwrap();
+        assert_eq!(dst.load("k1", "pw").unwrap().secret, b"k1");
+    }
 }
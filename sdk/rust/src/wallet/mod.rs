@@ -10,6 +10,8 @@
 //!
 //! The `Wallet` wraps a pluggable [`WalletSigner`] (Dilithium3, SPHINCS+, …).
 //! Implementations live under `wallet::signer` and are feature-gated by `pq`.
+//! With `pq`, [`Wallet::from_mnemonic`] derives the signer from a mnemonic and
+//! an `m/purpose'/chain'/account'` path (see [`path`]).
 //!
 //! ## Examples
 //! ```no_run
@@ -32,10 +34,11 @@ use std::sync::Arc;
 
 pub mod mnemonic;
 pub mod keystore;
+pub mod path;
 
-/// Post-quantum signer implementations (Dilithium3/SPHINCS+ via liboqs or other backends).
-/// Enabled with the `pq` feature.
-#[cfg(feature = "pq")]
+/// Signer implementations: post-quantum Dilithium3/SPHINCS+ via liboqs (feature `pq`)
+/// and a classical Ed25519 signer (feature `ed25519`).
+#[cfg(any(feature = "pq", feature = "ed25519"))]
 pub mod signer;
 
 //
@@ -59,6 +62,13 @@ pub trait WalletSigner: Send + Sync {
     /// The `domain` should be a short stable string/bytes identifying the
     /// signing context (e.g., `b"sign-domain/tx"` or `b"sign-domain/ws-auth"`).
     fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>>;
+
+    /// Sign each of `messages` under `domain`, in input order. Fails as a whole
+    /// on the first error. The default loops over [`Self::sign`]; backends with
+    /// per-call setup cost (e.g. liboqs key import) should override it.
+    fn sign_batch(&self, domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        messages.iter().map(|m| self.sign(domain, m)).collect()
+    }
 }
 
 /// Convenience wrapper around a signer providing address derivation and helpers.
@@ -93,6 +103,23 @@ impl Wallet {
         })
     }
 
+    /// Derive a wallet from `mnemonic` (no passphrase) at `path`
+    /// (`m/purpose'/chain'/account'`) for the PQ scheme `alg_id`
+    /// (`ALG_ID_DILITHIUM3` or `ALG_ID_SPHINCS_SHAKE_128S`). The same inputs
+    /// always yield the same keys and address.
+    #[cfg(feature = "pq")]
+    pub fn from_mnemonic(mnemonic: &Mnemonic, path: &str, alg_id: u16) -> Result<Self> {
+        use signer::{ALG_ID_DILITHIUM3, ALG_ID_SPHINCS_SHAKE_128S};
+
+        let path: DerivationPath = path.parse()?;
+        let seed = path.key_seed(mnemonic, alg_id)?;
+        match alg_id {
+            ALG_ID_DILITHIUM3 => Self::new(PqSigner(Dilithium3Signer::from_seed(&seed)?)),
+            ALG_ID_SPHINCS_SHAKE_128S => Self::new(PqSigner(SphincsShake128sSigner::from_seed(&seed)?)),
+            _ => Err(Error::InvalidParams("unsupported alg_id for mnemonic derivation")),
+        }
+    }
+
     /// Address in bech32m (`anim1…`) form.
     pub fn address(&self) -> &str {
         &self.address
@@ -113,6 +140,12 @@ impl Wallet {
         self.signer.sign(domain, message)
     }
 
+    /// Sign many messages under one domain; signatures are returned in input
+    /// order, and no signatures are returned if any message fails.
+    pub fn sign_batch(&self, domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        self.signer.sign_batch(domain, messages)
+    }
+
     /// Convenience for signing **transaction sign-bytes** (already CBOR-encoded,
     /// canonical, and domain-encoded by the caller).
     ///
@@ -125,6 +158,29 @@ impl Wallet {
     }
 }
 
+/// Adapts a `wallet::signer` implementation to [`WalletSigner`].
+#[cfg(feature = "pq")]
+struct PqSigner<S>(S);
+
+#[cfg(feature = "pq")]
+impl<S: signer::WalletSigner + Send + Sync> WalletSigner for PqSigner<S> {
+    fn alg_id(&self) -> u16 {
+        self.0.alg_id()
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.0.public_key().to_vec()
+    }
+
+    fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+        self.0.sign(domain, message)
+    }
+
+    fn sign_batch(&self, domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        self.0.sign_batch(domain, messages)
+    }
+}
+
 //
 // --------------------------- High-level Helpers -------------------------------
 //
@@ -140,9 +196,74 @@ pub fn validate_address(addr: &str) -> Result<(u16, [u8; 32])> {
 // ------------------------------ Re-exports -----------------------------------
 //
 
-pub use keystore::{Keystore, KeystoreEntry};
+pub use keystore::{KdfChoice, Keystore, KeystoreEntry};
 pub use mnemonic::{Mnemonic, MnemonicLang};
+pub use path::DerivationPath;
 
 #[cfg(feature = "pq")]
 pub use signer::{Dilithium3Signer, SphincsShake128sSigner};
+#[cfg(feature = "ed25519")]
+pub use signer::Ed25519Signer;
+
+//
+// --------------------------------- Tests -------------------------------------
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "pq")]
+    use signer::{ALG_ID_DILITHIUM3, ALG_ID_SPHINCS_SHAKE_128S};
+
+    #[cfg(feature = "pq")]
+    const PHRASE: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+    /// Echoes `domain || message`; refuses empty messages.
+    struct EchoSigner;
+
+    impl WalletSigner for EchoSigner {
+        fn alg_id(&self) -> u16 {
+            0x0103
+        }
 
+        fn public_key(&self) -> Vec<u8> {
+            vec![7u8; 32]
+        }
+
+        fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+            if message.is_empty() {
+                return Err(Error::InvalidParams("empty message"));
+            }
+            Ok([domain, message].concat())
+        }
+    }
+
+    #[test]
+    fn sign_batch_keeps_order_and_fails_atomically() -> Result<()> {
+        let w = Wallet::new(EchoSigner)?;
+        let sigs = w.sign_batch(b"d/", &[b"a", b"bb", b"c"])?;
+        assert_eq!(sigs, vec![b"d/a".to_vec(), b"d/bb".to_vec(), b"d/c".to_vec()]);
+        assert!(w.sign_batch(b"d/", &[b"a", b"", b"c"]).is_err());
+        assert!(w.sign_batch(b"d/", &[])?.is_empty());
+        Ok(())
+    }
+
+    #[cfg(feature = "pq")]
+    #[test]
+    fn from_mnemonic_is_stable_per_path_and_alg() -> Result<()> {
+        let m = Mnemonic::from_phrase(MnemonicLang::English, PHRASE)?;
+        let a = Wallet::from_mnemonic(&m, "m/44'/1'/0'", ALG_ID_DILITHIUM3)?;
+        let b = Wallet::from_mnemonic(&m, "m/44'/1'/0'", ALG_ID_DILITHIUM3)?;
+        assert_eq!(a.address(), b.address());
+        assert_eq!(a.alg_id(), ALG_ID_DILITHIUM3);
+
+        let other = Wallet::from_mnemonic(&m, "m/44'/1'/1'", ALG_ID_DILITHIUM3)?;
+        assert_ne!(a.address(), other.address());
+        let sphincs = Wallet::from_mnemonic(&m, "m/44'/1'/0'", ALG_ID_SPHINCS_SHAKE_128S)?;
+        assert_eq!(sphincs.alg_id(), ALG_ID_SPHINCS_SHAKE_128S);
+
+        assert!(Wallet::from_mnemonic(&m, "m/44'/1'/0'", 0xffff).is_err());
+        assert!(Wallet::from_mnemonic(&m, "m/44/1/0", ALG_ID_DILITHIUM3).is_err());
+        Ok(())
+    }
+}
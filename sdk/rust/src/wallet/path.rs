@@ -0,0 +1,127 @@
+//! Account derivation paths for mnemonic wallets.
+//!
+//! Paths use a BIP-44-flavoured syntax with exactly three **hardened** levels:
+//!
+//! ```text
+//! m/purpose'/chain'/account'      e.g. m/44'/1'/0'   (`h` also accepted for `'`)
+//! ```
+//!
+//! PQ schemes have no hierarchical key math, so the path is not walked level by
+//! level; instead it selects a per-account key seed from the mnemonic seed:
+//!
+//! ```text
+//! key_seed = HKDF-SHA3-256(salt=b"animica-wallet-path-v1", ikm=seed32,
+//!                          info=alg_id (u16, BE) || purpose' || chain' || account' (u32, BE each),
+//!                          L=32)
+//! ```
+//!
+//! where each level is encoded with the hardened bit (`0x8000_0000`) set. The
+//! key seed then drives the chosen scheme's deterministic key generation.
+
+use crate::error::{Error, Result};
+use crate::wallet::mnemonic::Mnemonic;
+use hkdf::Hkdf;
+use sha3::Sha3_256;
+use zeroize::Zeroizing;
+
+/// Hardened-index flag, as in BIP-32.
+const HARDENED: u32 = 0x8000_0000;
+
+/// A parsed `m/purpose'/chain'/account'` path (indices without the hardened bit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationPath {
+    pub purpose: u32,
+    pub chain: u32,
+    pub account: u32,
+}
+
+impl DerivationPath {
+    /// Derive the 32-byte key seed for `alg_id` at this path (see module docs).
+    pub fn key_seed(&self, mnemonic: &Mnemonic, alg_id: u16) -> Result<Zeroizing<[u8; 32]>> {
+        let master = Zeroizing::new(mnemonic.to_seed32(None)?);
+        let hk = Hkdf::<Sha3_256>::new(Some(b"animica-wallet-path-v1"), master.as_slice());
+
+        let mut info = [0u8; 14];
+        info[..2].copy_from_slice(&alg_id.to_be_bytes());
+        for (i, level) in [self.purpose, self.chain, self.account].into_iter().enumerate() {
+            info[2 + 4 * i..6 + 4 * i].copy_from_slice(&(level | HARDENED).to_be_bytes());
+        }
+
+        let mut out = Zeroizing::new([0u8; 32]);
+        hk.expand(&info, out.as_mut())
+            .map_err(|_| Error::Serde("hkdf expand failed".into()))?;
+        Ok(out)
+    }
+}
+
+impl std::str::FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::InvalidParams("derivation path must start with `m/`"));
+        }
+        let mut levels = [0u32; 3];
+        for slot in levels.iter_mut() {
+            let part = parts
+                .next()
+                .ok_or(Error::InvalidParams("derivation path needs purpose'/chain'/account'"))?;
+            let index = part
+                .strip_suffix('\'')
+                .or_else(|| part.strip_suffix('h'))
+                .ok_or(Error::InvalidParams("derivation path levels must be hardened (')"))?;
+            *slot = index
+                .parse::<u32>()
+                .ok()
+                .filter(|i| *i < HARDENED)
+                .ok_or(Error::InvalidParams("derivation path index must be 0..2^31"))?;
+        }
+        if parts.next().is_some() {
+            return Err(Error::InvalidParams("derivation path has more than three levels"));
+        }
+        let [purpose, chain, account] = levels;
+        Ok(Self { purpose, chain, account })
+    }
+}
+
+impl std::fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "m/{}'/{}'/{}'", self.purpose, self.chain, self.account)
+    }
+}
+
+//
+// --------------------------------- Tests -------------------------------------
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::mnemonic::MnemonicLang;
+
+    const PHRASE: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+    #[test]
+    fn parses_hardened_three_level_paths() {
+        let p: DerivationPath = "m/44'/1'/7h".parse().unwrap();
+        assert_eq!(p, DerivationPath { purpose: 44, chain: 1, account: 7 });
+        assert_eq!(p.to_string(), "m/44'/1'/7'");
+
+        for bad in ["44'/1'/0'", "m/44'/1'", "m/44'/1'/0'/0'", "m/44/1'/0'", "m/44'/x'/0'", "m/2147483648'/0'/0'"] {
+            assert!(bad.parse::<DerivationPath>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn key_seed_depends_on_path_and_alg() {
+        let m = Mnemonic::from_phrase(MnemonicLang::English, PHRASE).unwrap();
+        let p0: DerivationPath = "m/44'/1'/0'".parse().unwrap();
+        let p1: DerivationPath = "m/44'/1'/1'".parse().unwrap();
+
+        let a = p0.key_seed(&m, 0x0103).unwrap();
+        assert_eq!(*a, *p0.key_seed(&m, 0x0103).unwrap(), "deterministic");
+        assert_ne!(*a, *p1.key_seed(&m, 0x0103).unwrap(), "account changes the seed");
+        assert_ne!(*a, *p0.key_seed(&m, 0x0201).unwrap(), "alg_id changes the seed");
+    }
+}
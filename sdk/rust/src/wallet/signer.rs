@@ -1,4 +1,6 @@
-use crate::error::{Error, Result};
+#[cfg(feature = "pq")]
+use crate::error::Error;
+use crate::error::Result;
 use sha3::{Digest, Sha3_512};
 
 #[cfg(feature = "pq")]
@@ -7,6 +9,8 @@ use oqs::sig::{Algorithm, PublicKey, SecretKey, Sig};
 /// Canonical Animica PQ alg IDs (must match pq/alg_ids.yaml and SDKs)
 pub const ALG_ID_DILITHIUM3: u16 = 0x0103;
 pub const ALG_ID_SPHINCS_SHAKE_128S: u16 = 0x0201;
+/// Classical (non-PQ) Ed25519; `0x00xx` ids are reserved for classical schemes.
+pub const ALG_ID_ED25519: u16 = 0x0001;
 
 /// Internal helper: domain-separated prehash used by all signers.
 /// Hash = SHA3-512( domain || 0x00 || message )
@@ -29,6 +33,10 @@ pub trait WalletSigner {
     fn public_key(&self) -> &[u8];
     /// Sign `message` under a domain separator. Returns raw signature bytes.
     fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>>;
+    /// Sign each message under `domain`, in order; all-or-nothing.
+    fn sign_batch(&self, domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        messages.iter().map(|m| self.sign(domain, m)).collect()
+    }
 }
 
 #[cfg(feature = "pq")]
@@ -36,6 +44,104 @@ fn map_oqs_err<E: core::fmt::Display>(e: E) -> Error {
     Error::Other(format!("oqs error: {e}"))
 }
 
+/// Serializes every liboqs call that draws randomness (key generation and
+/// signing). liboqs' RNG is process-global and [`keypair_from_seed`] swaps it
+/// for a seed-derived stream while holding this lock, so no other SDK call
+/// can read (and leak or consume) that stream.
+#[cfg(feature = "pq")]
+static OQS_RNG: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(feature = "pq")]
+fn lock_oqs_rng() -> std::sync::MutexGuard<'static, ()> {
+    OQS_RNG.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Byte stream served to liboqs while [`OQS_RNG`] is held by a seeded keygen.
+#[cfg(feature = "pq")]
+static SEED_STREAM: std::sync::Mutex<Option<sha3::Shake256Reader>> = std::sync::Mutex::new(None);
+
+#[cfg(feature = "pq")]
+unsafe extern "C" fn seeded_randombytes(buf: *mut u8, len: usize) {
+    use sha3::digest::XofReader;
+
+    let out = std::slice::from_raw_parts_mut(buf, len);
+    match SEED_STREAM.lock().ok().as_mut().and_then(|g| g.as_mut()) {
+        Some(stream) => stream.read(out),
+        // Not inside a seeded keygen (a liboqs call from outside this SDK): stay random.
+        None => crate::utils::random::random_bytes(out),
+    }
+}
+
+/// Generate an `alg` keypair with liboqs' randomness replaced by
+/// SHAKE-256(b"animica-pq-keygen-v1" || seed), so equal seeds give equal keys.
+///
+/// Every SDK entry point into liboqs randomness takes [`OQS_RNG`], so
+/// concurrent `generate()`/`sign()` calls wait instead of reading the seeded
+/// stream. liboqs use outside this SDK in the same process is not covered.
+#[cfg(feature = "pq")]
+fn keypair_from_seed(alg: Algorithm, seed: &[u8; 32]) -> Result<(Vec<u8>, Vec<u8>)> {
+    use oqs_sys::rand::{OQS_randombytes_custom_algorithm, OQS_randombytes_switch_algorithm};
+    use sha3::digest::{ExtendableOutput, Update};
+
+    ensure_enabled(alg)?;
+    let sig = Sig::new(alg).map_err(map_oqs_err)?;
+    let _guard = lock_oqs_rng();
+
+    let mut xof = sha3::Shake256::default();
+    xof.update(b"animica-pq-keygen-v1");
+    xof.update(seed);
+    *SEED_STREAM.lock().unwrap_or_else(|e| e.into_inner()) = Some(xof.finalize_xof());
+
+    // SAFETY: the callback only touches `SEED_STREAM` and the buffer liboqs
+    // hands it; the system RNG is restored before the lock is released.
+    let keys = unsafe {
+        OQS_randombytes_custom_algorithm(Some(seeded_randombytes));
+        let keys = sig.keypair();
+        OQS_randombytes_switch_algorithm(b"system\0".as_ptr().cast());
+        keys
+    };
+    *SEED_STREAM.lock().unwrap_or_else(|e| e.into_inner()) = None;
+
+    let (pk, sk) = keys.map_err(map_oqs_err)?;
+    Ok((pk.into_vec(), sk.into_vec()))
+}
+
+/// Fresh `alg` keypair from the system RNG (under [`OQS_RNG`]).
+#[cfg(feature = "pq")]
+fn oqs_keypair(alg: Algorithm) -> Result<(Vec<u8>, Vec<u8>)> {
+    ensure_enabled(alg)?;
+    let sig = Sig::new(alg).map_err(map_oqs_err)?;
+    let (pk, sk) = {
+        let _guard = lock_oqs_rng();
+        sig.keypair().map_err(map_oqs_err)?
+    };
+    Ok((pk.into_vec(), sk.into_vec()))
+}
+
+/// Sign one message (see [`oqs_sign_batch`]).
+#[cfg(feature = "pq")]
+fn oqs_sign(alg: Algorithm, sk: &[u8], domain: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    let mut sigs = oqs_sign_batch(alg, sk, domain, &[message])?;
+    Ok(sigs.remove(0))
+}
+
+/// Sign a batch with one liboqs context and one secret-key import, holding
+/// [`OQS_RNG`] for the whole batch.
+#[cfg(feature = "pq")]
+fn oqs_sign_batch(alg: Algorithm, sk: &[u8], domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+    ensure_enabled(alg)?;
+    let sig = Sig::new(alg).map_err(map_oqs_err)?;
+    let sk: SecretKey = sig.secret_key_from_bytes(sk).map_err(map_oqs_err)?;
+    let _guard = lock_oqs_rng();
+    messages
+        .iter()
+        .map(|m| {
+            let digest = prehash(domain, m);
+            Ok(sig.sign(&digest, &sk).map_err(map_oqs_err)?.into_vec())
+        })
+        .collect()
+}
+
 #[cfg(feature = "pq")]
 fn ensure_enabled(alg: Algorithm) -> Result<()> {
     if !alg.is_enabled() {
@@ -73,16 +179,17 @@ impl Dilithium3Signer {
         }
     }
 
+    /// Deterministically derive a keypair from a 32-byte key seed
+    /// (e.g. [`crate::wallet::path::DerivationPath::key_seed`]).
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self> {
+        let (pk, sk) = keypair_from_seed(Self::algorithm(), seed)?;
+        Ok(Self { pk, sk })
+    }
+
     /// Generate a fresh keypair using liboqs.
     pub fn generate() -> Result<Self> {
-        let alg = Algorithm::Dilithium3;
-        ensure_enabled(alg)?;
-        let sig = Sig::new(alg).map_err(map_oqs_err)?;
-        let (pk, sk) = sig.keypair().map_err(map_oqs_err)?;
-        Ok(Self {
-            pk: pk.into_vec(),
-            sk: sk.into_vec(),
-        })
+        let (pk, sk) = oqs_keypair(Self::algorithm())?;
+        Ok(Self { pk, sk })
     }
 
     #[inline]
@@ -104,16 +211,11 @@ impl WalletSigner for Dilithium3Signer {
     }
 
     fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>> {
-        let alg = Self::algorithm();
-        ensure_enabled(alg)?;
-        let sig = Sig::new(alg).map_err(map_oqs_err)?;
-        // Import secret key bytes into liboqs
-        let sk: SecretKey = sig
-            .secret_key_from_bytes(&self.sk)
-            .map_err(map_oqs_err)?;
-        let digest = prehash(domain, message);
-        let signature = sig.sign(&digest, &sk).map_err(map_oqs_err)?;
-        Ok(signature.into_vec())
+        oqs_sign(Self::algorithm(), &self.sk, domain, message)
+    }
+
+    fn sign_batch(&self, domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        oqs_sign_batch(Self::algorithm(), &self.sk, domain, messages)
     }
 }
 
@@ -144,16 +246,17 @@ impl SphincsShake128sSigner {
         }
     }
 
+    /// Deterministically derive a keypair from a 32-byte key seed
+    /// (e.g. [`crate::wallet::path::DerivationPath::key_seed`]).
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self> {
+        let (pk, sk) = keypair_from_seed(Self::algorithm(), seed)?;
+        Ok(Self { pk, sk })
+    }
+
     /// Generate a fresh keypair using liboqs.
     pub fn generate() -> Result<Self> {
-        let alg = Algorithm::SphincsShake128sSimple;
-        ensure_enabled(alg)?;
-        let sig = Sig::new(alg).map_err(map_oqs_err)?;
-        let (pk, sk) = sig.keypair().map_err(map_oqs_err)?;
-        Ok(Self {
-            pk: pk.into_vec(),
-            sk: sk.into_vec(),
-        })
+        let (pk, sk) = oqs_keypair(Self::algorithm())?;
+        Ok(Self { pk, sk })
     }
 
     #[inline]
@@ -173,15 +276,87 @@ impl WalletSigner for SphincsShake128sSigner {
     }
 
     fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>> {
-        let alg = Self::algorithm();
-        ensure_enabled(alg)?;
-        let sig = Sig::new(alg).map_err(map_oqs_err)?;
-        let sk: SecretKey = sig
-            .secret_key_from_bytes(&self.sk)
-            .map_err(map_oqs_err)?;
+        oqs_sign(Self::algorithm(), &self.sk, domain, message)
+    }
+
+    fn sign_batch(&self, domain: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        oqs_sign_batch(Self::algorithm(), &self.sk, domain, messages)
+    }
+}
+
+/// Ed25519 signer (via ed25519-dalek). Not post-quantum; meant for testnets
+/// and tooling that doesn't need PQ signatures. Signs the same domain-separated
+/// prehash as the PQ signers.
+#[cfg(feature = "ed25519")]
+pub struct Ed25519Signer {
+    sk: ed25519_dalek::SigningKey,
+    pk: [u8; 32],
+}
+
+#[cfg(feature = "ed25519")]
+impl core::fmt::Debug for Ed25519Signer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Ed25519Signer")
+            .field("pk", &hex::encode(self.pk))
+            .finish()
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl Ed25519Signer {
+    /// Construct from a 32-byte secret seed (RFC 8032 private key).
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let sk = ed25519_dalek::SigningKey::from_bytes(&seed);
+        let pk = sk.verifying_key().to_bytes();
+        Self { sk, pk }
+    }
+
+    /// Generate a fresh keypair from the SDK CSPRNG.
+    pub fn generate() -> Result<Self> {
+        let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+        crate::utils::random::random_bytes(seed.as_mut());
+        Ok(Self::from_seed(*seed))
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl crate::wallet::WalletSigner for Ed25519Signer {
+    fn alg_id(&self) -> u16 {
+        ALG_ID_ED25519
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.pk.to_vec()
+    }
+
+    fn sign(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+        use ed25519_dalek::Signer;
+
         let digest = prehash(domain, message);
-        let signature = sig.sign(&digest, &sk).map_err(map_oqs_err)?;
-        Ok(signature.into_vec())
+        Ok(self.sk.sign(&digest).to_bytes().to_vec())
+    }
+}
+
+#[cfg(all(test, feature = "ed25519"))]
+mod ed25519_tests {
+    use super::*;
+    use crate::wallet::{Wallet, WalletSigner as _};
+
+    #[test]
+    fn ed25519_roundtrip_and_address() -> Result<()> {
+        let s = Ed25519Signer::from_seed([0x42; 32]);
+        let vk = ed25519_dalek::VerifyingKey::from_bytes(&s.pk).unwrap();
+        let sig = s.sign(b"sign-domain/tx", b"hello")?;
+        let sig = ed25519_dalek::Signature::from_slice(&sig).unwrap();
+        vk.verify_strict(&prehash(b"sign-domain/tx", b"hello"), &sig).unwrap();
+        assert!(vk.verify_strict(&prehash(b"sign-domain/other", b"hello"), &sig).is_err());
+
+        let w = Wallet::new(Ed25519Signer::from_seed([0x42; 32]))?;
+        assert!(w.address().starts_with("anim1"));
+        let (alg, _) = crate::address::validate_address(w.address())?;
+        assert_eq!(alg, ALG_ID_ED25519);
+        assert_ne!(Ed25519Signer::generate()?.pk, s.pk);
+        Ok(())
     }
 }
 
@@ -204,6 +379,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn from_seed_is_deterministic() -> Result<()> {
+        let a = Dilithium3Signer::from_seed(&[7u8; 32])?;
+        let b = Dilithium3Signer::from_seed(&[7u8; 32])?;
+        let c = Dilithium3Signer::from_seed(&[8u8; 32])?;
+        assert_eq!(a.public_key(), b.public_key());
+        assert_ne!(a.public_key(), c.public_key());
+        Ok(())
+    }
+
+    #[test]
+    fn from_seed_is_deterministic_under_concurrent_liboqs_use() -> Result<()> {
+        let expected = Dilithium3Signer::from_seed(&[9u8; 32])?.public_key().to_vec();
+        let signer = Dilithium3Signer::generate()?;
+        let stop = std::sync::atomic::AtomicBool::new(false);
+
+        std::thread::scope(|s| -> Result<()> {
+            // Background noise: fresh keys and signatures on other threads.
+            for _ in 0..2 {
+                s.spawn(|| {
+                    while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+                        Dilithium3Signer::generate().unwrap();
+                        signer.sign(TEST_DOMAIN, b"noise").unwrap();
+                    }
+                });
+            }
+            let seeded: Vec<_> = (0..4)
+                .map(|_| s.spawn(|| Dilithium3Signer::from_seed(&[9u8; 32]).map(|k| k.public_key().to_vec())))
+                .collect();
+            let results: Vec<_> = seeded.into_iter().map(|h| h.join().unwrap()).collect();
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+            for pk in results {
+                assert_eq!(pk?, expected);
+            }
+            Ok(())
+        })
+    }
+
     #[test]
     fn sphincs_roundtrip() -> Result<()> {
         let s = SphincsShake128sSigner::generate()?;
@@ -46,8 +46,7 @@ async fn main() -> Result<()> {
 
     // Load config from env & build client
     let cfg = Config::from_env()?;
-    let client = NodeClient::new(cfg.clone())?;
-    client.assert_chain_id().await?;
+    let client = NodeClient::connect(cfg.clone()).await?;
 
     // Read manifest JSON
     let manifest_raw = fs::read_to_string(&args.manifest)
@@ -1,21 +1,38 @@
 //! {{crate_name}} — a tiny convenience wrapper around `animica-sdk`
 //!
 //! This library is meant for starter projects. It provides:
-//! - A `Config` loader (env → strongly typed)
-//! - A minimal `NodeClient` with handy helpers for common RPCs
-//! - A polling `await_receipt` utility for quick demos
+//! - A `Config` loader (env and/or a TOML/JSON file → strongly typed)
+//! - A minimal `NodeClient` with handy helpers for common RPCs, optionally
+//!   failing over across several endpoints (`with_endpoints`)
+//! - A polling `await_receipt` utility for quick demos (`await_receipts` for many,
+//!   `await_receipt_confirmed` to wait out reorgs)
+//!   on a pluggable `Clock` (`MockClock` makes timeouts testable without sleeps)
+//! - `blocks_in_range` for indexers: parallel fetches, yielded in height order
+//! - A `simulate_transaction` dry run for pre-flight checks
+//! - A one-call `estimate_fee` (gas × priority-adjusted price), the node's own
+//!   `quote_fee` for an encoded tx, and `fee_history` (base fees + priority
+//!   percentiles) for wallets
+//! - `get_nonce` plus a shared `NonceManager` for firing several txs per block
+//! - Canonical transaction CBOR (`tx::encode_unsigned` / `tx::encode_signed`)
+//! - Typed `newHeads` streams (`ws` feature) for live dashboards, and a
+//!   reconnecting `ws::HeadFollower` for long-running chain watchers
 //!
 //! You can grow this crate in any direction: add higher-level flows,
 //! contract-specific clients (codegen), indexing helpers, etc.
 
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context, Result};
+use futures::stream::{self, Stream, StreamExt};
 use serde::de::DeserializeOwned;
 use serde_json::json;
 use tracing::{debug, info, instrument};
 
 use animica_sdk::{
+    abi::Abi,
     // The Rust SDK exposes HTTP & WS clients and typed core objects.
     rpc::http::Client as HttpClient,
     types, // re-exported structs (Tx/Receipt/Block/Head, etc.)
@@ -28,8 +45,14 @@ use animica_sdk::{
 /// use {{crate_name}}::prelude::*;
 /// ```
 pub mod prelude {
-    pub use super::{Config, NodeClient};
-    pub use animica_sdk::types;
+    pub use super::tx::TxRequest;
+    pub use super::{Clock, Config, FeeEstimate, FeeHistory, FeeQuote, MockClock, NodeClient, NonceManager, Priority, ReceiptError, SimResult, SystemClock};
+
+    /// The SDK's core types, plus the starter's [`FeeQuote`](crate::FeeQuote).
+    pub mod types {
+        pub use animica_sdk::types::*;
+        pub use crate::FeeQuote;
+    }
 }
 
 /// Basic runtime configuration for your app.
@@ -43,6 +66,9 @@ pub struct Config {
     pub chain_id: u64,
     /// Default request timeout applied by the underlying client where supported.
     pub default_timeout: Duration,
+    /// Lowest gas price `suggest_gas_price` returns, and its answer when the
+    /// node can't quote one. `None` leaves node prices and errors untouched.
+    pub gas_price_floor: Option<u128>,
 }
 
 impl Config {
@@ -52,42 +78,349 @@ impl Config {
     /// - `ANIMICA_RPC_URL` or `RPC_URL`
     /// - `ANIMICA_CHAIN_ID` or `CHAIN_ID`
     /// - `ANIMICA_TIMEOUT_SECS` (optional; default 20)
+    /// - `ANIMICA_GAS_PRICE_FLOOR` (optional; decimal)
     pub fn from_env() -> Result<Self> {
-        // Load .env if present (no error if missing)
-        let _ = dotenvy::dotenv();
+        Self::resolve(FileConfig::default())
+    }
 
-        fn getenv(keys: &[&str]) -> Option<String> {
-            for k in keys {
-                if let Ok(v) = std::env::var(k) {
-                    if !v.trim().is_empty() {
-                        return Some(v);
-                    }
-                }
-            }
-            None
+    /// Load config from a `.toml` or `.json` file, with the environment
+    /// variables of [`Config::from_env`] overriding any value they set.
+    ///
+    /// Recognized keys: `rpc_url`, `chain_id`, `timeout_secs` and
+    /// `gas_price_floor`; anything else is rejected by name.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read config file {}", path.display()))?;
+        let doc: serde_json::Value = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&text)
+                .with_context(|| format!("Invalid TOML in {}", path.display()))?,
+            Some("json") => serde_json::from_str(&text)
+                .with_context(|| format!("Invalid JSON in {}", path.display()))?,
+            _ => return Err(anyhow!("Unsupported config file type (want .toml or .json): {}", path.display())),
+        };
+        let file = FileConfig::from_doc(&doc).with_context(|| format!("Bad config file {}", path.display()))?;
+        Self::resolve(file)
+    }
+
+    /// `from_file` on `ANIMICA_CONFIG` when it is set, otherwise `from_env`.
+    pub fn load() -> Result<Self> {
+        match getenv(&["ANIMICA_CONFIG"]) {
+            Some(path) => Self::from_file(path),
+            None => Self::from_env(),
         }
+    }
+
+    /// Environment first, then `file`, then defaults.
+    fn resolve(file: FileConfig) -> Result<Self> {
+        // Load .env if present (no error if missing)
+        let _ = dotenvy::dotenv();
 
         let rpc_url = getenv(&["ANIMICA_RPC_URL", "RPC_URL"])
+            .or(file.rpc_url)
             .context("Missing RPC URL (set ANIMICA_RPC_URL or RPC_URL)")?;
 
-        let chain_id_str = getenv(&["ANIMICA_CHAIN_ID", "CHAIN_ID"])
-            .context("Missing CHAIN_ID (set ANIMICA_CHAIN_ID or CHAIN_ID)")?;
-        let chain_id = chain_id_str
-            .parse::<u64>()
-            .with_context(|| format!("Invalid CHAIN_ID: {chain_id_str}"))?;
+        let chain_id = match getenv(&["ANIMICA_CHAIN_ID", "CHAIN_ID"]) {
+            Some(s) => s.parse::<u64>().with_context(|| format!("Invalid CHAIN_ID: {s}"))?,
+            None => file
+                .chain_id
+                .context("Missing CHAIN_ID (set ANIMICA_CHAIN_ID or CHAIN_ID)")?,
+        };
 
         let timeout_secs = getenv(&["ANIMICA_TIMEOUT_SECS"])
             .and_then(|s| s.parse::<u64>().ok())
+            .or(file.timeout_secs)
             .unwrap_or(20);
 
+        let gas_price_floor = match getenv(&["ANIMICA_GAS_PRICE_FLOOR"]) {
+            Some(s) => Some(
+                s.trim()
+                    .parse::<u128>()
+                    .with_context(|| format!("Invalid ANIMICA_GAS_PRICE_FLOOR: {s}"))?,
+            ),
+            None => file.gas_price_floor,
+        };
+
         Ok(Self {
             rpc_url,
             chain_id,
             default_timeout: Duration::from_secs(timeout_secs),
+            gas_price_floor,
         })
     }
 }
 
+/// First non-empty variable among `keys`.
+fn getenv(keys: &[&str]) -> Option<String> {
+    for k in keys {
+        if let Ok(v) = std::env::var(k) {
+            if !v.trim().is_empty() {
+                return Some(v);
+            }
+        }
+    }
+    None
+}
+
+/// Values read by `Config::from_file`, before env overrides.
+#[derive(Default)]
+struct FileConfig {
+    rpc_url: Option<String>,
+    chain_id: Option<u64>,
+    timeout_secs: Option<u64>,
+    gas_price_floor: Option<u128>,
+}
+
+impl FileConfig {
+    fn from_doc(doc: &serde_json::Value) -> Result<Self> {
+        let table = doc.as_object().context("config root must be a table")?;
+        let mut out = Self::default();
+        for (key, v) in table {
+            let bad = |want: &str| anyhow!("config key `{key}`: expected {want}, got {v}");
+            match key.as_str() {
+                "rpc_url" => out.rpc_url = Some(v.as_str().ok_or_else(|| bad("a string"))?.to_string()),
+                "chain_id" => out.chain_id = Some(v.as_u64().ok_or_else(|| bad("an unsigned integer"))?),
+                "timeout_secs" => out.timeout_secs = Some(v.as_u64().ok_or_else(|| bad("an unsigned integer"))?),
+                "gas_price_floor" => {
+                    out.gas_price_floor = Some(json_quantity(v).ok_or_else(|| bad("an unsigned integer"))?)
+                }
+                _ => return Err(anyhow!("unknown config key `{key}`")),
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Why `await_receipt_confirmed` gave up; carried inside the `anyhow::Error`
+/// (use `downcast_ref::<ReceiptError>()`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReceiptError {
+    /// The receipt's block left the canonical chain, or the receipt vanished
+    /// after having been seen. The tx may need re-sending.
+    Reorged { tx_hash: String, block_number: u64, block_hash: String },
+    /// Not confirmed within the timeout.
+    Timeout { tx_hash: String },
+}
+
+impl std::fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reorged { tx_hash, block_number, block_hash } => {
+                write!(f, "tx {tx_hash} reorged out of block {block_number} ({block_hash})")
+            }
+            Self::Timeout { tx_hash } => write!(f, "timed out waiting for confirmed receipt: {tx_hash}"),
+        }
+    }
+}
+
+impl std::error::Error for ReceiptError {}
+
+/// Outcome of a `tx.simulate` dry run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimResult {
+    /// Whether execution would succeed.
+    pub success: bool,
+    /// Gas consumed by the simulated execution.
+    pub gas_used: u64,
+    /// Raw return data (or revert payload on failure).
+    pub return_data: Vec<u8>,
+    /// Decoded revert reason, when the payload is recognizable.
+    pub revert_reason: Option<String>,
+}
+
+/// Fee priority; maps to a gas-price multiplier from chain params.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Priority {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl Priority {
+    /// Key under `priorityMultipliers` in `chain.getParams`.
+    fn param_key(self) -> &'static str {
+        match self {
+            Priority::Slow => "slow",
+            Priority::Normal => "normal",
+            Priority::Fast => "fast",
+        }
+    }
+
+    /// Multiplier (in percent) used when the chain params don't define one.
+    fn default_percent(self) -> u128 {
+        match self {
+            Priority::Slow => 90,
+            Priority::Normal => 100,
+            Priority::Fast => 125,
+        }
+    }
+}
+
+/// Result of `estimate_fee`: gas units, priority-adjusted price, and their product.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeEstimate {
+    pub gas: u64,
+    pub gas_price: u128,
+    /// `gas * gas_price`, the most this transaction should cost.
+    pub max_fee: u128,
+    pub priority: Priority,
+}
+
+/// The node's own fee quote for an encoded transaction, from `quote_fee`.
+///
+/// Unlike [`FeeEstimate`] (assembled SDK-side from gas and price lookups),
+/// this is what `tx.estimateFee` reports for the exact bytes to be sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeQuote {
+    pub gas_limit: u64,
+    pub gas_price: u128,
+    /// `gas_limit * gas_price` unless the node reports its own total.
+    pub total: u128,
+}
+
+impl FeeQuote {
+    /// Parse a `tx.estimateFee` response (camelCase or snake_case keys).
+    ///
+    /// Quantities may be `0x`-hex strings, decimal strings, or JSON numbers.
+    pub fn from_json(v: &serde_json::Value) -> Result<Self> {
+        let field = |camel: &str, snake: &str| v.get(camel).or_else(|| v.get(snake));
+        let gas_limit = field("gasLimit", "gas_limit")
+            .and_then(json_quantity)
+            .and_then(|g| u64::try_from(g).ok())
+            .with_context(|| format!("tx.estimateFee: bad gasLimit in {v}"))?;
+        let gas_price = field("gasPrice", "gas_price")
+            .and_then(json_quantity)
+            .with_context(|| format!("tx.estimateFee: bad gasPrice in {v}"))?;
+        let total = match field("total", "total") {
+            Some(t) => json_quantity(t).with_context(|| format!("tx.estimateFee: bad total {t}"))?,
+            None => gas_price.checked_mul(u128::from(gas_limit)).context("fee overflow")?,
+        };
+        Ok(Self { gas_limit, gas_price, total })
+    }
+}
+
+/// Recent fee market history from `fee_history`, oldest block first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeHistory {
+    /// Height of the first block covered.
+    pub oldest_block: u64,
+    /// Base fee per block; one extra trailing entry is the next block's base fee.
+    pub base_fee_per_gas: Vec<u128>,
+    /// Fraction of the gas limit used by each block (0.0–1.0).
+    pub gas_used_ratio: Vec<f64>,
+    /// Per-block priority fees at each requested percentile (empty if none requested).
+    pub reward: Vec<Vec<u128>>,
+}
+
+impl FeeHistory {
+    /// Parse a `chain.feeHistory` response (camelCase or snake_case keys).
+    ///
+    /// Quantities may be `0x`-hex strings, decimal strings, or JSON numbers.
+    pub fn from_json(v: &serde_json::Value) -> Result<Self> {
+        fn field<'a>(v: &'a serde_json::Value, camel: &str, snake: &str) -> Option<&'a serde_json::Value> {
+            v.get(camel).or_else(|| v.get(snake))
+        }
+        fn quantities(v: Option<&serde_json::Value>, what: &str) -> Result<Vec<u128>> {
+            v.and_then(|v| v.as_array())
+                .with_context(|| format!("feeHistory: missing {what}"))?
+                .iter()
+                .map(|q| json_quantity(q).with_context(|| format!("feeHistory: bad {what} entry {q}")))
+                .collect()
+        }
+
+        let oldest_block = field(v, "oldestBlock", "oldest_block")
+            .and_then(json_quantity)
+            .and_then(|n| u64::try_from(n).ok())
+            .context("feeHistory: bad oldestBlock")?;
+        let base_fee_per_gas = quantities(field(v, "baseFeePerGas", "base_fee_per_gas"), "baseFeePerGas")?;
+        let gas_used_ratio = field(v, "gasUsedRatio", "gas_used_ratio")
+            .and_then(|r| r.as_array())
+            .context("feeHistory: missing gasUsedRatio")?
+            .iter()
+            .map(|r| r.as_f64().with_context(|| format!("feeHistory: bad gasUsedRatio entry {r}")))
+            .collect::<Result<Vec<_>>>()?;
+        let reward = match v.get("reward") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(rows) => rows
+                .as_array()
+                .context("feeHistory: reward is not an array")?
+                .iter()
+                .map(|row| quantities(Some(row), "reward"))
+                .collect::<Result<_>>()?,
+        };
+        Ok(Self { oldest_block, base_fee_per_gas, gas_used_ratio, reward })
+    }
+}
+
+/// Time source for polling helpers (`await_receipt`, `wait_for_heads`, ...).
+///
+/// `NodeClient` uses [`SystemClock`] unless told otherwise; tests can inject a
+/// [`MockClock`] via [`NodeClient::with_clock`] to exercise timeouts without
+/// real sleeps.
+pub trait Clock: Send + Sync + 'static {
+    /// Current instant.
+    fn now(&self) -> Instant;
+    /// Wait for `d` to pass on this clock.
+    fn sleep(&self, d: Duration) -> futures::future::BoxFuture<'static, ()>;
+}
+
+/// Wall-clock time and `tokio::time::sleep`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, d: Duration) -> futures::future::BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(d))
+    }
+}
+
+/// Manually driven clock: `sleep` returns at once after advancing the clock by
+/// the requested duration. Clones share the same time.
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// A clock starting at the current instant that only moves when told to.
+    pub fn new() -> Self {
+        Self { start: Instant::now(), elapsed: Arc::new(Mutex::new(Duration::ZERO)) }
+    }
+
+    /// Move the clock forward by `d`.
+    pub fn advance(&self, d: Duration) {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner()) += d;
+    }
+
+    /// Total time advanced so far.
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn sleep(&self, d: Duration) -> futures::future::BoxFuture<'static, ()> {
+        self.advance(d);
+        Box::pin(std::future::ready(()))
+    }
+}
+
 /// Thin, async JSON-RPC client built on top of `animica-sdk`.
 ///
 /// This wrapper provides a few typed helpers and a generic `call` method
@@ -95,24 +428,150 @@ impl Config {
 #[derive(Clone)]
 pub struct NodeClient {
     cfg: Config,
-    http: HttpClient,
+    /// RPC endpoints in priority order (shared across clones), see `with_endpoints`.
+    endpoints: Arc<Vec<Endpoint>>,
+    /// How long an endpoint is skipped after a retryable failure.
+    failover_cooldown: Duration,
+    /// Memoized `chain.getParams` result (shared across clones), see `cached_params`.
+    params_cache: Arc<Mutex<Option<(Instant, serde_json::Value)>>>,
+    /// Chain ID confirmed by `assert_chain_id` (shared across clones).
+    verified_chain_id: Arc<OnceLock<u64>>,
+    /// Time source for polling and cache expiry, see `with_clock`.
+    clock: Arc<dyn Clock>,
 }
 
 impl std::fmt::Debug for NodeClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let urls: Vec<&str> = self.endpoints.iter().map(|e| e.url.as_str()).collect();
         f.debug_struct("NodeClient")
-            .field("rpc_url", &self.cfg.rpc_url)
+            .field("endpoints", &urls)
             .field("chain_id", &self.cfg.chain_id)
             .finish()
     }
 }
 
+/// One RPC endpoint and its failover state.
+struct Endpoint {
+    url: String,
+    http: HttpClient,
+    /// Set after a retryable failure; the endpoint is skipped until then.
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    fn is_up(&self, now: Instant) -> bool {
+        let down_until = self.down_until.lock().unwrap_or_else(|e| e.into_inner());
+        down_until.is_none_or(|t| now >= t)
+    }
+
+    fn mark(&self, down_until: Option<Instant>) {
+        *self.down_until.lock().unwrap_or_else(|e| e.into_inner()) = down_until;
+    }
+}
+
 impl NodeClient {
     /// Build a new client from a `Config`.
+    ///
+    /// Makes no network calls, so it works offline; the chain ID is not
+    /// checked until [`NodeClient::assert_chain_id`]. Prefer
+    /// [`NodeClient::connect`] when a node is reachable.
     pub fn new(cfg: Config) -> Result<Self> {
-        let http = HttpClient::new(&cfg.rpc_url)
-            .with_timeout(cfg.default_timeout);
-        Ok(Self { cfg, http })
+        let url = cfg.rpc_url.clone();
+        Self::with_endpoints(vec![url], cfg)
+    }
+
+    /// Build a client that fails over across `endpoints`, highest priority first.
+    ///
+    /// Every call (the generic `call` and all typed helpers) goes to the first
+    /// healthy endpoint. When that fails with a retryable error (transport,
+    /// 5xx, 429) after the HTTP client's own retries, the endpoint is put on a
+    /// cooldown (30s, see `with_failover_cooldown`) and the same call is
+    /// re-sent to the next one. If every endpoint is cooling down, all are
+    /// tried in order anyway. `cfg.rpc_url` is replaced by the first endpoint.
+    pub fn with_endpoints(endpoints: Vec<String>, mut cfg: Config) -> Result<Self> {
+        let primary = endpoints.first().context("with_endpoints: no endpoints given")?;
+        cfg.rpc_url = primary.clone();
+        let endpoints = endpoints
+            .into_iter()
+            .map(|url| Endpoint {
+                http: HttpClient::new(&url).with_timeout(cfg.default_timeout),
+                url,
+                down_until: Mutex::new(None),
+            })
+            .collect();
+        Ok(Self {
+            cfg,
+            endpoints: Arc::new(endpoints),
+            failover_cooldown: Duration::from_secs(30),
+            params_cache: Arc::new(Mutex::new(None)),
+            verified_chain_id: Arc::new(OnceLock::new()),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Replace the time source used by polling helpers and `cached_params`
+    /// (default: [`SystemClock`]).
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// How long an endpoint that failed is skipped (default 30s).
+    pub fn with_failover_cooldown(mut self, cooldown: Duration) -> Self {
+        self.failover_cooldown = cooldown;
+        self
+    }
+
+    /// Endpoints not currently cooling down after a failure, in priority order.
+    pub fn healthy_endpoints(&self) -> Vec<String> {
+        let now = self.clock.now();
+        self.endpoints.iter().filter(|e| e.is_up(now)).map(|e| e.url.clone()).collect()
+    }
+
+    /// Run `op` against the first healthy endpoint, moving down the list on
+    /// retryable errors. Returns the last error if no endpoint succeeds.
+    async fn with_failover<T, F, Fut>(&self, op: F) -> Result<T, animica_sdk::error::Error>
+    where
+        F: Fn(HttpClient) -> Fut,
+        Fut: std::future::Future<Output = Result<T, animica_sdk::error::Error>>,
+    {
+        let now = self.clock.now();
+        let (up, down): (Vec<&Endpoint>, Vec<&Endpoint>) =
+            self.endpoints.iter().partition(|e| e.is_up(now));
+        let order = if up.is_empty() { down } else { up };
+        let mut last_err = None;
+        for ep in order {
+            match op(ep.http.clone()).await {
+                Ok(v) => {
+                    ep.mark(None);
+                    return Ok(v);
+                }
+                Err(e) if e.is_retryable() => {
+                    debug!(endpoint = %ep.url, error = %e, "rpc endpoint failed; failing over");
+                    ep.mark(Some(self.clock.now() + self.failover_cooldown));
+                    last_err = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_err.expect("with_endpoints guarantees at least one endpoint"))
+    }
+
+    /// Build a client and verify up front that the node serves `cfg.chain_id`.
+    ///
+    /// Fails with [`animica_sdk::error::Error::ChainIdMismatch`] (inside the
+    /// `anyhow::Error`) when pointed at the wrong chain, instead of letting the
+    /// first wrong-chain call fail deep in app logic.
+    pub async fn connect(cfg: Config) -> Result<Self> {
+        let client = Self::new(cfg)?;
+        client.assert_chain_id().await?;
+        Ok(client)
+    }
+
+    /// Chain ID confirmed against the node, if `connect` / `assert_chain_id`
+    /// has succeeded.
+    pub fn verified_chain_id(&self) -> Option<u64> {
+        self.verified_chain_id.get().copied()
     }
 
     /// Access the loaded configuration.
@@ -132,8 +591,10 @@ impl NodeClient {
     ) -> Result<T> {
         debug!(%method, "rpc.call");
         let out: T = self
-            .http
-            .call(method, params)
+            .with_failover(|http| {
+                let params = params.clone();
+                async move { http.call(method, params).await }
+            })
             .await
             .with_context(|| format!("RPC call failed: {method}"))?;
         Ok(out)
@@ -144,21 +605,110 @@ impl NodeClient {
         self.call("chain.getParams", json!([])).await
     }
 
+    /// Like `get_params`, but memoized for `ttl`.
+    ///
+    /// Chain params rarely change, so fee/gas lookups can call this freely; the
+    /// node is only queried again once the cached value is older than `ttl`.
+    /// Failed fetches are not cached.
+    pub async fn cached_params(&self, ttl: Duration) -> Result<serde_json::Value> {
+        {
+            let guard = self.params_cache.lock().map_err(|_| anyhow!("params cache poisoned"))?;
+            if let Some((fetched_at, params)) = guard.as_ref() {
+                if self.clock.now().duration_since(*fetched_at) < ttl {
+                    return Ok(params.clone());
+                }
+            }
+        }
+        // Lock is not held across the await; concurrent misses may both fetch.
+        let params = self.get_params().await?;
+        let mut guard = self.params_cache.lock().map_err(|_| anyhow!("params cache poisoned"))?;
+        *guard = Some((self.clock.now(), params.clone()));
+        Ok(params)
+    }
+
     /// Get the current head summary: height, hash, parent, timestamp, etc.
     pub async fn get_head(&self) -> Result<types::Head> {
         self.call("chain.getHead", json!([])).await
     }
 
+    /// Untyped `chain.getHead`, for when [`types::Head`] lags the node's shape.
+    pub async fn get_head_raw(&self) -> Result<serde_json::Value> {
+        self.call("chain.getHead", json!([])).await
+    }
+
+    /// Untyped `tx.getTransactionByHash`; `None` if the node doesn't know the tx.
+    pub async fn get_transaction_raw(&self, tx_hash: &str) -> Result<Option<serde_json::Value>> {
+        self.call("tx.getTransactionByHash", json!([tx_hash])).await
+    }
+
+    /// Untyped `chain.getBlockByHeight`; `None` past the current head.
+    pub async fn get_block_raw(&self, number: u64) -> Result<Option<serde_json::Value>> {
+        self.call("chain.getBlockByHeight", json!([number])).await
+    }
+
+    /// Stream blocks `from..=to` in ascending height order.
+    ///
+    /// Up to `concurrency` `chain.getBlockByHeight` fetches run at once, each
+    /// through the HTTP client's retry policy, so only errors that survive the
+    /// retries (or a height the node doesn't have) reach the stream. The stream
+    /// ends after yielding the first such error.
+    pub fn blocks_in_range(
+        &self,
+        from: u64,
+        to: u64,
+        concurrency: usize,
+    ) -> Result<impl Stream<Item = Result<types::Block>> + Send + 'static> {
+        if from > to {
+            return Err(anyhow!("invalid block range: from {from} > to {to}"));
+        }
+        if concurrency == 0 {
+            return Err(anyhow!("concurrency must be > 0"));
+        }
+        let client = self.clone();
+        Ok(stream::iter(from..=to)
+            .map(move |height| {
+                let client = client.clone();
+                async move {
+                    let block: Option<types::Block> =
+                        client.call("chain.getBlockByHeight", json!([height])).await?;
+                    block.with_context(|| format!("block {height} not found"))
+                }
+            })
+            .buffered(concurrency)
+            .scan(false, |failed, item| {
+                let out = (!*failed).then(|| {
+                    *failed = item.is_err();
+                    item
+                });
+                futures::future::ready(out)
+            }))
+    }
+
     /// Resolve the node's chain ID via RPC and assert it matches our config.
+    ///
+    /// Once verified the ID is cached, so later calls skip the RPC. A mismatch
+    /// is an [`animica_sdk::error::Error::ChainIdMismatch`].
     pub async fn assert_chain_id(&self) -> Result<u64> {
+        if let Some(&id) = self.verified_chain_id.get() {
+            return Ok(id);
+        }
         let id: u64 = self.call("chain.getChainId", json!([])).await?;
         if id != self.cfg.chain_id {
-            return Err(anyhow!(
-                "ChainId mismatch: node reports {id}, config expects {}",
-                self.cfg.chain_id
-            ));
+            return Err(animica_sdk::error::Error::ChainIdMismatch {
+                expected: self.cfg.chain_id,
+                got: id,
+            }
+            .into());
         }
-        Ok(id)
+        Ok(*self.verified_chain_id.get_or_init(|| id))
+    }
+
+    /// Next nonce for `address` as the node sees it, via `account.getNonce`.
+    pub async fn get_nonce(&self, address: &str) -> Result<u64> {
+        let v: serde_json::Value = self.call("account.getNonce", json!([address])).await?;
+        json_quantity(&v)
+            .and_then(|n| u64::try_from(n).ok())
+            .with_context(|| format!("account.getNonce: bad quantity {v}"))
     }
 
     /// Submit a raw (CBOR-encoded) transaction blob.
@@ -175,6 +725,151 @@ impl NodeClient {
         Ok(tx_hash)
     }
 
+    /// Dry-run a transaction via `tx.simulate` without submitting it.
+    ///
+    /// `tx` is the same JSON shape you would sign and send. Revert reasons are
+    /// decoded when they use the standard `Error(string)` payload; use
+    /// `simulate_transaction_with_abi` for contract-specific errors.
+    pub async fn simulate_transaction(&self, tx: &serde_json::Value) -> Result<SimResult> {
+        self.simulate(tx, None).await
+    }
+
+    /// Like `simulate_transaction`, decoding reverts against the contract's ABI errors.
+    pub async fn simulate_transaction_with_abi(
+        &self,
+        tx: &serde_json::Value,
+        abi: &Abi,
+    ) -> Result<SimResult> {
+        self.simulate(tx, Some(abi)).await
+    }
+
+    async fn simulate(&self, tx: &serde_json::Value, abi: Option<&Abi>) -> Result<SimResult> {
+        let v: serde_json::Value = self
+            .call("tx.simulate", json!([tx]))
+            .await
+            .context("tx.simulate failed")?;
+
+        let success = v
+            .get("success")
+            .or_else(|| v.get("ok"))
+            .and_then(|s| s.as_bool())
+            .context("tx.simulate: missing success flag")?;
+        let gas_used = match v.get("gasUsed").or_else(|| v.get("gas_used")) {
+            Some(g) => json_quantity(g)
+                .and_then(|g| u64::try_from(g).ok())
+                .context("tx.simulate: bad gasUsed")?,
+            None => 0,
+        };
+        let return_data = match v.get("returnData").or_else(|| v.get("return")).and_then(|d| d.as_str()) {
+            Some(s) => hex::decode(s).map_err(|e| anyhow!("tx.simulate: bad returnData: {e}"))?,
+            None => Vec::new(),
+        };
+        let revert_reason = if success {
+            None
+        } else {
+            match abi {
+                Some(abi) => abi.decode_revert(&return_data),
+                None => animica_sdk::abi::decode_error_string(&return_data),
+            }
+        };
+        Ok(SimResult { success, gas_used, return_data, revert_reason })
+    }
+
+    /// Estimate gas units for `tx` via `tx.estimateGas`.
+    pub async fn estimate_gas(&self, tx: &serde_json::Value) -> Result<u64> {
+        let v: serde_json::Value = self.call("tx.estimateGas", json!([tx])).await?;
+        json_quantity(&v)
+            .and_then(|g| u64::try_from(g).ok())
+            .with_context(|| format!("tx.estimateGas: bad quantity {v}"))
+    }
+
+    /// Node-suggested gas price via `chain.getGasPrice`, raised to
+    /// `Config::gas_price_floor` if set.
+    ///
+    /// With a floor configured, a failed or unparsable lookup yields the floor
+    /// instead of an error, so demos keep working against minimal nodes.
+    pub async fn suggest_gas_price(&self) -> Result<u128> {
+        let quoted = async {
+            let v: serde_json::Value = self.call("chain.getGasPrice", json!([])).await?;
+            json_quantity(&v).with_context(|| format!("chain.getGasPrice: bad quantity {v}"))
+        }
+        .await;
+        match (quoted, self.cfg.gas_price_floor) {
+            (Ok(price), floor) => Ok(price.max(floor.unwrap_or(0))),
+            (Err(e), Some(floor)) => {
+                debug!(error = %e, floor, "gas price lookup failed; using floor");
+                Ok(floor)
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    /// Node-side fee quote for an encoded transaction via `tx.estimateFee`.
+    ///
+    /// `raw_cbor` is the same blob you would pass to `send_raw_transaction`
+    /// (e.g. from [`tx::encode_signed`]).
+    pub async fn quote_fee(&self, raw_cbor: &[u8]) -> Result<FeeQuote> {
+        let hex = format!("0x{}", hex::encode(raw_cbor));
+        let v: serde_json::Value = self.call("tx.estimateFee", json!([hex])).await?;
+        FeeQuote::from_json(&v)
+    }
+
+    /// Base fees and priority-fee percentiles over the last `block_count` blocks
+    /// via `chain.feeHistory` (an `eth_feeHistory`-style call ending at the head).
+    ///
+    /// `reward_percentiles` must be ascending values in `0.0..=100.0`; pass `&[]`
+    /// to skip rewards. Handy for wallet fee sliders.
+    pub async fn fee_history(&self, block_count: u64, reward_percentiles: &[f64]) -> Result<FeeHistory> {
+        if block_count == 0 {
+            return Err(anyhow!("block_count must be > 0"));
+        }
+        if reward_percentiles.iter().any(|p| !(0.0..=100.0).contains(p))
+            || reward_percentiles.windows(2).any(|w| w[0] > w[1])
+        {
+            return Err(anyhow!("reward_percentiles must be ascending within 0..=100"));
+        }
+        let v: serde_json::Value = self
+            .call("chain.feeHistory", json!([block_count, "latest", reward_percentiles]))
+            .await?;
+        FeeHistory::from_json(&v)
+    }
+
+    /// `estimate_fee_with_priority` at `Priority::Normal`.
+    pub async fn estimate_fee(&self, tx: &serde_json::Value) -> Result<FeeEstimate> {
+        self.estimate_fee_with_priority(tx, Priority::Normal).await
+    }
+
+    /// Estimate gas and price for `tx` and combine them into a total fee.
+    ///
+    /// The suggested price is scaled by the priority's multiplier from chain
+    /// params (`priorityMultipliers.{slow,normal,fast}`, e.g. `1.25`), falling
+    /// back to 0.9 / 1.0 / 1.25. Params come from `cached_params`.
+    pub async fn estimate_fee_with_priority(
+        &self,
+        tx: &serde_json::Value,
+        priority: Priority,
+    ) -> Result<FeeEstimate> {
+        let gas = self.estimate_gas(tx).await?;
+        let base_price = self.suggest_gas_price().await?;
+        let params = self.cached_params(Duration::from_secs(60)).await?;
+
+        let percent = params
+            .get("priorityMultipliers")
+            .and_then(|m| m.get(priority.param_key()))
+            .and_then(|m| m.as_f64())
+            .filter(|m| m.is_finite() && *m > 0.0)
+            .map(|m| (m * 100.0).round() as u128)
+            .unwrap_or_else(|| priority.default_percent());
+        let gas_price = base_price
+            .checked_mul(percent)
+            .context("gas price overflow")?
+            / 100;
+        let max_fee = gas_price
+            .checked_mul(u128::from(gas))
+            .context("fee overflow")?;
+        Ok(FeeEstimate { gas, gas_price, max_fee, priority })
+    }
+
     /// Fetch a transaction receipt by hash. Returns `None` if not yet available.
     pub async fn get_receipt(&self, tx_hash: &str) -> Result<Option<types::Receipt>> {
         // Most nodes return `null` until the receipt is available.
@@ -186,6 +881,14 @@ impl NodeClient {
         Ok(receipt_opt)
     }
 
+    /// Like [`NodeClient::get_receipt`] but returns the JSON as sent by the
+    /// node, so callers can fall back to it when typed decoding fails.
+    pub async fn get_receipt_raw(&self, tx_hash: &str) -> Result<Option<serde_json::Value>> {
+        self.call("tx.getTransactionReceipt", json!([tx_hash]))
+            .await
+            .context("tx.getTransactionReceipt failed")
+    }
+
     /// Poll for a transaction receipt until it appears or times out.
     ///
     /// This is handy for quickstarts and CLI demos. For production,
@@ -197,16 +900,451 @@ impl NodeClient {
         timeout: Duration,
         poll_every: Duration,
     ) -> Result<types::Receipt> {
-        let start = std::time::Instant::now();
+        let start = self.clock.now();
         loop {
             if let Some(r) = self.get_receipt(tx_hash).await? {
                 return Ok(r);
             }
-            if start.elapsed() >= timeout {
+            if self.clock.now().duration_since(start) >= timeout {
                 return Err(anyhow!("timed out waiting for receipt: {tx_hash}"));
             }
-            tokio::time::sleep(poll_every).await;
+            self.clock.sleep(poll_every).await;
+        }
+    }
+
+    /// Wait until `tx_hash` is in a canonical block with `confirmations`
+    /// heads on top of it (`0` = just included).
+    ///
+    /// Each poll re-checks that the receipt's block hash is still the
+    /// canonical one at its height; the interval doubles from 250ms up to 5s.
+    /// Fails with [`ReceiptError::Reorged`] when the receipt's block is no
+    /// longer canonical (or a seen receipt disappears), and with
+    /// [`ReceiptError::Timeout`] after `timeout`.
+    #[instrument(level = "info", skip(self))]
+    pub async fn await_receipt_confirmed(
+        &self,
+        tx_hash: &str,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> Result<types::Receipt> {
+        const FIRST_POLL: Duration = Duration::from_millis(250);
+        const MAX_POLL: Duration = Duration::from_secs(5);
+
+        let start = self.clock.now();
+        let mut delay = FIRST_POLL;
+        let mut seen: Option<(u64, String)> = None;
+        loop {
+            match self.get_receipt(tx_hash).await? {
+                Some(r) => {
+                    if let (Some(number), Some(hash)) = (r.block_number, r.block_hash.clone()) {
+                        let canonical = self
+                            .get_block_raw(number)
+                            .await?
+                            .and_then(|b| {
+                                b.pointer("/header/hash").or_else(|| b.get("hash")).cloned()
+                            })
+                            .and_then(|h| h.as_str().map(str::to_string));
+                        if canonical.as_deref() != Some(hash.as_str()) {
+                            return Err(ReceiptError::Reorged {
+                                tx_hash: tx_hash.to_string(),
+                                block_number: number,
+                                block_hash: hash,
+                            }
+                            .into());
+                        }
+                        if self.get_head().await?.number.saturating_sub(number) >= confirmations {
+                            return Ok(r);
+                        }
+                        seen = Some((number, hash));
+                    }
+                }
+                None => {
+                    if let Some((block_number, block_hash)) = seen {
+                        return Err(ReceiptError::Reorged {
+                            tx_hash: tx_hash.to_string(),
+                            block_number,
+                            block_hash,
+                        }
+                        .into());
+                    }
+                }
+            }
+            if self.clock.now().duration_since(start) >= timeout {
+                return Err(ReceiptError::Timeout { tx_hash: tx_hash.to_string() }.into());
+            }
+            self.clock.sleep(delay).await;
+            delay = (delay * 2).min(MAX_POLL);
+        }
+    }
+
+    /// Batch counterpart of [`NodeClient::await_receipt`] for indexers
+    /// confirming many transactions at once.
+    ///
+    /// Each poll sends one JSON-RPC batch covering only the hashes still
+    /// missing a receipt. Receipts are returned in `hashes` order. On timeout,
+    /// the error names every hash that is still missing.
+    #[instrument(level = "info", skip(self, hashes), fields(n = hashes.len()))]
+    pub async fn await_receipts(
+        &self,
+        hashes: &[String],
+        timeout: Duration,
+        poll_every: Duration,
+    ) -> Result<Vec<types::Receipt>> {
+        let start = self.clock.now();
+        let mut found: Vec<Option<types::Receipt>> = hashes.iter().map(|_| None).collect();
+        loop {
+            let missing: Vec<usize> = (0..hashes.len()).filter(|&i| found[i].is_none()).collect();
+            if missing.is_empty() {
+                return Ok(found.into_iter().flatten().collect());
+            }
+            let calls: Vec<(&str, Option<serde_json::Value>)> = missing
+                .iter()
+                .map(|&i| ("tx.getTransactionReceipt", Some(json!([hashes[i]]))))
+                .collect();
+            let results = self
+                .with_failover(|http| {
+                    let calls = calls.clone();
+                    async move { http.batch(calls).await }
+                })
+                .await
+                .context("tx.getTransactionReceipt batch failed")?;
+            for (&i, res) in missing.iter().zip(results) {
+                let v = res.with_context(|| format!("tx.getTransactionReceipt failed: {}", hashes[i]))?;
+                if !v.is_null() {
+                    let r = serde_json::from_value(v)
+                        .with_context(|| format!("bad receipt for {}", hashes[i]))?;
+                    found[i] = Some(r);
+                }
+            }
+            if found.iter().all(Option::is_some) {
+                continue;
+            }
+            if self.clock.now().duration_since(start) >= timeout {
+                let still: Vec<&str> = hashes
+                    .iter()
+                    .zip(&found)
+                    .filter(|(_, r)| r.is_none())
+                    .map(|(h, _)| h.as_str())
+                    .collect();
+                return Err(anyhow!(
+                    "timed out with {} of {} receipts missing: {}",
+                    still.len(),
+                    hashes.len(),
+                    still.join(", ")
+                ));
+            }
+            self.clock.sleep(poll_every).await;
+        }
+    }
+
+    /// Wait until the chain has advanced `n` heights past the current head,
+    /// returning the first head at or beyond that height.
+    ///
+    /// Polls `chain.getHead`, so heights produced between two polls still
+    /// count. Handy in integration tests waiting for confirmations.
+    #[instrument(level = "info", skip(self))]
+    pub async fn wait_for_heads(&self, n: u64, timeout: Duration) -> Result<types::Head> {
+        const POLL_EVERY: Duration = Duration::from_millis(250);
+
+        let start = self.clock.now();
+        let target = self.get_head().await?.number.saturating_add(n);
+        loop {
+            let head = self.get_head().await?;
+            if head.number >= target {
+                return Ok(head);
+            }
+            if self.clock.now().duration_since(start) >= timeout {
+                return Err(anyhow!(
+                    "timed out at height {} waiting for height {target}",
+                    head.number
+                ));
+            }
+            self.clock.sleep(POLL_EVERY).await;
+        }
+    }
+}
+
+/// Hands out increasing nonces per sender without a round trip per tx.
+///
+/// The first `next` for an address seeds the counter from
+/// [`NodeClient::get_nonce`]; later ones count up locally. Clones share the
+/// counters, so one manager can serve many tasks.
+#[derive(Clone, Debug)]
+pub struct NonceManager {
+    client: NodeClient,
+    /// Next nonce to hand out per address (one past the last used).
+    next: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl NonceManager {
+    /// A manager with empty counters that seeds them from `client`.
+    pub fn new(client: NodeClient) -> Self {
+        Self { client, next: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// Reserve the next nonce for `address`.
+    pub async fn next(&self, address: &str) -> Result<u64> {
+        if let Some(n) = self.take_cached(address)? {
+            return Ok(n);
+        }
+        // Lock is not held across the await; if another task seeded the
+        // counter meanwhile, its value wins.
+        let chain = self.client.get_nonce(address).await?;
+        let mut next = self.next.lock().map_err(|_| anyhow!("nonce cache poisoned"))?;
+        let slot = next.entry(address.to_string()).or_insert(chain);
+        let n = *slot;
+        *slot += 1;
+        Ok(n)
+    }
+
+    /// Give back `nonce` after its tx failed to submit.
+    ///
+    /// Only the most recently reserved nonce can be reclaimed; returns whether
+    /// it was. Otherwise later nonces are already out and the gap stays, so
+    /// call `reset_from_chain` once the in-flight txs settle.
+    pub fn release(&self, address: &str, nonce: u64) -> Result<bool> {
+        let mut next = self.next.lock().map_err(|_| anyhow!("nonce cache poisoned"))?;
+        match next.get_mut(address) {
+            Some(slot) if nonce.checked_add(1) == Some(*slot) => {
+                *slot = nonce;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Re-seed `address` from the node (e.g. after a dropped tx left a gap)
+    /// and return the nonce `next` will hand out.
+    pub async fn reset_from_chain(&self, address: &str) -> Result<u64> {
+        let chain = self.client.get_nonce(address).await?;
+        let mut next = self.next.lock().map_err(|_| anyhow!("nonce cache poisoned"))?;
+        next.insert(address.to_string(), chain);
+        Ok(chain)
+    }
+
+    fn take_cached(&self, address: &str) -> Result<Option<u64>> {
+        let mut next = self.next.lock().map_err(|_| anyhow!("nonce cache poisoned"))?;
+        Ok(next.get_mut(address).map(|slot| {
+            let n = *slot;
+            *slot += 1;
+            n
+        }))
+    }
+}
+
+/// Parse an RPC quantity: a JSON number, a `0x`-hex string, or a decimal string.
+fn json_quantity(v: &serde_json::Value) -> Option<u128> {
+    match v {
+        serde_json::Value::Number(n) => n.as_u64().map(u128::from),
+        serde_json::Value::String(s) => match s.strip_prefix("0x") {
+            Some(h) => u128::from_str_radix(h, 16).ok(),
+            None => s.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+// --- Transaction encoding (canonical CBOR) ------------------------------------
+
+pub mod tx {
+    //! Build the canonical CBOR blob `NodeClient::send_raw_transaction` expects.
+    //!
+    //! The layout mirrors `animica_sdk::tx::encode` byte for byte (and therefore
+    //! the Python and TypeScript SDKs), but is written out by hand so the
+    //! template needs no CBOR dependency:
+    //!
+    //! ```text
+    //! unsigned: 55799-tagged map {0: chain_id, 1: nonce, 2: gas_price, 3: gas_limit,
+    //!           4: from, 5: to | null, 6: value, 7: data, 8: access_list, 9: kind}
+    //! signed:   55799-tagged [ bytes(unsigned), {0: alg_id, 1: public_key, 2: signature} ]
+    //! ```
+    //!
+    //! Typical flow: `encode_unsigned` → sign `sign_bytes(&unsigned)` with your
+    //! key → `encode_signed` → `send_raw_transaction`.
+
+    use anyhow::{bail, Result};
+
+    /// Domain separator for transaction signatures (same as `animica_sdk::tx::encode::TX_SIGN_DOMAIN`).
+    pub const TX_SIGN_DOMAIN: &[u8] = b"animica/tx/sign/v1";
+
+    /// CBOR self-describe tag (55799) that prefixes every encoded object.
+    const SELF_DESCRIBE: [u8; 3] = [0xd9, 0xd9, 0xf7];
+
+    /// Transaction kind, encoded as key 9.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum TxKind {
+        Transfer = 0,
+        Call = 1,
+        Deploy = 2,
+    }
+
+    /// Unsigned transaction fields, in the node's canonical order.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct TxRequest {
+        pub chain_id: u64,
+        pub nonce: u64,
+        pub gas_price: u64,
+        pub gas_limit: u64,
+        /// Sender, bech32m `anim1...`.
+        pub from: String,
+        /// Recipient; must be `None` for deploys and `Some` otherwise.
+        pub to: Option<String>,
+        pub value: u128,
+        /// Call data or deploy code.
+        pub data: Vec<u8>,
+        /// `(address, storage_keys)` pairs; usually empty.
+        pub access_list: Vec<(String, Vec<Vec<u8>>)>,
+        pub kind: TxKind,
+    }
+
+    impl TxRequest {
+        /// A plain value transfer with no data or access list.
+        pub fn transfer(
+            chain_id: u64,
+            from: impl Into<String>,
+            to: impl Into<String>,
+            value: u128,
+            nonce: u64,
+            gas_price: u64,
+            gas_limit: u64,
+        ) -> Self {
+            Self {
+                chain_id,
+                nonce,
+                gas_price,
+                gas_limit,
+                from: from.into(),
+                to: Some(to.into()),
+                value,
+                data: Vec::new(),
+                access_list: Vec::new(),
+                kind: TxKind::Transfer,
+            }
+        }
+    }
+
+    /// Encode the unsigned transaction body (the bytes that get signed).
+    pub fn encode_unsigned(req: &TxRequest) -> Result<Vec<u8>> {
+        if req.from.is_empty() {
+            bail!("tx.from is empty");
+        }
+        match (req.kind, &req.to) {
+            (TxKind::Deploy, Some(_)) => bail!("deploy transactions must not set `to`"),
+            (TxKind::Transfer | TxKind::Call, None) => bail!("{:?} transactions require `to`", req.kind),
+            _ => {}
+        }
+
+        let mut out = Vec::with_capacity(128 + req.data.len());
+        out.extend_from_slice(&SELF_DESCRIBE);
+        head(&mut out, MAJOR_MAP, 10);
+        for (key, v) in [req.chain_id, req.nonce, req.gas_price, req.gas_limit].into_iter().enumerate() {
+            head(&mut out, MAJOR_UINT, key as u64);
+            head(&mut out, MAJOR_UINT, v);
+        }
+        head(&mut out, MAJOR_UINT, 4);
+        text(&mut out, &req.from);
+        head(&mut out, MAJOR_UINT, 5);
+        match &req.to {
+            Some(to) => text(&mut out, to),
+            None => out.push(NULL),
+        }
+        head(&mut out, MAJOR_UINT, 6);
+        match u64::try_from(req.value) {
+            Ok(v) => head(&mut out, MAJOR_UINT, v),
+            Err(_) => {
+                // Positive bignum (tag 2) over the full 16-byte big-endian value.
+                out.extend_from_slice(&[0xc2, 0x50]);
+                out.extend_from_slice(&req.value.to_be_bytes());
+            }
+        }
+        head(&mut out, MAJOR_UINT, 7);
+        bytes(&mut out, &req.data);
+        head(&mut out, MAJOR_UINT, 8);
+        head(&mut out, MAJOR_ARRAY, req.access_list.len() as u64);
+        for (addr, keys) in &req.access_list {
+            head(&mut out, MAJOR_ARRAY, 2);
+            text(&mut out, addr);
+            head(&mut out, MAJOR_ARRAY, keys.len() as u64);
+            for k in keys {
+                bytes(&mut out, k);
+            }
+        }
+        head(&mut out, MAJOR_UINT, 9);
+        head(&mut out, MAJOR_UINT, req.kind as u64);
+        Ok(out)
+    }
+
+    /// Message to hand to a raw signer: `TX_SIGN_DOMAIN || 0x00 || unsigned`.
+    ///
+    /// This is the exact input the SDK's wallet signers hash internally, so
+    /// don't pass it to one of those — give them `TX_SIGN_DOMAIN` and
+    /// `unsigned` instead. Use it for external signers (HSMs, hardware wallets).
+    pub fn sign_bytes(unsigned: &[u8]) -> Vec<u8> {
+        let mut msg = Vec::with_capacity(TX_SIGN_DOMAIN.len() + 1 + unsigned.len());
+        msg.extend_from_slice(TX_SIGN_DOMAIN);
+        msg.push(0x00);
+        msg.extend_from_slice(unsigned);
+        msg
+    }
+
+    /// Wrap an `encode_unsigned` body and its signature into the raw transaction.
+    pub fn encode_signed(unsigned: &[u8], alg_id: u16, pubkey: &[u8], sig: &[u8]) -> Result<Vec<u8>> {
+        if !unsigned.starts_with(&SELF_DESCRIBE) || unsigned.get(SELF_DESCRIBE.len()) != Some(&0xaa) {
+            bail!("unsigned body is not an encoded transaction map");
+        }
+        if pubkey.is_empty() || sig.is_empty() {
+            bail!("public key and signature must be non-empty");
         }
+        let mut out = Vec::with_capacity(unsigned.len() + pubkey.len() + sig.len() + 24);
+        out.extend_from_slice(&SELF_DESCRIBE);
+        head(&mut out, MAJOR_ARRAY, 2);
+        bytes(&mut out, unsigned);
+        head(&mut out, MAJOR_MAP, 3);
+        head(&mut out, MAJOR_UINT, 0);
+        head(&mut out, MAJOR_UINT, u64::from(alg_id));
+        head(&mut out, MAJOR_UINT, 1);
+        bytes(&mut out, pubkey);
+        head(&mut out, MAJOR_UINT, 2);
+        bytes(&mut out, sig);
+        Ok(out)
+    }
+
+    const MAJOR_UINT: u8 = 0;
+    const MAJOR_BYTES: u8 = 2;
+    const MAJOR_TEXT: u8 = 3;
+    const MAJOR_ARRAY: u8 = 4;
+    const MAJOR_MAP: u8 = 5;
+    const NULL: u8 = 0xf6;
+
+    /// Shortest-form CBOR item header.
+    fn head(out: &mut Vec<u8>, major: u8, n: u64) {
+        let m = major << 5;
+        match n {
+            0..=23 => out.push(m | n as u8),
+            24..=0xff => out.extend_from_slice(&[m | 24, n as u8]),
+            0x100..=0xffff => {
+                out.push(m | 25);
+                out.extend_from_slice(&(n as u16).to_be_bytes());
+            }
+            0x1_0000..=0xffff_ffff => {
+                out.push(m | 26);
+                out.extend_from_slice(&(n as u32).to_be_bytes());
+            }
+            _ => {
+                out.push(m | 27);
+                out.extend_from_slice(&n.to_be_bytes());
+            }
+        }
+    }
+
+    fn bytes(out: &mut Vec<u8>, b: &[u8]) {
+        head(out, MAJOR_BYTES, b.len() as u64);
+        out.extend_from_slice(b);
+    }
+
+    fn text(out: &mut Vec<u8>, s: &str) {
+        head(out, MAJOR_TEXT, s.len() as u64);
+        out.extend_from_slice(s.as_bytes());
     }
 }
 
@@ -221,24 +1359,247 @@ pub mod ws {
 
     use super::*;
     use animica_sdk::rpc::ws;
+    use futures::future::BoxFuture;
+    use futures::stream::{self, BoxStream, Stream, StreamExt};
 
-    /// Subscribe to `newHeads` and yield them as a stream.
+    /// Subscribe to `newHeads` and yield the raw JSON frames as a stream; see
+    /// [`subscribe_heads_typed`] for decoded heads.
     ///
     /// Example:
     /// ```ignore
-    /// let mut stream = client.subscribe_new_heads().await?;
+    /// let mut stream = subscribe_new_heads(&cfg).await?;
     /// while let Some(head) = stream.next().await {
-    ///     println!("new head: {}", head.height);
+    ///     println!("new head: {}", head["number"]);
     /// }
     /// ```
-    pub async fn subscribe_new_heads(cfg: &Config) -> Result<ws::HeadStream> {
-        let client = ws::Client::connect(&cfg.rpc_url).await?;
-        let stream = client.subscribe_new_heads().await?;
-        Ok(stream)
+    pub async fn subscribe_new_heads(cfg: &Config) -> Result<ws::Subscription> {
+        let client = ws::WsClient::connect(&cfg.rpc_url).await?;
+        Ok(client.subscribe_topic("newHeads").await?)
+    }
+
+    /// Subscribe to `newHeads` and decode every frame into [`types::Head`].
+    ///
+    /// A frame that fails to decode yields an `Err` item; the stream keeps going.
+    ///
+    /// ```ignore
+    /// let mut heads = Box::pin(subscribe_heads_typed(&cfg).await?);
+    /// while let Some(head) = heads.next().await {
+    ///     let head = head?;
+    ///     println!("#{} {}", head.number, head.hash);
+    /// }
+    /// ```
+    pub async fn subscribe_heads_typed(
+        cfg: &Config,
+    ) -> Result<impl Stream<Item = Result<types::Head>>> {
+        let client = ws::WsClient::connect(&cfg.rpc_url).await?;
+        let sub = client.subscribe_topic("newHeads").await?;
+        let frames = stream::unfold(sub, |mut sub| async move {
+            sub.next().await.map(|frame| (frame, sub))
+        });
+        Ok(decode_heads(frames))
+    }
+
+    /// Decode a stream of raw `newHeads` frames into [`types::Head`]s.
+    ///
+    /// Accepts either the bare head object or an envelope carrying it under
+    /// `result` (as in `{"subscription": "...", "result": {...}}`).
+    pub fn decode_heads<S>(frames: S) -> impl Stream<Item = Result<types::Head>>
+    where
+        S: Stream<Item = serde_json::Value>,
+    {
+        frames.map(decode_head)
+    }
+
+    fn decode_head(mut frame: serde_json::Value) -> Result<types::Head> {
+        if let Some(inner) = frame.get_mut("result").filter(|r| r.is_object()) {
+            frame = inner.take();
+        }
+        serde_json::from_value::<types::Head>(frame).context("decode newHeads frame")
+    }
+
+    /// Raw `newHeads` frames from one connection; ends when it drops.
+    pub type FrameStream = BoxStream<'static, serde_json::Value>;
+
+    type Connector = Box<dyn FnMut(&Config) -> BoxFuture<'static, Result<FrameStream>> + Send>;
+
+    /// Item yielded by [`HeadFollower::next_event`].
+    #[derive(Debug, Clone)]
+    pub enum FollowEvent {
+        /// A new head, in arrival order.
+        Head(types::Head),
+        /// The connection dropped and the first head after reconnecting is
+        /// not the successor of the last one seen: heights `from..=to` may
+        /// have been missed. Backfill them over HTTP if you need every block.
+        Gap { from: u64, to: u64 },
+    }
+
+    /// Reconnecting `newHeads` watcher: the building block for "watch the chain".
+    ///
+    /// Owns the [`Config`], (re)connects and resubscribes whenever the socket
+    /// drops, backing off between failed attempts, and reports a
+    /// [`FollowEvent::Gap`] when a reconnect may have skipped heights.
+    /// Undecodable frames are logged and skipped.
+    ///
+    /// ```ignore
+    /// let mut follower = HeadFollower::new(cfg);
+    /// while let Some(ev) = follower.next_event().await {
+    ///     match ev {
+    ///         FollowEvent::Head(h) => println!("#{} {}", h.number, h.hash),
+    ///         FollowEvent::Gap { from, to } => println!("missed {from}..={to}"),
+    ///     }
+    /// }
+    /// ```
+    pub struct HeadFollower {
+        cfg: Config,
+        connect: Connector,
+        frames: Option<FrameStream>,
+        last: Option<u64>,
+        /// Head held back while the `Gap` in front of it is delivered.
+        pending: Option<types::Head>,
+        /// Set on (re)connect; the next head is checked for a gap.
+        just_connected: bool,
+        backoff: Duration,
+        max_retries: usize,
+    }
+
+    impl HeadFollower {
+        /// Follow `newHeads` on `cfg.rpc_url`.
+        pub fn new(cfg: Config) -> Self {
+            Self::with_connector(cfg, |cfg: &Config| {
+                let url = cfg.rpc_url.clone();
+                Box::pin(async move { connect_frames(&url).await }) as BoxFuture<'static, _>
+            })
+        }
+
+        /// Like [`HeadFollower::new`], but frames come from `connect`, called
+        /// on every (re)connect. Useful for custom transports and tests.
+        pub fn with_connector<F>(cfg: Config, connect: F) -> Self
+        where
+            F: FnMut(&Config) -> BoxFuture<'static, Result<FrameStream>> + Send + 'static,
+        {
+            Self {
+                cfg,
+                connect: Box::new(connect),
+                frames: None,
+                last: None,
+                pending: None,
+                just_connected: false,
+                backoff: Duration::from_millis(250),
+                max_retries: 10,
+            }
+        }
+
+        /// Initial delay between failed connection attempts (doubles, capped at 30s).
+        pub fn with_backoff(mut self, d: Duration) -> Self {
+            self.backoff = d;
+            self
+        }
+
+        /// Consecutive failed connection attempts tolerated before giving up.
+        pub fn with_max_retries(mut self, n: usize) -> Self {
+            self.max_retries = n;
+            self
+        }
+
+        /// The configuration this follower connects with.
+        pub fn config(&self) -> &Config {
+            &self.cfg
+        }
+
+        /// Height of the last head returned, if any.
+        pub fn last_height(&self) -> Option<u64> {
+            self.last
+        }
+
+        /// Next head, skipping [`FollowEvent::Gap`] markers. `None` once
+        /// reconnecting has failed `max_retries` times in a row.
+        pub async fn next_head(&mut self) -> Option<types::Head> {
+            loop {
+                match self.next_event().await? {
+                    FollowEvent::Head(h) => return Some(h),
+                    FollowEvent::Gap { from, to } => {
+                        tracing::warn!(from, to, "newHeads gap after reconnect");
+                    }
+                }
+            }
+        }
+
+        /// Next head or gap marker. `None` once reconnecting has failed
+        /// `max_retries` times in a row.
+        pub async fn next_event(&mut self) -> Option<FollowEvent> {
+            loop {
+                if let Some(head) = self.pending.take() {
+                    self.last = Some(head.number);
+                    return Some(FollowEvent::Head(head));
+                }
+                let Some(frames) = self.frames.as_mut() else {
+                    self.frames = Some(self.reconnect().await?);
+                    self.just_connected = true;
+                    continue;
+                };
+                let Some(frame) = frames.next().await else {
+                    debug!(last = ?self.last, "newHeads stream ended; reconnecting");
+                    self.frames = None;
+                    continue;
+                };
+                let head = match decode_head(frame) {
+                    Ok(h) => h,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "skipping undecodable newHeads frame");
+                        continue;
+                    }
+                };
+                if std::mem::take(&mut self.just_connected) {
+                    if let Some(last) = self.last {
+                        if head.number > last.saturating_add(1) {
+                            let gap = FollowEvent::Gap { from: last + 1, to: head.number - 1 };
+                            self.pending = Some(head);
+                            return Some(gap);
+                        }
+                    }
+                }
+                self.last = Some(head.number);
+                return Some(FollowEvent::Head(head));
+            }
+        }
+
+        async fn reconnect(&mut self) -> Option<FrameStream> {
+            let mut delay = self.backoff;
+            for attempt in 0..=self.max_retries {
+                if attempt > 0 {
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(Duration::from_secs(30));
+                }
+                match (self.connect)(&self.cfg).await {
+                    Ok(frames) => return Some(frames),
+                    Err(e) => tracing::warn!(attempt, error = %e, "newHeads connect failed"),
+                }
+            }
+            None
+        }
+    }
+
+    /// Connect, subscribe to `newHeads`, and keep the client alive for as
+    /// long as the frame stream is.
+    async fn connect_frames(url: &str) -> Result<FrameStream> {
+        let client = ws::WsClient::connect(url).await?;
+        let sub = client.subscribe_topic("newHeads").await?;
+        Ok(stream::unfold((client, sub), |(client, mut sub)| async move {
+            sub.next().await.map(|frame| (frame, (client, sub)))
+        })
+        .boxed())
+    }
+
+    /// Project a typed head stream down to just block heights.
+    pub fn map_height<S>(heads: S) -> impl Stream<Item = Result<u64>>
+    where
+        S: Stream<Item = Result<types::Head>>,
+    {
+        heads.map(|h| h.map(|h| h.number))
     }
 }
 
-// --- Small, focused hex utility (kept private; used by send_raw_transaction) -
+// --- Small, focused hex utility (kept private; used by tx helpers) ----------
 
 mod hex {
     // Local minimal dependency to keep the template self-contained if you choose
@@ -252,6 +1613,119 @@ mod hex {
         }
         out
     }
+
+    pub fn decode(s: &str) -> Result<Vec<u8>, &'static str> {
+        let digits = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+        if digits.len() % 2 != 0 {
+            return Err("odd-length hex");
+        }
+        let nib = |c: u8| match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err("invalid hex digit"),
+        };
+        digits
+            .chunks(2)
+            .map(|p| Ok(nib(p[0])? << 4 | nib(p[1])?))
+            .collect()
+    }
+}
+
+// --- Test helpers: loopback mock node + config -----------------------------
+
+#[cfg(test)]
+mod test_util {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// `Config` pointing at `rpc_url` (chain 1337, 5s timeout, no gas floor).
+    pub fn test_config(rpc_url: impl Into<String>) -> Config {
+        Config {
+            rpc_url: rpc_url.into(),
+            chain_id: 1337,
+            default_timeout: Duration::from_secs(5),
+            gas_price_floor: None,
+        }
+    }
+
+    /// Minimal JSON-RPC server answering each call with `route(method, params)`
+    /// and counting hits. Batch requests are answered element-wise (one hit
+    /// per HTTP request).
+    pub fn serve_rpc<F>(route: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(&str, &serde_json::Value) -> serde_json::Value + Send + 'static,
+    {
+        serve(move |body| {
+            let call: serde_json::Value = serde_json::from_slice(body).unwrap_or_default();
+            let answer = |call: &serde_json::Value| {
+                let result = route(call["method"].as_str().unwrap_or(""), &call["params"]);
+                json!({"jsonrpc": "2.0", "id": call["id"], "result": result})
+            };
+            let out = match call.as_array() {
+                Some(calls) => serde_json::Value::from(calls.iter().map(answer).collect::<Vec<_>>()),
+                None => answer(&call),
+            };
+            ("200 OK", out.to_string())
+        })
+    }
+
+    /// HTTP server answering every request with `503 Service Unavailable`.
+    pub fn serve_unavailable() -> (String, Arc<AtomicUsize>) {
+        serve(|_| ("503 Service Unavailable", String::new()))
+    }
+
+    /// Accept loop behind the mocks: reads each request's body (up to
+    /// `Content-Length`), counts the hit, and replies with `respond(body)`.
+    fn serve<F>(respond: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(&[u8]) -> (&'static str, String) + Send + 'static,
+    {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                // Read headers, then the body up to Content-Length.
+                let mut req = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body_start = loop {
+                    let n = stream.read(&mut buf).unwrap_or(0);
+                    req.extend_from_slice(&buf[..n]);
+                    if let Some(p) = req.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break p + 4;
+                    }
+                    if n == 0 {
+                        break req.len();
+                    }
+                };
+                let head = String::from_utf8_lossy(&req[..body_start]).to_ascii_lowercase();
+                let want = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("content-length:"))
+                    .and_then(|v| v.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                while req.len() < body_start + want {
+                    let n = stream.read(&mut buf).unwrap_or(0);
+                    if n == 0 {
+                        break;
+                    }
+                    req.extend_from_slice(&buf[..n]);
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = respond(&req[body_start..]);
+                let resp = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(resp.as_bytes());
+            }
+        });
+        (format!("http://{addr}"), hits)
+    }
 }
 
 // --- Tests (unit-level smoke) ------------------------------------------------
@@ -259,17 +1733,585 @@ mod hex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_util::{serve_rpc, serve_unavailable, test_config};
+
+    /// Serializes tests that mutate `ANIMICA_*` variables.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
     fn config_from_env_defaults() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         // Provide a minimal env to exercise parsing without making network calls.
         std::env::set_var("ANIMICA_RPC_URL", "http://localhost:8545");
         std::env::set_var("ANIMICA_CHAIN_ID", "1337");
         std::env::remove_var("ANIMICA_TIMEOUT_SECS");
+        std::env::remove_var("ANIMICA_GAS_PRICE_FLOOR");
 
         let cfg = Config::from_env().expect("config");
         assert_eq!(cfg.rpc_url, "http://localhost:8545");
         assert_eq!(cfg.chain_id, 1337);
         assert_eq!(cfg.default_timeout, Duration::from_secs(20));
+        assert_eq!(cfg.gas_price_floor, None);
+    }
+
+    #[test]
+    fn config_from_file_with_env_overrides() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        for k in ["ANIMICA_RPC_URL", "RPC_URL", "ANIMICA_CHAIN_ID", "CHAIN_ID", "ANIMICA_TIMEOUT_SECS", "ANIMICA_GAS_PRICE_FLOOR"] {
+            std::env::remove_var(k);
+        }
+        let dir = std::env::temp_dir().join(format!("starter-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, body: &str| {
+            let p = dir.join(name);
+            std::fs::write(&p, body).unwrap();
+            p
+        };
+
+        let toml = write(
+            "node.toml",
+            "rpc_url = \"http://file:8545\"\nchain_id = 7\ntimeout_secs = 3\ngas_price_floor = 10\n",
+        );
+        let cfg = Config::from_file(&toml).unwrap();
+        assert_eq!(cfg.rpc_url, "http://file:8545");
+        assert_eq!((cfg.chain_id, cfg.default_timeout, cfg.gas_price_floor), (7, Duration::from_secs(3), Some(10)));
+
+        // Env wins over the file; the rest still comes from the file.
+        std::env::set_var("ANIMICA_CHAIN_ID", "42");
+        let cfg = Config::from_file(&toml).unwrap();
+        assert_eq!((cfg.chain_id, cfg.rpc_url.as_str()), (42, "http://file:8545"));
+        std::env::remove_var("ANIMICA_CHAIN_ID");
+
+        let json = write("node.json", r#"{"rpc_url": "http://json:8545", "chain_id": 9}"#);
+        std::env::set_var("ANIMICA_CONFIG", &json);
+        let cfg = Config::load().unwrap();
+        std::env::remove_var("ANIMICA_CONFIG");
+        assert_eq!((cfg.chain_id, cfg.default_timeout), (9, Duration::from_secs(20)));
+
+        let err = Config::from_file(write("bad.toml", "rpc_url = \"x\"\nchain_id = \"seven\"\n")).unwrap_err();
+        assert!(format!("{err:#}").contains("`chain_id`"), "{err:#}");
+        let err = Config::from_file(write("typo.json", r#"{"rpc_ur": "x"}"#)).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown config key `rpc_ur`"), "{err:#}");
+        assert!(Config::from_file(write("node.yaml", "")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Golden body for `TxRequest::transfer(1, "anim1from", "anim1to", 1_000_000, 0, 1000, 21000)`.
+    const TRANSFER_UNSIGNED: &str = concat!(
+        "d9d9f7aa",
+        "0001",
+        "0100",
+        "021903e8",
+        "03195208",
+        "0469616e696d3166726f6d",
+        "0567616e696d31746f",
+        "061a000f4240",
+        "0740",
+        "0880",
+        "0900",
+    );
+
+    #[test]
+    fn tx_encoding_matches_golden_transfer_vectors() {
+        let req = tx::TxRequest::transfer(1, "anim1from", "anim1to", 1_000_000, 0, 1000, 21000);
+        let unsigned = tx::encode_unsigned(&req).unwrap();
+        assert_eq!(hex::encode(&unsigned), TRANSFER_UNSIGNED);
+
+        let msg = tx::sign_bytes(&unsigned);
+        assert_eq!(&msg[..tx::TX_SIGN_DOMAIN.len()], tx::TX_SIGN_DOMAIN);
+        assert_eq!(msg[tx::TX_SIGN_DOMAIN.len()], 0x00);
+        assert_eq!(&msg[tx::TX_SIGN_DOMAIN.len() + 1..], &unsigned[..]);
+
+        let signed = tx::encode_signed(&unsigned, 0x0103, b"pk", b"sig").unwrap();
+        let expected = format!("d9d9f7825830{TRANSFER_UNSIGNED}a300190103") + "0142706b" + "0243736967";
+        assert_eq!(hex::encode(&signed), expected);
+
+        // Values above u64 use a 16-byte positive bignum.
+        let big = tx::TxRequest { value: u128::from(u64::MAX) + 1, ..req.clone() };
+        let enc = hex::encode(&tx::encode_unsigned(&big).unwrap());
+        assert!(enc.contains("06c25000000000000000010000000000000000"));
+
+        let deploy_with_to = tx::TxRequest { kind: tx::TxKind::Deploy, ..req };
+        assert!(tx::encode_unsigned(&deploy_with_to).is_err());
+        assert!(tx::encode_signed(b"not a tx", 0x0103, b"pk", b"sig").is_err());
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn decode_heads_from_synthetic_frames() {
+        use futures::StreamExt;
+
+        let frames = futures::stream::iter(vec![
+            json!({"number": 7, "hash": "0xaa", "timestamp": 1_700_000_000}),
+            json!({"subscription": "0x1", "result": {"number": 8, "hash": "0xbb", "timestamp": 1_700_000_002, "miner": "anim1x"}}),
+            json!({"number": "not-a-height"}),
+        ]);
+        let heads: Vec<_> = ws::decode_heads(frames).collect().await;
+        assert_eq!(heads.len(), 3);
+        let second = heads[1].as_ref().unwrap();
+        assert_eq!((second.number, second.hash.as_str()), (8, "0xbb"));
+        assert_eq!(second.extra["miner"], "anim1x");
+        assert!(heads[2].is_err());
+
+        let ok = futures::stream::iter(heads.into_iter().take(2));
+        let heights: Vec<u64> = ws::map_height(ok).map(|h| h.unwrap()).collect().await;
+        assert_eq!(heights, vec![7, 8]);
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn head_follower_reports_gap_after_reconnect() {
+        use futures::StreamExt;
+        use std::collections::VecDeque;
+        use ws::{FollowEvent, FrameStream, HeadFollower};
+
+        let head = |n: u64| json!({"number": n, "hash": format!("0x{n:02x}"), "timestamp": n});
+        // Connection 1 delivers #7 then drops; connection 2 resumes at #10.
+        // After that every connect fails.
+        let scripts = Arc::new(Mutex::new(VecDeque::from(vec![
+            vec![head(7)],
+            vec![head(10), json!({"number": "bad"}), head(11)],
+        ])));
+        let follower = |scripts: Arc<Mutex<VecDeque<Vec<serde_json::Value>>>>| {
+            let cfg = test_config("ws://unused");
+            HeadFollower::with_connector(cfg, move |_: &Config| {
+                let next = scripts.lock().unwrap().pop_front();
+                Box::pin(async move {
+                    let frames = next.ok_or_else(|| anyhow!("connection refused"))?;
+                    Ok(futures::stream::iter(frames).boxed() as FrameStream)
+                }) as futures::future::BoxFuture<'static, _>
+            })
+            .with_backoff(Duration::ZERO)
+            .with_max_retries(1)
+        };
+
+        let mut f = follower(scripts);
+        let mut events = Vec::new();
+        while let Some(ev) = f.next_event().await {
+            events.push(match ev {
+                FollowEvent::Head(h) => format!("head {}", h.number),
+                FollowEvent::Gap { from, to } => format!("gap {from}..={to}"),
+            });
+        }
+        assert_eq!(events, ["head 7", "gap 8..=9", "head 10", "head 11"]);
+        assert_eq!(f.last_height(), Some(11));
+
+        // `next_head` yields the same heads with the gap marker skipped.
+        let mut f = follower(Arc::new(Mutex::new(VecDeque::from(vec![vec![head(7)], vec![head(10)]]))));
+        assert_eq!(f.next_head().await.map(|h| h.number), Some(7));
+        assert_eq!(f.next_head().await.map(|h| h.number), Some(10));
+        assert!(f.next_head().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn wait_for_heads_returns_after_n_new_heights() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        // Every getHead call sees the chain one block further along.
+        let height = AtomicU64::new(100);
+        let (url, hits) = serve_rpc(move |_, _| {
+            let h = height.fetch_add(1, Ordering::SeqCst);
+            json!({"number": h, "hash": format!("0x{h:064x}"), "timestamp": 1_700_000_000 + h})
+        });
+        let client = NodeClient::new(test_config(url)).unwrap();
+
+        let head = client.wait_for_heads(3, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(head.number, 103);
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        let err = client.wait_for_heads(1_000, Duration::from_millis(300)).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn await_receipt_times_out_on_mock_clock_without_sleeping() {
+        use std::sync::atomic::Ordering;
+
+        let (rpc_url, hits) = serve_rpc(|_, _| serde_json::Value::Null);
+        let clock = MockClock::new();
+        let client = NodeClient::new(test_config(rpc_url)).unwrap().with_clock(clock.clone());
+
+        let wall = Instant::now();
+        let err = client
+            .await_receipt("0xabc", Duration::from_secs(30), Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out waiting for receipt: 0xabc"), "{err}");
+        // Polls at t = 0, 1, ..., 30 on the mock clock; no real waiting.
+        assert_eq!(hits.load(Ordering::SeqCst), 31);
+        assert_eq!(clock.elapsed(), Duration::from_secs(30));
+        assert!(wall.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn await_receipts_collects_across_polls_in_input_order() {
+        use std::collections::HashMap;
+        use std::sync::atomic::Ordering;
+
+        // `0xa<k>` gets a receipt on its k-th lookup; `0xff` never does.
+        let lookups = Mutex::new(HashMap::<String, usize>::new());
+        let (rpc_url, hits) = serve_rpc(move |_, params| {
+            let hash = params[0].as_str().unwrap_or_default().to_string();
+            let mut seen = lookups.lock().unwrap();
+            let n = seen.entry(hash.clone()).or_default();
+            *n += 1;
+            let ready_after = hash.strip_prefix("0xa").and_then(|k| k.parse().ok()).unwrap_or(usize::MAX);
+            if *n >= ready_after {
+                json!({"tx_hash": hash, "status": "SUCCESS", "gas_used": 21_000})
+            } else {
+                serde_json::Value::Null
+            }
+        });
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+
+        let hashes: Vec<String> = ["0xa3", "0xa1", "0xa2"].map(String::from).to_vec();
+        let receipts = client
+            .await_receipts(&hashes, Duration::from_secs(5), Duration::from_millis(10))
+            .await
+            .unwrap();
+        let got: Vec<&str> = receipts.iter().map(|r| r.tx_hash.as_str()).collect();
+        assert_eq!(got, ["0xa3", "0xa1", "0xa2"]);
+        assert_eq!(hits.load(Ordering::SeqCst), 3, "one batch per poll");
+
+        let hashes: Vec<String> = ["0xa1", "0xff"].map(String::from).to_vec();
+        let err = client
+            .await_receipts(&hashes, Duration::from_millis(50), Duration::from_millis(10))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1 of 2 receipts missing: 0xff"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn connect_rejects_wrong_chain_and_caches_verified_id() {
+        use std::sync::atomic::Ordering;
+
+        let (rpc_url, hits) = serve_rpc(|_, _| json!(5));
+        let cfg = |chain_id| Config { chain_id, ..test_config(rpc_url.clone()) };
+
+        let err = NodeClient::connect(cfg(1)).await.unwrap_err();
+        match err.downcast_ref::<animica_sdk::error::Error>() {
+            Some(animica_sdk::error::Error::ChainIdMismatch { expected, got }) => {
+                assert_eq!((*expected, *got), (1, 5));
+            }
+            other => panic!("expected ChainIdMismatch, got {other:?} ({err})"),
+        }
+        assert!(NodeClient::new(cfg(1)).unwrap().verified_chain_id().is_none());
+
+        let client = NodeClient::connect(cfg(5)).await.unwrap();
+        assert_eq!(client.verified_chain_id(), Some(5));
+        let before = hits.load(Ordering::SeqCst);
+        assert_eq!(client.clone().assert_chain_id().await.unwrap(), 5);
+        assert_eq!(hits.load(Ordering::SeqCst), before, "verified id is cached");
+    }
+
+    #[tokio::test]
+    async fn raw_variants_return_untyped_json() {
+        // `gas_used` arrives as a hex string: typed decoding fails, the raw
+        // variant still works.
+        let receipt = json!({"tx_hash": "0xab", "status": "SUCCESS", "gas_used": "0x5208", "novel": {"x": 1}});
+        let expected = receipt.clone();
+        let (rpc_url, _hits) = serve_rpc(move |method, _| match method {
+            "tx.getTransactionReceipt" => receipt.clone(),
+            "chain.getBlockByHeight" => serde_json::Value::Null,
+            _ => json!({"number": 1, "hash": "0x01", "timestamp": 2, "novel": true}),
+        });
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+
+        assert!(client.get_receipt("0xab").await.is_err());
+        assert_eq!(client.get_receipt_raw("0xab").await.unwrap(), Some(expected));
+        assert_eq!(client.get_head_raw().await.unwrap()["novel"], true);
+        assert_eq!(client.get_block_raw(99).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn cached_params_skips_rpc_within_ttl() {
+        use std::sync::atomic::Ordering;
+
+        let (rpc_url, hits) = serve_rpc(|_, _| json!({"chainId": 1337, "gasPriceMin": 1}));
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+
+        let first = client.cached_params(Duration::from_secs(60)).await.unwrap();
+        let second = client.cached_params(Duration::from_secs(60)).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first["chainId"], 1337);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // A zero TTL is always expired, so this refreshes.
+        client.cached_params(Duration::ZERO).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn simulate_decodes_revert_reason() {
+        // Error(string) selector + ABI-encoded "insufficient balance".
+        let msg = "insufficient balance";
+        let mut payload = vec![0x08, 0xc3, 0x79, 0xa0];
+        let mut word = [0u8; 32];
+        word[31] = 32;
+        payload.extend_from_slice(&word);
+        word[31] = msg.len() as u8;
+        payload.extend_from_slice(&word);
+        let mut body = msg.as_bytes().to_vec();
+        body.resize(32, 0);
+        payload.extend_from_slice(&body);
+
+        let result = json!({
+            "success": false,
+            "gasUsed": "0x5208",
+            "returnData": format!("0x{}", hex::encode(&payload)),
+        });
+        let (rpc_url, _hits) = serve_rpc(move |_, _| result.clone());
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+
+        let sim = client.simulate_transaction(&json!({"to": "anim1xyz", "data": "0x"})).await.unwrap();
+        assert!(!sim.success);
+        assert_eq!(sim.gas_used, 21_000);
+        assert_eq!(sim.return_data, payload);
+        assert_eq!(sim.revert_reason.as_deref(), Some(msg));
+    }
+
+    #[tokio::test]
+    async fn estimate_fee_combines_gas_and_price() {
+        let (rpc_url, _hits) = serve_rpc(|method, _| match method {
+            "tx.estimateGas" => json!("0x5208"),
+            "chain.getGasPrice" => json!(1_000),
+            "chain.getParams" => json!({"priorityMultipliers": {"fast": 1.5}}),
+            _ => serde_json::Value::Null,
+        });
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+        let tx = json!({"to": "anim1xyz", "value": "0x1"});
+
+        let normal = client.estimate_fee(&tx).await.unwrap();
+        assert_eq!(
+            normal,
+            FeeEstimate { gas: 21_000, gas_price: 1_000, max_fee: 21_000_000, priority: Priority::Normal }
+        );
+
+        // `fast` comes from chain params; `slow` falls back to the default 0.9.
+        let fast = client.estimate_fee_with_priority(&tx, Priority::Fast).await.unwrap();
+        assert_eq!((fast.gas_price, fast.max_fee), (1_500, 31_500_000));
+        let slow = client.estimate_fee_with_priority(&tx, Priority::Slow).await.unwrap();
+        assert_eq!((slow.gas_price, slow.max_fee), (900, 18_900_000));
+    }
+
+    #[tokio::test]
+    async fn fee_history_parses_hex_and_decimal_quantities() {
+        let (rpc_url, _hits) = serve_rpc(|method, params| match method {
+            "chain.feeHistory" => {
+                assert_eq!(params, &json!([3, "latest", [10.0, 50.0]]));
+                json!({
+                    "oldestBlock": "0x64",
+                    "baseFeePerGas": ["0x3e8", 1100, "1210", "0x533"],
+                    "gasUsedRatio": [0.5, 0.92, 0.0],
+                    "reward": [["0x1", "0x2"], [3, "4"], ["0x0", "0x0"]],
+                })
+            }
+            _ => serde_json::Value::Null,
+        });
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+
+        let h = client.fee_history(3, &[10.0, 50.0]).await.unwrap();
+        assert_eq!(
+            h,
+            FeeHistory {
+                oldest_block: 100,
+                base_fee_per_gas: vec![1000, 1100, 1210, 1331],
+                gas_used_ratio: vec![0.5, 0.92, 0.0],
+                reward: vec![vec![1, 2], vec![3, 4], vec![0, 0]],
+            }
+        );
+        assert!(client.fee_history(3, &[50.0, 10.0]).await.is_err());
+        assert!(client.fee_history(0, &[]).await.is_err());
+
+        let no_rewards = FeeHistory::from_json(&json!({
+            "oldest_block": 7,
+            "base_fee_per_gas": ["0x1"],
+            "gas_used_ratio": [],
+        }))
+        .unwrap();
+        assert!(no_rewards.reward.is_empty());
+        assert!(FeeHistory::from_json(&json!({"oldestBlock": "0x1"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn nonce_manager_counts_locally_and_reclaims_last() {
+        let chain_nonce = Arc::new(std::sync::atomic::AtomicU64::new(5));
+        let served = chain_nonce.clone();
+        let (rpc_url, hits) = serve_rpc(move |method, params| match method {
+            "account.getNonce" => {
+                assert_eq!(params, &json!(["anim1alice"]));
+                json!(format!("{:#x}", served.load(std::sync::atomic::Ordering::SeqCst)))
+            }
+            _ => serde_json::Value::Null,
+        });
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+        assert_eq!(client.get_nonce("anim1alice").await.unwrap(), 5);
+
+        let nonces = NonceManager::new(client);
+        let shared = nonces.clone();
+        assert_eq!(nonces.next("anim1alice").await.unwrap(), 5);
+        assert_eq!(shared.next("anim1alice").await.unwrap(), 6);
+        assert_eq!(nonces.next("anim1alice").await.unwrap(), 7);
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 2, "only the first next() hits the node");
+
+        // The tx with nonce 7 failed: reclaim it. Nonce 5 is no longer the last.
+        assert!(nonces.release("anim1alice", 7).unwrap());
+        assert!(!nonces.release("anim1alice", 5).unwrap());
+        assert_eq!(shared.next("anim1alice").await.unwrap(), 7);
+
+        chain_nonce.store(6, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(nonces.reset_from_chain("anim1alice").await.unwrap(), 6);
+        assert_eq!(shared.next("anim1alice").await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn quote_fee_and_gas_price_floor() {
+        let (rpc_url, _hits) = serve_rpc(|method, params| match method {
+            "tx.estimateFee" => {
+                assert_eq!(params, &json!(["0xa1b2"]));
+                json!({"gasLimit": "0x5208", "gasPrice": "1500"})
+            }
+            "chain.getGasPrice" => json!("900"),
+            _ => serde_json::Value::Null,
+        });
+        let cfg = test_config(rpc_url);
+        let client = NodeClient::new(cfg.clone()).unwrap();
+        assert_eq!(
+            client.quote_fee(&[0xa1, 0xb2]).await.unwrap(),
+            FeeQuote { gas_limit: 21_000, gas_price: 1_500, total: 31_500_000 }
+        );
+        assert_eq!(client.suggest_gas_price().await.unwrap(), 900);
+
+        let floored = NodeClient::new(Config { gas_price_floor: Some(1_000), ..cfg }).unwrap();
+        assert_eq!(floored.suggest_gas_price().await.unwrap(), 1_000);
+
+        // A node without `chain.getGasPrice` falls back to the floor, if any.
+        let (rpc_url, _hits) = serve_rpc(|_, _| serde_json::Value::Null);
+        let cfg = Config { gas_price_floor: Some(7), ..test_config(rpc_url) };
+        assert_eq!(NodeClient::new(cfg.clone()).unwrap().suggest_gas_price().await.unwrap(), 7);
+        let bare = NodeClient::new(Config { gas_price_floor: None, ..cfg }).unwrap();
+        assert!(bare.suggest_gas_price().await.is_err());
+
+        assert_eq!(
+            FeeQuote::from_json(&json!({"gas_limit": 2, "gas_price": "0x3", "total": "10"})).unwrap().total,
+            10
+        );
+    }
+
+    #[tokio::test]
+    async fn blocks_in_range_yields_in_order_and_stops_on_error() {
+        let (rpc_url, hits) = serve_rpc(|method, params| {
+            assert_eq!(method, "chain.getBlockByHeight");
+            let h = params[0].as_u64().unwrap();
+            if h > 20 {
+                return serde_json::Value::Null;
+            }
+            json!({
+                "header": {
+                    "hash": format!("0x{h:064x}"),
+                    "parent_hash": format!("0x{:064x}", h.saturating_sub(1)),
+                    "number": h,
+                    "timestamp": 1_700_000_000 + h,
+                    "chain_id": 1337,
+                },
+            })
+        });
+        let client = NodeClient::new(test_config(rpc_url)).unwrap();
+
+        let heights: Vec<u64> = client
+            .blocks_in_range(10, 17, 4)
+            .unwrap()
+            .map(|b| b.unwrap().header.number)
+            .collect()
+            .await;
+        assert_eq!(heights, (10..=17).collect::<Vec<_>>());
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 8);
+
+        // Height 21 is unknown: the error is yielded once and the stream ends.
+        let out: Vec<_> = client.blocks_in_range(19, 25, 2).unwrap().collect().await;
+        assert_eq!(out.len(), 3);
+        assert!(out[..2].iter().all(|b| b.is_ok()));
+        assert!(out[2].as_ref().unwrap_err().to_string().contains("block 21 not found"));
+
+        assert!(client.blocks_in_range(5, 4, 1).is_err());
+        assert!(client.blocks_in_range(1, 1, 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn failover_skips_dead_endpoint_during_cooldown() {
+        use std::sync::atomic::Ordering;
+
+        let (dead, dead_hits) = serve_unavailable();
+        let (live, live_hits) = serve_rpc(|_, _| json!({"number": 3, "hash": "0x03", "timestamp": 0}));
+        let clock = MockClock::new();
+        let client = NodeClient::with_endpoints(
+            vec![dead.clone(), live.clone()],
+            test_config("http://ignored"),
+        )
+        .unwrap()
+        .with_clock(clock.clone())
+        .with_failover_cooldown(Duration::from_secs(10));
+        assert_eq!(client.config().rpc_url, dead);
+        assert_eq!(client.healthy_endpoints(), vec![dead.clone(), live.clone()]);
+
+        assert_eq!(client.get_head().await.unwrap().number, 3);
+        assert!(dead_hits.load(Ordering::SeqCst) >= 1);
+        assert_eq!(live_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(client.healthy_endpoints(), vec![live.clone()]);
+
+        // Within the cooldown the dead endpoint is not contacted at all.
+        let before = dead_hits.load(Ordering::SeqCst);
+        client.get_head().await.unwrap();
+        assert_eq!(dead_hits.load(Ordering::SeqCst), before);
+        assert_eq!(live_hits.load(Ordering::SeqCst), 2);
+
+        // After it, the primary is tried again first.
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(client.healthy_endpoints().len(), 2);
+        client.get_head().await.unwrap();
+        assert!(dead_hits.load(Ordering::SeqCst) > before);
+
+        assert!(NodeClient::with_endpoints(vec![], client.config().clone()).is_err());
+    }
+
+    #[tokio::test]
+    async fn await_receipt_confirmed_backs_off_and_detects_reorg() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        // Head advances one block per `chain.getHead`; block 10 stays `0xaa`
+        // until `canonical` is switched.
+        let head = Arc::new(AtomicU64::new(10));
+        let canonical = Arc::new(Mutex::new("0xaa".to_string()));
+        let (h, c) = (head.clone(), canonical.clone());
+        let (rpc_url, _hits) = serve_rpc(move |method, _| match method {
+            "tx.getTransactionReceipt" => json!({
+                "tx_hash": "0xabc",
+                "status": "SUCCESS",
+                "gas_used": 21_000,
+                "block_number": 10,
+                "block_hash": "0xaa",
+            }),
+            "chain.getBlockByHeight" => json!({"header": {"hash": c.lock().unwrap().clone(), "number": 10}}),
+            "chain.getHead" => {
+                let n = h.fetch_add(1, Ordering::SeqCst);
+                json!({"number": n, "hash": format!("0x{n:02x}"), "timestamp": 0})
+            }
+            _ => serde_json::Value::Null,
+        });
+        let clock = MockClock::new();
+        let client = NodeClient::new(test_config(rpc_url)).unwrap().with_clock(clock.clone());
+
+        // Heads 10, 11, 12, 13: three sleeps of 250ms, 500ms, 1s.
+        let r = client.await_receipt_confirmed("0xabc", 3, Duration::from_secs(60)).await.unwrap();
+        assert_eq!(r.block_number, Some(10));
+        assert_eq!(clock.elapsed(), Duration::from_millis(1_750));
+
+        *canonical.lock().unwrap() = "0xbb".into();
+        let err = client.await_receipt_confirmed("0xabc", 100, Duration::from_secs(60)).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ReceiptError>(),
+            Some(&ReceiptError::Reorged { tx_hash: "0xabc".into(), block_number: 10, block_hash: "0xaa".into() })
+        );
     }
 }
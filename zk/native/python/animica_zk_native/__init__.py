@@ -16,6 +16,7 @@ Exposed functions (stable API):
 - version_info() -> dict[str, Any]
 - pairing_product_check_bytes(pairs: list[tuple[bytes, bytes]]) -> bool
 - kzg_verify_opening_bytes(commit_g1, proof_g1, z_fr, y_fr, g2_gen, g2_tau) -> bool
+- groth16_verify_bytes(vk, public_inputs, proof) -> bool   # native only
 - sizes() -> dict[str,int]   # only when native is present; raises otherwise
 """
 
@@ -176,6 +177,24 @@ def kzg_verify_opening_bytes(
     )
 
 
+def groth16_verify_bytes(
+    vk: bytes,
+    public_inputs: Iterable[bytes],
+    proof: bytes,
+) -> bool:
+    """
+    Groth16 verification over BN254: `vk` is an ark-groth16 verifying key,
+    `public_inputs` are canonical Fr bytes, `proof` is `a:G1 || b:G2 || c:G1`
+    (all canonical uncompressed ark-serialize). Native only; raises
+    RuntimeError if the extension is not present.
+    """
+    if _core is None or not hasattr(_core, "groth16_verify_bytes_py"):
+        raise RuntimeError("groth16_verify_bytes() is only available with native extension")
+    return bool(
+        _core.groth16_verify_bytes_py(vk, list(public_inputs), proof)  # type: ignore[attr-defined]
+    )
+
+
 def sizes() -> dict[str, int]:
     """
     Introspect canonical ark-serialize sizes for G1/G2/Fr (native only).
@@ -198,6 +217,7 @@ __all__ = [
     "version_info",
     "pairing_product_check_bytes",
     "kzg_verify_opening_bytes",
+    "groth16_verify_bytes",
     "sizes",
     "BANNER",
 ]
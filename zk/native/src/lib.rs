@@ -3,7 +3,7 @@
 //
 // animica_zk_native — optional native accelerators for zk verification.
 // Features:
-//   - "pairing": BN254 pairing helpers (Groth16-related)
+//   - "pairing": BN254 pairing helpers and a Groth16 verifier
 //   - "kzg":     Minimal KZG single-opening verification (BN254), implies "pairing"
 //   - "python":  Build Python extension module via pyo3 exposing fast paths
 //
@@ -28,10 +28,10 @@ use thiserror::Error;
 #[cfg(feature = "pairing")]
 use ark_bn254::{Bn254, G1Affine, G1Projective, G2Affine, G2Projective, Fr};
 #[cfg(feature = "pairing")]
-use ark_ec::pairing::Pairing;
+use ark_ec::pairing::{MillerLoopOutput, Pairing};
 #[cfg(feature = "pairing")]
 use ark_ec::CurveGroup;
-#[cfg(feature = "pairing")]
+#[cfg(feature = "kzg")]
 use ark_ff::{BigInteger, PrimeField};
 #[cfg(feature = "pairing")]
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -56,16 +56,63 @@ pub enum NativeError {
     Internal(String),
 }
 
-#[cfg(feature = "pairing")]
+#[cfg(feature = "kzg")]
 fn deser_g1(bytes: &[u8]) -> Result<G1Affine, NativeError> {
-    G1Affine::deserialize_uncompressed(bytes)
-        .map_err(|e| NativeError::Deserialize(format!("G1Affine: {e}")))
+    deser_g1_with(bytes, false)
 }
 
-#[cfg(feature = "pairing")]
+#[cfg(feature = "kzg")]
 fn deser_g2(bytes: &[u8]) -> Result<G2Affine, NativeError> {
-    G2Affine::deserialize_uncompressed(bytes)
-        .map_err(|e| NativeError::Deserialize(format!("G2Affine: {e}")))
+    deser_g2_with(bytes, false)
+}
+
+// Points are decoded unchecked and then validated here, so the guarantee does
+// not hinge on ark-serialize's validation mode: always on-curve, and in the
+// prime-order subgroup unless the caller vouches for the input
+// (`skip_subgroup_check`). Points that fail are `InvalidInput`, not
+// `Deserialize`.
+
+#[cfg(feature = "pairing")]
+fn deser_g1_with(mut bytes: &[u8], skip_subgroup_check: bool) -> Result<G1Affine, NativeError> {
+    read_g1(&mut bytes, skip_subgroup_check)
+}
+
+#[cfg(feature = "pairing")]
+fn deser_g2_with(mut bytes: &[u8], skip_subgroup_check: bool) -> Result<G2Affine, NativeError> {
+    read_g2(&mut bytes, skip_subgroup_check)
+}
+
+/// Decode one G1 point from the front of `r`, advancing it.
+#[cfg(feature = "pairing")]
+fn read_g1(r: &mut &[u8], skip_subgroup_check: bool) -> Result<G1Affine, NativeError> {
+    let p = G1Affine::deserialize_uncompressed_unchecked(&mut *r)
+        .map_err(|e| NativeError::Deserialize(format!("G1Affine: {e}")))?;
+    check_point(&p, "G1", skip_subgroup_check)?;
+    Ok(p)
+}
+
+/// Decode one G2 point from the front of `r`, advancing it.
+#[cfg(feature = "pairing")]
+fn read_g2(r: &mut &[u8], skip_subgroup_check: bool) -> Result<G2Affine, NativeError> {
+    let q = G2Affine::deserialize_uncompressed_unchecked(&mut *r)
+        .map_err(|e| NativeError::Deserialize(format!("G2Affine: {e}")))?;
+    check_point(&q, "G2", skip_subgroup_check)?;
+    Ok(q)
+}
+
+#[cfg(feature = "pairing")]
+fn check_point<P: ark_ec::short_weierstrass::SWCurveConfig>(
+    p: &ark_ec::short_weierstrass::Affine<P>,
+    group: &str,
+    skip_subgroup_check: bool,
+) -> Result<(), NativeError> {
+    if !p.is_on_curve() {
+        return Err(NativeError::InvalidInput(format!("{group} point not on curve")));
+    }
+    if !skip_subgroup_check && !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(NativeError::InvalidInput(format!("{group} point not in prime-order subgroup")));
+    }
+    Ok(())
 }
 
 #[cfg(feature = "pairing")]
@@ -74,23 +121,256 @@ fn deser_fr(bytes: &[u8]) -> Result<Fr, NativeError> {
         .map_err(|e| NativeError::Deserialize(format!("Fr: {e}")))
 }
 
+/// Deserialize `(G1, G2)` pairs and run the multi-Miller loop over them.
+#[cfg(feature = "pairing")]
+fn multi_miller_loop(pairs: &[(Vec<u8>, Vec<u8>)]) -> Result<MillerLoopOutput<Bn254>, NativeError> {
+    multi_miller_loop_with(pairs, false)
+}
+
+#[cfg(feature = "pairing")]
+fn multi_miller_loop_with(
+    pairs: &[(Vec<u8>, Vec<u8>)],
+    skip_subgroup_check: bool,
+) -> Result<MillerLoopOutput<Bn254>, NativeError> {
+    if pairs.is_empty() {
+        return Err(NativeError::InvalidInput("at least one pair required".into()));
+    }
+    let mut g1s = Vec::with_capacity(pairs.len());
+    let mut g2s = Vec::with_capacity(pairs.len());
+    for (g1b, g2b) in pairs {
+        g1s.push(deser_g1_with(g1b, skip_subgroup_check)?);
+        g2s.push(deser_g2_with(g2b, skip_subgroup_check)?);
+    }
+    Ok(Bn254::multi_miller_loop(g1s, g2s))
+}
+
+/// Final exponentiation of a Miller loop output, checked against the GT identity.
+#[cfg(feature = "pairing")]
+fn final_exp_is_identity(ml: MillerLoopOutput<Bn254>) -> Result<bool, NativeError> {
+    use ark_ff::One;
+    let gt = Bn254::final_exponentiation(ml)
+        .ok_or_else(|| NativeError::InvalidInput("miller loop output is zero".into()))?;
+    Ok(gt.0.is_one())
+}
+
 /// BN254 product pairing check:
 /// Returns `true` iff ∏ e(P_i, Q_i) == 1 in GT.
 /// Inputs are canonical uncompressed bytes for G1Affine / G2Affine pairs.
+///
+/// Every point must be on its curve and in the prime-order subgroup; see
+/// [`pairing_product_check_bytes_with`] to skip the subgroup check.
 #[cfg(feature = "pairing")]
 pub fn pairing_product_check_bytes(pairs: &[(Vec<u8>, Vec<u8>)]) -> Result<bool, NativeError> {
-    if pairs.is_empty() {
-        return Err(NativeError::InvalidInput("at least one pair required".into()));
+    final_exp_is_identity(multi_miller_loop(pairs)?)
+}
+
+/// [`pairing_product_check_bytes`] with an escape hatch: `skip_subgroup_check`
+/// drops the (G2-dominated) subgroup membership test for inputs already known
+/// to be valid, e.g. cached verifying-key points. On-curve is still checked.
+/// Never skip it for untrusted proof points.
+#[cfg(feature = "pairing")]
+pub fn pairing_product_check_bytes_with(
+    pairs: &[(Vec<u8>, Vec<u8>)],
+    skip_subgroup_check: bool,
+) -> Result<bool, NativeError> {
+    final_exp_is_identity(multi_miller_loop_with(pairs, skip_subgroup_check)?)
+}
+
+/// Miller-loop-only half of [`pairing_product_check_bytes`]: computes
+/// ∏ f_{P_i,Q_i} over the given pairs **without** the final exponentiation.
+///
+/// Serialization of the intermediate: the returned bytes are the canonical
+/// **uncompressed** ark-serialize encoding of the `Fq12` value wrapped by
+/// `MillerLoopOutput<Bn254>` (12 base-field limbs, 384 bytes). It is only
+/// meaningful as input to [`miller_loop_mul_bytes`] / [`final_exp_is_one`]; it
+/// is not a GT element and must not be compared directly.
+///
+/// Accumulating several batches with [`miller_loop_mul_bytes`] and finishing
+/// with one [`final_exp_is_one`] is equivalent to a single product check over
+/// all pairs, but pays for only one final exponentiation.
+#[cfg(feature = "pairing")]
+pub fn miller_loop_bytes(pairs: &[(Vec<u8>, Vec<u8>)]) -> Result<Vec<u8>, NativeError> {
+    let ml = multi_miller_loop(pairs)?;
+    let mut out = Vec::new();
+    ml.0.serialize_uncompressed(&mut out)
+        .map_err(|e| NativeError::Internal(format!("Fq12 serialize: {e}")))?;
+    Ok(out)
+}
+
+#[cfg(feature = "pairing")]
+fn deser_miller_output(bytes: &[u8]) -> Result<MillerLoopOutput<Bn254>, NativeError> {
+    let f = <Bn254 as Pairing>::TargetField::deserialize_uncompressed(bytes)
+        .map_err(|e| NativeError::Deserialize(format!("Fq12: {e}")))?;
+    Ok(MillerLoopOutput(f))
+}
+
+/// Multiply two serialized Miller loop outputs (see [`miller_loop_bytes`]),
+/// returning the serialized product in the same format.
+#[cfg(feature = "pairing")]
+pub fn miller_loop_mul_bytes(a: &[u8], b: &[u8]) -> Result<Vec<u8>, NativeError> {
+    let prod = deser_miller_output(a)?.0 * deser_miller_output(b)?.0;
+    let mut out = Vec::new();
+    prod.serialize_uncompressed(&mut out)
+        .map_err(|e| NativeError::Internal(format!("Fq12 serialize: {e}")))?;
+    Ok(out)
+}
+
+/// Final-exponentiation-only half of [`pairing_product_check_bytes`]: returns
+/// `true` iff the serialized Miller loop output (see [`miller_loop_bytes`])
+/// exponentiates to the GT identity.
+#[cfg(feature = "pairing")]
+pub fn final_exp_is_one(ml_bytes: &[u8]) -> Result<bool, NativeError> {
+    final_exp_is_identity(deser_miller_output(ml_bytes)?)
+}
+
+/// Groth16 verifying key over BN254.
+///
+/// Wire format (`from_bytes`) is ark-groth16's canonical **uncompressed**
+/// `VerifyingKey<Bn254>` encoding: `alpha_g1 ‖ beta_g2 ‖ gamma_g2 ‖ delta_g2 ‖
+/// len:u64-LE ‖ gamma_abc_g1[len]`, where `gamma_abc_g1` has one more entry
+/// than there are public inputs.
+#[cfg(feature = "pairing")]
+#[derive(Clone, Debug)]
+pub struct Groth16Vk {
+    alpha_g1: G1Affine,
+    beta_g2: G2Affine,
+    gamma_g2: G2Affine,
+    delta_g2: G2Affine,
+    gamma_abc_g1: Vec<G1Affine>,
+}
+
+#[cfg(feature = "pairing")]
+impl Groth16Vk {
+    /// Decode and validate (on-curve, subgroup) a verifying key.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, NativeError> {
+        let r = &mut bytes;
+        let alpha_g1 = read_g1(r, false)?;
+        let beta_g2 = read_g2(r, false)?;
+        let gamma_g2 = read_g2(r, false)?;
+        let delta_g2 = read_g2(r, false)?;
+        let len = u64::deserialize_uncompressed(&mut *r)
+            .map_err(|e| NativeError::Deserialize(format!("gamma_abc_g1 length: {e}")))?;
+        let gamma_abc_g1 = (0..len).map(|_| read_g1(r, false)).collect::<Result<Vec<_>, _>>()?;
+        if gamma_abc_g1.is_empty() {
+            return Err(NativeError::InvalidInput("vk has no gamma_abc_g1 points".into()));
+        }
+        if !r.is_empty() {
+            return Err(NativeError::InvalidInput(format!("{} trailing bytes after vk", r.len())));
+        }
+        Ok(Self { alpha_g1, beta_g2, gamma_g2, delta_g2, gamma_abc_g1 })
     }
-    let mut prepared = Vec::with_capacity(pairs.len());
-    for (g1b, g2b) in pairs {
-        let p = deser_g1(g1b)?;
-        let q = deser_g2(g2b)?;
-        prepared.push((p, q));
+
+    /// Number of public inputs this key expects.
+    pub fn num_inputs(&self) -> usize {
+        self.gamma_abc_g1.len() - 1
+    }
+
+    /// `IC_0 + Σ x_i · IC_{i+1}` for serialized public inputs `x`, the sum
+    /// computed as one variable-base MSM over `IC_1..`.
+    fn linear_combination(&self, inputs: &[Vec<u8>]) -> Result<G1Affine, NativeError> {
+        use ark_ec::VariableBaseMSM;
+        if inputs.len() != self.num_inputs() {
+            return Err(NativeError::InvalidInput(format!(
+                "expected {} public inputs, got {}",
+                self.num_inputs(),
+                inputs.len()
+            )));
+        }
+        let scalars = inputs.iter().map(|x| deser_fr(x)).collect::<Result<Vec<_>, _>>()?;
+        let sum = G1Projective::msm(&self.gamma_abc_g1[1..], &scalars)
+            .map_err(|n| NativeError::Internal(format!("msm length mismatch: {n}")))?;
+        Ok((sum + self.gamma_abc_g1[0]).into_affine())
     }
-    let ml = Bn254::multi_miller_loop(prepared.iter().map(|(p, q)| (p, q)));
-    let gt = ml.final_exponentiation();
-    Ok(gt.is_one())
+}
+
+/// A [`Groth16Vk`] with its pairing-side constants precomputed: `e(α, β)` and
+/// the line coefficients (`G2Prepared`) of `-γ` and `-δ`.
+///
+/// Build it once per verifying key and reuse it with
+/// [`groth16_verify_prepared`]; each verification then only prepares the
+/// proof's own `B` and runs one 3-pair Miller loop.
+#[cfg(feature = "pairing")]
+#[derive(Clone, Debug)]
+pub struct PreparedVk {
+    vk: Groth16Vk,
+    alpha_beta: ark_ec::pairing::PairingOutput<Bn254>,
+    gamma_g2_neg: <Bn254 as Pairing>::G2Prepared,
+    delta_g2_neg: <Bn254 as Pairing>::G2Prepared,
+}
+
+#[cfg(feature = "pairing")]
+impl PreparedVk {
+    /// Precompute the pairing constants of `vk`.
+    pub fn new(vk: Groth16Vk) -> Self {
+        let alpha_beta = Bn254::pairing(vk.alpha_g1, vk.beta_g2);
+        let gamma_g2_neg = (-vk.gamma_g2).into();
+        let delta_g2_neg = (-vk.delta_g2).into();
+        Self { vk, alpha_beta, gamma_g2_neg, delta_g2_neg }
+    }
+
+    /// Decode a verifying key (see [`Groth16Vk::from_bytes`]) and prepare it.
+    pub fn from_bytes(vk_bytes: &[u8]) -> Result<Self, NativeError> {
+        Groth16Vk::from_bytes(vk_bytes).map(Self::new)
+    }
+
+    /// The underlying verifying key.
+    pub fn vk(&self) -> &Groth16Vk {
+        &self.vk
+    }
+}
+
+/// Decode a Groth16 proof: `a:G1 ‖ b:G2 ‖ c:G1`, canonical uncompressed.
+#[cfg(feature = "pairing")]
+fn deser_groth16_proof(mut bytes: &[u8]) -> Result<(G1Affine, G2Affine, G1Affine), NativeError> {
+    let r = &mut bytes;
+    let proof = (read_g1(r, false)?, read_g2(r, false)?, read_g1(r, false)?);
+    if !r.is_empty() {
+        return Err(NativeError::InvalidInput(format!("{} trailing bytes after proof", r.len())));
+    }
+    Ok(proof)
+}
+
+/// Verify a Groth16 proof over BN254:
+/// `e(A, B) == e(α, β) · e(L, γ) · e(C, δ)` with `L = IC_0 + Σ xᵢ·IC_{i+1}`.
+///
+/// `vk_bytes` as in [`Groth16Vk::from_bytes`]; `inputs` are canonical `Fr`
+/// bytes; `proof_bytes` is `a:G1 ‖ b:G2 ‖ c:G1` (uncompressed).
+/// Decodes and prepares the key on every call; when verifying many proofs
+/// under one key, use [`PreparedVk`] with [`groth16_verify_prepared`].
+#[cfg(feature = "pairing")]
+pub fn groth16_verify_bytes(
+    vk_bytes: &[u8],
+    inputs: &[Vec<u8>],
+    proof_bytes: &[u8],
+) -> Result<bool, NativeError> {
+    use ark_ff::One;
+    let vk = Groth16Vk::from_bytes(vk_bytes)?;
+    let (a, b, c) = deser_groth16_proof(proof_bytes)?;
+    let l = vk.linear_combination(inputs)?;
+    let out = Bn254::multi_pairing(
+        [a, (-vk.alpha_g1), (-l), (-c)],
+        [b, vk.beta_g2, vk.gamma_g2, vk.delta_g2],
+    );
+    Ok(out.0.is_one())
+}
+
+/// [`groth16_verify_bytes`] against a [`PreparedVk`], reusing its
+/// precomputed `e(α, β)` and prepared `-γ`, `-δ`.
+#[cfg(feature = "pairing")]
+pub fn groth16_verify_prepared(
+    pvk: &PreparedVk,
+    inputs: &[Vec<u8>],
+    proof_bytes: &[u8],
+) -> Result<bool, NativeError> {
+    let (a, b, c) = deser_groth16_proof(proof_bytes)?;
+    let l = pvk.vk.linear_combination(inputs)?;
+    let ml = Bn254::multi_miller_loop(
+        [a, l, c],
+        [b.into(), pvk.gamma_g2_neg.clone(), pvk.delta_g2_neg.clone()],
+    );
+    let out = Bn254::final_exponentiation(ml)
+        .ok_or_else(|| NativeError::InvalidInput("miller loop output is zero".into()))?;
+    Ok(out == pvk.alpha_beta)
 }
 
 /// Minimal KZG single-opening verification over BN254:
@@ -124,6 +404,7 @@ pub fn kzg_verify_opening_bytes(
     let y = deser_fr(y_fr)?;
     let g2_gen = deser_g2(g2_gen_bytes)?;
     let g2_tau = deser_g2(g2_tau_bytes)?;
+    use ark_ec::AffineRepr;
 
     // C - y*G1 (using the canonical G1 generator as [1] in SRS)
     let g1_gen = G1Affine::generator();
@@ -140,6 +421,126 @@ pub fn kzg_verify_opening_bytes(
     Ok(lhs == rhs)
 }
 
+/// Batch-verify KZG openings of many commitments at one shared point `z`.
+///
+/// `items` are `(C_i, π_i, y_i)` byte triples in the encodings used by
+/// [`kzg_verify_opening_bytes`]. Because every check shares the G2 side
+/// `G2^{τ} - z·G2`, it is computed once, and the per-item equations are folded
+/// with random coefficients `r_i` (`r_0 = 1`) into a single two-pairing check:
+///
+/// `e(Σ r_i·(C_i - y_i·G1), G2) == e(Σ r_i·π_i, G2^{τ} - z·G2)`
+///
+/// Returns `Ok(true)` iff every opening verifies, except with probability
+/// about `n / |Fr|` over the coefficients. A `false` result does not say
+/// which item failed; fall back to [`kzg_verify_opening_bytes`] for that.
+#[cfg(feature = "kzg")]
+pub fn kzg_verify_same_point(
+    items: &[(&[u8], &[u8], &[u8])],
+    z_fr: &[u8],
+    g2_gen_bytes: &[u8],
+    g2_tau_bytes: &[u8],
+) -> Result<bool, NativeError> {
+    use ark_ec::AffineRepr;
+    use ark_ff::{One, UniformRand, Zero};
+
+    if items.is_empty() {
+        return Err(NativeError::InvalidInput("at least one opening required".into()));
+    }
+    let z = deser_fr(z_fr)?;
+    let g2_gen = deser_g2(g2_gen_bytes)?;
+    let g2_tau = deser_g2(g2_tau_bytes)?;
+
+    // Shared G2^{τ} - z * G2, once for the whole batch.
+    let z_g2: G2Projective = g2_gen.mul_bigint(z.into_bigint());
+    let g2_tau_minus_z = (g2_tau.into_group() - z_g2).into_affine();
+
+    let mut rng = rand::thread_rng();
+    let mut commits = G1Projective::zero();
+    let mut proofs = G1Projective::zero();
+    let mut y_sum = Fr::zero();
+    for (i, (c, pi, y)) in items.iter().enumerate() {
+        let c = deser_g1(c)?;
+        let pi = deser_g1(pi)?;
+        let y = deser_fr(y)?;
+        let r = if i == 0 { Fr::one() } else { Fr::rand(&mut rng) };
+        commits += c * r;
+        proofs += pi * r;
+        y_sum += r * y;
+    }
+    let lhs = commits - G1Affine::generator() * y_sum;
+
+    // e(lhs, G2) · e(-Σ r·π, G2^{τ} - z·G2) == 1
+    let ml = Bn254::multi_miller_loop([lhs.into_affine(), (-proofs).into_affine()], [g2_gen, g2_tau_minus_z]);
+    final_exp_is_identity(ml)
+}
+
+/// One KZG opening for [`kzg_verify_batch_bytes`]: `(C, π, z, y)` bytes.
+#[cfg(feature = "kzg")]
+pub type KzgOpening<'a> = (&'a [u8], &'a [u8], &'a [u8], &'a [u8]);
+
+/// Batch-verify KZG openings of many commitments at their own points.
+///
+/// `openings` are `(C_i, π_i, z_i, y_i)` byte tuples in the encodings used by
+/// [`kzg_verify_opening_bytes`]. Rewriting each check as
+/// `e(C_i - y_i·G1 + z_i·π_i, G2) == e(π_i, G2^{τ})` and folding them with
+/// powers of the challenge `r` (`r^0 = 1`) leaves a single two-pair product,
+/// run through [`pairing_product_check_bytes`]:
+///
+/// `e(Σ r^i·(C_i - y_i·G1 + z_i·π_i), G2) · e(-Σ r^i·π_i, G2^{τ}) == 1`
+///
+/// Soundness: `r_challenge` (canonical `Fr` bytes, non-zero) must be sampled
+/// only after every `C_i`, `π_i`, `z_i`, `y_i` is fixed, e.g. as a
+/// Fiat-Shamir hash over all of them. A prover who knows `r` in advance can
+/// pick invalid openings whose errors cancel in the sum. With an honest
+/// challenge a bad batch passes with probability at most about `n / |Fr|`.
+/// A `false` result does not say which item failed.
+#[cfg(feature = "kzg")]
+pub fn kzg_verify_batch_bytes(
+    openings: &[KzgOpening<'_>],
+    g2_gen_bytes: &[u8],
+    g2_tau_bytes: &[u8],
+    r_challenge: &[u8],
+) -> Result<bool, NativeError> {
+    use ark_ec::AffineRepr;
+    use ark_ff::{One, Zero};
+
+    if openings.is_empty() {
+        return Err(NativeError::InvalidInput("at least one opening required".into()));
+    }
+    let r = deser_fr(r_challenge)?;
+    if r.is_zero() {
+        return Err(NativeError::InvalidInput("batch challenge must be non-zero".into()));
+    }
+
+    let mut lhs = G1Projective::zero();
+    let mut proofs = G1Projective::zero();
+    let mut y_sum = Fr::zero();
+    let mut r_pow = Fr::one();
+    for (c, pi, z, y) in openings {
+        let c = deser_g1(c)?;
+        let pi = deser_g1(pi)?;
+        let z = deser_fr(z)?;
+        let y = deser_fr(y)?;
+        lhs += c * r_pow + pi * (r_pow * z);
+        proofs += pi * r_pow;
+        y_sum += r_pow * y;
+        r_pow *= r;
+    }
+    lhs -= G1Affine::generator() * y_sum;
+
+    let g1_bytes = |p: G1Projective| -> Result<Vec<u8>, NativeError> {
+        let mut v = Vec::new();
+        p.into_affine()
+            .serialize_uncompressed(&mut v)
+            .map_err(|e| NativeError::Internal(format!("G1Affine serialize: {e}")))?;
+        Ok(v)
+    };
+    pairing_product_check_bytes(&[
+        (g1_bytes(lhs)?, g2_gen_bytes.to_vec()),
+        (g1_bytes(-proofs)?, g2_tau_bytes.to_vec()),
+    ])
+}
+
 #[cfg(feature = "python")]
 mod py {
     use super::*;
@@ -244,6 +645,32 @@ mod py {
         }
     }
 
+    /// Groth16 verification over BN254.
+    ///
+    /// Args:
+    ///   vk: ark-groth16 `VerifyingKey<Bn254>` bytes (canonical uncompressed)
+    ///   public_inputs: list of canonical `Fr` bytes, one per public input
+    ///   proof: `a:G1 ‖ b:G2 ‖ c:G1` bytes (canonical uncompressed)
+    /// Returns:
+    ///   bool — True iff the proof verifies.
+    #[pyfunction]
+    fn groth16_verify_bytes_py(vk: &PyAny, public_inputs: &PyAny, proof: &PyAny) -> PyResult<bool> {
+        if !cfg!(feature = "pairing") {
+            return Err(PyValueError::new_err("feature 'pairing' not enabled"));
+        }
+        let vk: Vec<u8> = vk.extract()?;
+        let inputs: Vec<Vec<u8>> = public_inputs.downcast::<PyList>()?.extract()?;
+        let proof: Vec<u8> = proof.extract()?;
+        #[cfg(feature = "pairing")]
+        {
+            groth16_verify_bytes(&vk, &inputs, &proof).map_err(py_err)
+        }
+        #[cfg(not(feature = "pairing"))]
+        {
+            Err(PyValueError::new_err("feature 'pairing' not enabled"))
+        }
+    }
+
     /// Convenience helpers for callers to introspect ark-serialize sizes.
     #[pyfunction]
     fn sizes(py: Python<'_>) -> PyResult<PyObject> {
@@ -279,6 +706,7 @@ mod py {
         m.add_function(wrap_pyfunction!(available, m)?)?;
         m.add_function(wrap_pyfunction!(pairing_product_check_bytes_py, m)?)?;
         m.add_function(wrap_pyfunction!(kzg_verify_opening_bytes_py, m)?)?;
+        m.add_function(wrap_pyfunction!(groth16_verify_bytes_py, m)?)?;
         m.add_function(wrap_pyfunction!(sizes, m)?)?;
         // Provide a minimal banner for quick smoke tests
         m.add("BANNER", PyBytes::new(py, b"animica_zk_native: bn254 pairing/kzg accelerators"))?;
@@ -286,6 +714,299 @@ mod py {
     }
 }
 
+// ---- Tests -------------------------------------------------------------------
+
+#[cfg(all(test, feature = "pairing"))]
+mod tests {
+    use super::*;
+    use ark_ec::AffineRepr;
+
+    fn g1_bytes(k: u64) -> Vec<u8> {
+        let p = (G1Affine::generator().into_group() * Fr::from(k)).into_affine();
+        let mut v = Vec::new();
+        p.serialize_uncompressed(&mut v).unwrap();
+        v
+    }
+
+    fn g1_neg_bytes(k: u64) -> Vec<u8> {
+        let p = (-(G1Affine::generator().into_group() * Fr::from(k))).into_affine();
+        let mut v = Vec::new();
+        p.serialize_uncompressed(&mut v).unwrap();
+        v
+    }
+
+    fn g2_bytes(k: u64) -> Vec<u8> {
+        let q = (G2Affine::generator().into_group() * Fr::from(k)).into_affine();
+        let mut v = Vec::new();
+        q.serialize_uncompressed(&mut v).unwrap();
+        v
+    }
+
+    #[test]
+    fn split_miller_final_matches_combined_check() {
+        // e(6·G1, G2) · e(-2·G1, 3·G2) == 1
+        let valid = vec![(g1_bytes(6), g2_bytes(1)), (g1_neg_bytes(2), g2_bytes(3))];
+        // e(6·G1, G2) · e(-2·G1, 4·G2) != 1
+        let invalid = vec![(g1_bytes(6), g2_bytes(1)), (g1_neg_bytes(2), g2_bytes(4))];
+
+        for pairs in [&valid, &invalid] {
+            let combined = pairing_product_check_bytes(pairs).unwrap();
+
+            // Whole product in one Miller loop.
+            let ml = miller_loop_bytes(pairs).unwrap();
+            assert_eq!(ml.len(), 384);
+            assert_eq!(final_exp_is_one(&ml).unwrap(), combined);
+
+            // One Miller loop per pair, accumulated manually.
+            let a = miller_loop_bytes(&pairs[..1]).unwrap();
+            let b = miller_loop_bytes(&pairs[1..]).unwrap();
+            let acc = miller_loop_mul_bytes(&a, &b).unwrap();
+            assert_eq!(final_exp_is_one(&acc).unwrap(), combined);
+        }
+        assert!(pairing_product_check_bytes(&valid).unwrap());
+        assert!(!pairing_product_check_bytes(&invalid).unwrap());
+    }
+
+    /// A point on the G2 twist that is *not* in the prime-order subgroup
+    /// (G2 has a large cofactor, so almost any x yields one).
+    fn g2_off_subgroup_bytes() -> Vec<u8> {
+        use ark_bn254::Fq2;
+        let q = (1u64..)
+            .find_map(|x| {
+                G2Affine::get_point_from_x_unchecked(Fq2::from(x), false)
+                    .filter(|q| !q.is_in_correct_subgroup_assuming_on_curve())
+            })
+            .unwrap();
+        assert!(q.is_on_curve());
+        let mut v = Vec::new();
+        q.serialize_uncompressed(&mut v).unwrap();
+        v
+    }
+
+    #[test]
+    fn rejects_off_subgroup_and_off_curve_points() {
+        let bad_q = g2_off_subgroup_bytes();
+        let pairs = vec![(g1_bytes(1), bad_q.clone())];
+        let err = pairing_product_check_bytes(&pairs).unwrap_err();
+        assert!(matches!(err, NativeError::InvalidInput(ref m) if m.contains("subgroup")), "{err}");
+        // Trusted-input escape hatch still decodes and runs the pairing.
+        assert!(pairing_product_check_bytes_with(&pairs, true).is_ok());
+
+        // Generator is (1, 2); bumping x to 2 leaves the curve.
+        let mut off_curve = g1_bytes(1);
+        off_curve[0] ^= 0x03;
+        let pairs = vec![(off_curve, g2_bytes(1))];
+        for skip in [false, true] {
+            let err = pairing_product_check_bytes_with(&pairs, skip).unwrap_err();
+            assert!(matches!(err, NativeError::InvalidInput(ref m) if m.contains("not on curve")), "{err}");
+        }
+    }
+
+    /// A valid Groth16 instance built from known discrete logs: every group
+    /// element is `s·G`, and `B` is solved so the verification equation holds.
+    /// Returns `(vk_bytes, proof_bytes, inputs)` for `n` public inputs.
+    fn groth16_fixture(n: u64) -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>) {
+        use ark_ff::Field;
+        let g1 = |s: Fr| (G1Affine::generator() * s).into_affine();
+        let g2 = |s: Fr| (G2Affine::generator() * s).into_affine();
+        let (alpha, beta, gamma, delta) = (Fr::from(3u64), Fr::from(5u64), Fr::from(7u64), Fr::from(11u64));
+        let ic: Vec<Fr> = (0..=n).map(|i| Fr::from(13 + 4 * i)).collect();
+        let xs: Vec<Fr> = (0..n).map(|i| Fr::from(23 + 6 * i)).collect();
+        let l = ic[0] + xs.iter().zip(&ic[1..]).map(|(x, s)| *x * s).sum::<Fr>();
+        let (a, c) = (Fr::from(31u64), Fr::from(37u64));
+        let b = (alpha * beta + l * gamma + c * delta) * a.inverse().unwrap();
+
+        let mut vk = Vec::new();
+        g1(alpha).serialize_uncompressed(&mut vk).unwrap();
+        for s in [beta, gamma, delta] {
+            g2(s).serialize_uncompressed(&mut vk).unwrap();
+        }
+        (ic.len() as u64).serialize_uncompressed(&mut vk).unwrap();
+        for s in ic {
+            g1(s).serialize_uncompressed(&mut vk).unwrap();
+        }
+        let mut proof = Vec::new();
+        g1(a).serialize_uncompressed(&mut proof).unwrap();
+        g2(b).serialize_uncompressed(&mut proof).unwrap();
+        g1(c).serialize_uncompressed(&mut proof).unwrap();
+        let inputs = xs
+            .iter()
+            .map(|x| {
+                let mut v = Vec::new();
+                x.serialize_uncompressed(&mut v).unwrap();
+                v
+            })
+            .collect();
+        (vk, proof, inputs)
+    }
+
+    #[test]
+    fn groth16_prepared_matches_unprepared() {
+        let (vk, proof, inputs) = groth16_fixture(2);
+        let pvk = PreparedVk::from_bytes(&vk).unwrap();
+        assert_eq!(pvk.vk().num_inputs(), 2);
+
+        let mut wrong_input = inputs.clone();
+        wrong_input[1] = inputs[0].clone();
+        let mut wrong_proof = proof.clone();
+        wrong_proof[..64].copy_from_slice(&g1_bytes(32));
+
+        for (p, xs, want) in [(&proof, &inputs, true), (&proof, &wrong_input, false), (&wrong_proof, &inputs, false)] {
+            assert_eq!(groth16_verify_bytes(&vk, xs, p).unwrap(), want);
+            assert_eq!(groth16_verify_prepared(&pvk, xs, p).unwrap(), want);
+        }
+
+        let err = groth16_verify_prepared(&pvk, &inputs[..1], &proof).unwrap_err();
+        assert!(matches!(err, NativeError::InvalidInput(_)), "{err}");
+        assert!(PreparedVk::from_bytes(&vk[..vk.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn groth16_msm_over_many_and_no_inputs() {
+        for n in [0, 1, 9] {
+            let (vk, proof, inputs) = groth16_fixture(n);
+            assert!(groth16_verify_bytes(&vk, &inputs, &proof).unwrap(), "n = {n}");
+            if n > 0 {
+                let mut bumped = inputs.clone();
+                bumped[n as usize - 1][0] ^= 1;
+                assert!(!groth16_verify_bytes(&vk, &bumped, &proof).unwrap(), "n = {n}");
+            }
+        }
+    }
+
+    /// Produced by ark-groth16 0.4 (`circuit_specific_setup` + `prove`, `StdRng`
+    /// seed 2288) for `y = x³ + x + 5` with private `x = 3` and public `y = 35`.
+    const ARK_VK: &str = concat!(
+        "e5399991056b0eb078e1df67bbb31a44e9a4264d0a70352b0038e6f8f2075a1b2fea660bcec3e2305cf822a7a8d7a0f5",
+        "fd43454d907fc1d1670d3fb1ffb6fa9aeff8419807c42080324fe098b0243df2bb5f965789cde3367a447ab69be5b420",
+        "3d68e4fe9eeb61390051a39bfdb78518c0c22019416038914cc0521f6e570a1b352aa73f71f0f3a55ae3a9e6e983d955",
+        "961d35e4a5d8e32cbe6379daf81aa02693836796c6f07033631f2c200676d8aa1d5ca82628075f1d140791bfeed9ac11",
+        "69aee0aa409f39718d6f2ffe97cd37dc54cf3af284b34565346a97201fb2622d85020550bc85d951ea365fe3aeed1b81",
+        "a080b2bdddf46193f3d46e283e092b053c88cf1ca864ca100e2601e6b383d12dc0d440c2ac76afd7686d07eaba689622",
+        "8f10ddd63651428b2c477582de15bcffdb82dd5b98a6c21f1e1d14e3b38861062895e55f64ff3a29af656971a2d8698b",
+        "5f71181ebbb389b1371f11814dbdfa17090fd5d7f88f3e20796d1c1fc733f877a27816c9f36a6204a7c312094edb3319",
+        "1af8e820d2bcbeedef26936ddb5bd07f21605589155d2c06ab0ca24f39609117c315c054eab85981930d910c437d837c",
+        "c29a1b48868be47c47fb9bd30b21b59f0200000000000000c96681bf6ceec47f144559a4fddc540cae0d606e4063aeee",
+        "1d0a42393e2f122982ca05eab0290f6351a9eeae3fd65333404c7640eca47dfdb5e0e2342173d1039003250ed7399a67",
+        "c439ca52244a8601c5d2df01026e6222439bbdd3f070bb20f884c39f1b4a473e684de74e763fd02499bc0ef69b95bd1c",
+        "2f5d696ceae539a8",
+    );
+    const ARK_PROOF: &str = concat!(
+        "d42c4dd66b2a808f921ad90854cea301b9a01680cd6ca19d318fe462f3df5f0c125cd7bdf86c1449ed4f301d7c709090",
+        "7bd4618b9b9f276021d90a35fa380babd190681840f5ea022fcc7292ca9653ce98c9a07ea918d11f026e0236e3588100",
+        "129c53534aafad712fae1be26e4211f5b3c6372234da9644012c09900294ae23029d6cfa59b31e30f5019c4b1c861f95",
+        "e6f140e7fd09d827a83b3c0e0cbd8d2194e0f2ca9a9c8413620e1f2a91324d21099a1569beba39720c249e5a682e9c12",
+        "b4a11c3f6436b02006eb382c52bc5ae3c4ccd829e22a5aa12825a2ac1ec86407b823afe4f3c2a31a3b584f3a01ea33ad",
+        "5dbe487b44c84b99922672f08ba78aa6",
+    );
+    const ARK_INPUT: &str = "2300000000000000000000000000000000000000000000000000000000000000";
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn groth16_accepts_ark_groth16_proof() {
+        let (vk, proof, input) = (unhex(ARK_VK), unhex(ARK_PROOF), unhex(ARK_INPUT));
+        let pvk = PreparedVk::from_bytes(&vk).unwrap();
+        assert_eq!(pvk.vk().num_inputs(), 1);
+        assert!(groth16_verify_bytes(&vk, &[input.clone()], &proof).unwrap());
+        assert!(groth16_verify_prepared(&pvk, &[input.clone()], &proof).unwrap());
+
+        let mut wrong = input;
+        wrong[0] ^= 1; // y = 34
+        assert!(!groth16_verify_bytes(&vk, &[wrong.clone()], &proof).unwrap());
+        assert!(!groth16_verify_prepared(&pvk, &[wrong], &proof).unwrap());
+    }
+
+    /// Openings of `p_i(X) = a_i + b_i·X` at `z` under the trapdoor `τ`:
+    /// `C = p(τ)·G1`, `π = b·G1` (the quotient is the constant `b`), `y = p(z)`.
+    #[cfg(feature = "kzg")]
+    fn kzg_fixture(z: Fr, tau: Fr, n: u64) -> Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        (0..n)
+            .map(|i| {
+                let (a, b) = (Fr::from(3 + i), Fr::from(7 + 2 * i));
+                let c = (G1Affine::generator() * (a + b * tau)).into_affine();
+                let pi = (G1Affine::generator() * b).into_affine();
+                (ser(&c), ser(&pi), ser(&(a + b * z)))
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "kzg")]
+    fn ser<T: CanonicalSerialize>(x: &T) -> Vec<u8> {
+        let mut v = Vec::new();
+        x.serialize_uncompressed(&mut v).unwrap();
+        v
+    }
+
+    #[cfg(feature = "kzg")]
+    #[test]
+    fn kzg_same_point_batch_matches_individual_checks() {
+        let (z, tau) = (Fr::from(5u64), Fr::from(1234u64));
+        let z_b = ser(&z);
+        let g2_gen = g2_bytes(1);
+        let g2_tau = ser(&(G2Affine::generator() * tau).into_affine());
+
+        let mut openings = kzg_fixture(z, tau, 4);
+        let batch = |o: &[(Vec<u8>, Vec<u8>, Vec<u8>)]| {
+            let items: Vec<_> = o.iter().map(|(c, p, y)| (&c[..], &p[..], &y[..])).collect();
+            kzg_verify_same_point(&items, &z_b, &g2_gen, &g2_tau).unwrap()
+        };
+        for (c, p, y) in &openings {
+            assert!(kzg_verify_opening_bytes(c, p, &z_b, y, &g2_gen, &g2_tau).unwrap());
+        }
+        assert!(batch(&openings));
+
+        // Corrupt one evaluation: the individual check and the batch both fail.
+        openings[2].2 = ser(&Fr::from(999u64));
+        let (c, p, y) = &openings[2];
+        assert!(!kzg_verify_opening_bytes(c, p, &z_b, y, &g2_gen, &g2_tau).unwrap());
+        assert!(!batch(&openings));
+
+        assert!(kzg_verify_same_point(&[], &z_b, &g2_gen, &g2_tau).is_err());
+    }
+
+    #[cfg(feature = "kzg")]
+    #[test]
+    fn kzg_batch_at_distinct_points() {
+        let tau = Fr::from(1234u64);
+        let g2_gen = g2_bytes(1);
+        let g2_tau = ser(&(G2Affine::generator() * tau).into_affine());
+        let r = ser(&Fr::from(987_654_321u64));
+
+        // Opening i is the i-th polynomial of `kzg_fixture`, opened at z = 5 + i.
+        let mut openings: Vec<_> = (0..4u64)
+            .map(|i| {
+                let z = Fr::from(5 + i);
+                let (c, p, y) = kzg_fixture(z, tau, i + 1).pop().unwrap();
+                (c, p, ser(&z), y)
+            })
+            .collect();
+        type Owned = (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>);
+        let batch = |o: &[Owned], r: &[u8]| {
+            let items: Vec<KzgOpening<'_>> = o.iter().map(|(c, p, z, y)| (&c[..], &p[..], &z[..], &y[..])).collect();
+            kzg_verify_batch_bytes(&items, &g2_gen, &g2_tau, r)
+        };
+        for (c, p, z, y) in &openings {
+            assert!(kzg_verify_opening_bytes(c, p, z, y, &g2_gen, &g2_tau).unwrap());
+        }
+        assert!(batch(&openings, &r).unwrap());
+
+        // Corrupt one opening's evaluation: the batch fails.
+        openings[1].3 = ser(&Fr::from(999u64));
+        assert!(!batch(&openings, &r).unwrap());
+
+        assert!(batch(&[], &r).is_err());
+        assert!(matches!(batch(&openings, &ser(&Fr::from(0u64))), Err(NativeError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn final_exp_rejects_malformed_bytes() {
+        assert!(matches!(final_exp_is_one(&[0u8; 10]), Err(NativeError::Deserialize(_))));
+    }
+}
+
 // ---- No-pyo3 fallback --------------------------------------------------------
 
 #[cfg(not(feature = "python"))]
@@ -297,7 +1018,11 @@ pub mod no_python {
     //!
     //! Rust callers can use:
     //!   - `pairing_product_check_bytes` (feature = "pairing")
-    //!   - `kzg_verify_opening_bytes` (feature = "kzg")
+    //!   - `miller_loop_bytes` / `miller_loop_mul_bytes` / `final_exp_is_one`
+    //!     (feature = "pairing")
+    //!   - `groth16_verify_bytes` / `groth16_verify_prepared` (feature = "pairing")
+    //!   - `kzg_verify_opening_bytes` / `kzg_verify_same_point` /
+    //!     `kzg_verify_batch_bytes` (feature = "kzg")
 
     // Intentionally empty – Rust APIs are available at crate root.
 }